@@ -0,0 +1,118 @@
+//! RPC interface for the renvm bridge module.
+
+use codec::Codec;
+use ecosystem_renvm_bridge_rpc_runtime_api::BurnInfo;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_client_api::ProofProvider;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as RenVmBridgeClient;
+pub use ecosystem_renvm_bridge_rpc_runtime_api::RenVmBridgeApi as RenVmBridgeRuntimeApi;
+
+/// The max number of burns returned in one request.
+pub const MAX_PENDING_BURNS: u32 = 1000;
+
+/// The pending burns at a block, with the storage proof of them against the
+/// state root of the block.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(serialize = "BlockHash: Serialize, BlockNumber: Serialize, Balance: std::fmt::Display"))]
+#[serde(bound(deserialize = "BlockHash: Deserialize<'de>, BlockNumber: Deserialize<'de>, Balance: std::str::FromStr"))]
+pub struct PendingBurns<BlockHash, BlockNumber, Balance> {
+	pub at: BlockHash,
+	pub burns: Vec<BurnInfo<BlockNumber, Balance>>,
+	pub proof: Vec<Bytes>,
+}
+
+#[rpc]
+pub trait RenVmBridgeApi<BlockHash, ResponseType> {
+	#[rpc(name = "renVmBridge_getPendingBurns")]
+	fn get_pending_burns(&self, from_nonce: u64, limit: u32, at: Option<BlockHash>) -> Result<ResponseType>;
+}
+
+/// A struct that implements the [`RenVmBridgeApi`].
+pub struct RenVmBridge<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> RenVmBridge<C, B> {
+	/// Create new `RenVmBridge` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		RenVmBridge {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	ProofError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::ProofError => 2,
+		}
+	}
+}
+
+impl<C, Block, BlockNumber, Balance>
+	RenVmBridgeApi<<Block as BlockT>::Hash, PendingBurns<<Block as BlockT>::Hash, BlockNumber, Balance>>
+	for RenVmBridge<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+	C::Api: RenVmBridgeRuntimeApi<Block, BlockNumber, Balance>,
+	BlockNumber: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_pending_burns(
+		&self,
+		from_nonce: u64,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<PendingBurns<<Block as BlockT>::Hash, BlockNumber, Balance>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let at = BlockId::hash(at_hash);
+
+		let burns = api
+			.get_pending_burns(&at, from_nonce, limit.min(MAX_PENDING_BURNS))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pending burns.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		let proof = self
+			.client
+			.read_proof(&at, &mut burns.iter().map(|burn| burn.storage_key.as_slice()))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::ProofError.into()),
+				message: "Unable to read storage proof of pending burns.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		Ok(PendingBurns {
+			at: at_hash,
+			burns,
+			proof: proof.iter_nodes().map(Into::into).collect(),
+		})
+	}
+}
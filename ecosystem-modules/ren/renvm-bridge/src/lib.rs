@@ -1,22 +1,31 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, weights::Weight};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::migration::StorageIterator,
+	traits::{EnsureOrigin, Get},
+	weights::Weight,
+	IterableStorageMap,
+};
 use frame_system::{self as system, ensure_none, ensure_signed};
-use orml_traits::BasicCurrency;
-use primitives::Balance;
+use primitives::{Balance, CurrencyId};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 use sp_runtime::{
+	traits::Saturating,
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchResult,
+	DispatchResult, RuntimeDebug,
 };
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+use support::BridgeSafety;
 
 mod mock;
 mod tests;
 
+pub type BurnNonce = u64;
+
 #[derive(Encode, Decode, Clone)]
 pub struct EcdsaSignature(pub [u8; 65]);
 
@@ -32,30 +41,103 @@ impl sp_std::fmt::Debug for EcdsaSignature {
 	}
 }
 
+/// The RenVM bridged asset
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RenVmAsset {
+	/// The RenVM token identifier
+	pub identifier: [u8; 32],
+	/// The addresses of RenVM public keys authorised to sign mints
+	pub signers: Vec<[u8; 20]>,
+	/// The number of signatures of signers required to mint
+	pub threshold: u32,
+	/// The max amount of a mint
+	pub mint_limit: Balance,
+	/// The max amount of a burn
+	pub burn_limit: Balance,
+	/// Whether mint is paused
+	pub mint_paused: bool,
+	/// Whether burn is paused
+	pub burn_paused: bool,
+}
+
+/// The details of a burn, indexed by its nonce
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BurnDetails<BlockNumber> {
+	/// The block number at which the burn occurred
+	pub block_number: BlockNumber,
+	pub currency_id: CurrencyId,
+	/// The recipient address on the origin chain
+	pub to: [u8; 20],
+	pub amount: Balance,
+}
+
 pub trait Trait: system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
-	type Currency: BasicCurrency<Self::AccountId, Balance = Balance>;
-	/// The RenVM split public key
-	type PublicKey: Get<[u8; 20]>;
-	/// The RenVM Currency identifier
-	type CurrencyIdentifier: Get<[u8; 32]>;
+
+	/// The bridge safety which mints and burns the RenVM assets
+	type BridgeSafety: BridgeSafety<Self::AccountId, CurrencyId, Balance>;
+
 	/// A configuration for base priority of unsigned transactions.
 	///
 	/// This is exposed so that it can be tuned for particular runtime, when
 	/// multiple modules send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
-	/// Record burn event details when burn occurs until x blocks have passed
+	/// Record burn details when burn occurs until x blocks have passed
 	type BurnEventStoreDuration: Get<Self::BlockNumber>;
+
+	/// The origin which may update the RenVM assets and rotate their signers
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The number of blocks the previous signers remain valid after rotation
+	type SignersOverlapDuration: Get<Self::BlockNumber>;
+
+	/// The asset bridged before multiple assets were supported, with its
+	/// RenVM token identifier and public key
+	type LegacyAsset: Get<(CurrencyId, [u8; 32], [u8; 20])>;
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Template {
-		/// Signature blacklist. This is required to prevent double claim.
+		/// Signed message hash blacklist. This is required to prevent double claim.
+		SignedMessages get(fn signed_messages): map hasher(identity) [u8; 32] => Option<()>;
+
+		/// The signatures of the mints made before `SignedMessages` was introduced. They
+		/// can not be converted to message hashes, so they are kept to prevent double claim.
 		Signatures get(fn signatures): map hasher(opaque_twox_256) EcdsaSignature => Option<()>;
 
-		/// Record burn event details
-		BurnEvents get(fn burn_events): map hasher(twox_64_concat) T::BlockNumber => Vec<([u8; 20], Balance)>
+		/// The registry of RenVM bridged assets
+		Assets get(fn assets): map hasher(twox_64_concat) CurrencyId => Option<RenVmAsset>;
+
+		/// The signers and threshold of assets before rotation, and the block
+		/// number until which they remain valid
+		PreviousSigners get(fn previous_signers): map hasher(twox_64_concat) CurrencyId => Option<(Vec<[u8; 20]>, u32, T::BlockNumber)>;
+
+		/// The burn details indexed by burn nonce
+		Burns get(fn burns): map hasher(twox_64_concat) BurnNonce => Option<BurnDetails<T::BlockNumber>>;
+
+		/// The nonce of the next burn
+		NextBurnNonce get(fn next_burn_nonce): BurnNonce;
+
+		/// The nonces of burns to remove at the block number
+		BurnExpiries get(fn burn_expiries): map hasher(twox_64_concat) T::BlockNumber => Vec<BurnNonce>;
+	}
+
+	add_extra_genesis {
+		config(assets): Vec<(CurrencyId, [u8; 32], Vec<[u8; 20]>, u32)>;
+		build(|config: &GenesisConfig| {
+			for (currency_id, identifier, signers, threshold) in config.assets.iter() {
+				Assets::insert(currency_id, RenVmAsset {
+					identifier: *identifier,
+					signers: signers.clone(),
+					threshold: *threshold,
+					mint_limit: Balance::max_value(),
+					burn_limit: Balance::max_value(),
+					mint_paused: false,
+					burn_paused: false,
+				});
+			}
+		})
 	}
 }
 
@@ -63,11 +145,24 @@ decl_event!(
 	pub enum Event<T> where
 		<T as system::Trait>::AccountId,
 		DestAddress = [u8; 20],
+		SignerAddress = [u8; 20],
 	{
-		/// Asset minted. \[owner, amount\]
-		Minted(AccountId, Balance),
-		/// Asset burnt in this chain \[owner, dest, amount\]
-		Burnt(AccountId, DestAddress, Balance),
+		/// Asset minted, large mints are delayed by the bridge safety.
+		/// \[currency_id, owner, amount\]
+		Minted(CurrencyId, AccountId, Balance),
+		/// Asset burnt in this chain \[burn_nonce, currency_id, owner, dest, amount\]
+		Burnt(BurnNonce, CurrencyId, AccountId, DestAddress, Balance),
+		/// Asset registered \[currency_id\]
+		AssetRegistered(CurrencyId),
+		/// Asset limits updated \[currency_id, mint_limit, burn_limit\]
+		AssetLimitsUpdated(CurrencyId, Balance, Balance),
+		/// Asset paused or unpaused \[currency_id, mint_paused, burn_paused\]
+		AssetPauseUpdated(CurrencyId, bool, bool),
+		/// Signers rotated, the previous signers remain valid for the overlap
+		/// duration \[currency_id, signers, threshold\]
+		SignersRotated(CurrencyId, Vec<SignerAddress>, u32),
+		/// Previous signers expired \[currency_id, signers\]
+		PreviousSignersExpired(CurrencyId, Vec<SignerAddress>),
 	}
 );
 
@@ -77,6 +172,24 @@ decl_error! {
 		InvalidMintSignature,
 		/// The mint signature has already been used.
 		SignatureAlreadyUsed,
+		/// The threshold is zero or more than the number of signers.
+		InvalidThreshold,
+		/// The asset is not registered.
+		AssetNotFound,
+		/// The asset has already been registered.
+		AssetAlreadyRegistered,
+		/// Mint of the asset is paused.
+		MintPaused,
+		/// Burn of the asset is paused.
+		BurnPaused,
+		/// The amount exceeds the mint limit of the asset.
+		ExceedMintLimit,
+		/// The amount exceeds the burn limit of the asset.
+		ExceedBurnLimit,
+		/// There are more signatures than the signers of the asset.
+		TooManySignatures,
+		/// The previous signers have not expired yet.
+		PreviousSignersNotExpired,
 	}
 }
 
@@ -86,6 +199,12 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		/// Register the asset bridged before multiple assets were supported,
+		/// and index its unexpired burn events by burn nonce.
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_legacy_asset().saturating_add(Self::migrate_burn_events())
+		}
+
 		/// Allow a user to mint if they have a valid signature from RenVM.
 		///
 		/// The dispatch origin of this call must be _None_.
@@ -94,32 +213,138 @@ decl_module! {
 		#[weight = 10_000]
 		fn mint(
 			origin,
+			currency_id: CurrencyId,
 			who: T::AccountId,
 			p_hash: [u8; 32],
 			#[compact] amount: Balance,
 			n_hash: [u8; 32],
-			sig: EcdsaSignature,
+			sigs: Vec<EcdsaSignature>,
 		) {
 			ensure_none(origin)?;
-			Self::do_mint(who, amount, sig)?;
+			let asset = Self::ensure_mint_allowed(currency_id, amount)?;
+			let message_hash = Encode::using_encoded(&who, |encoded| {
+				Self::signed_message_hash(&p_hash, amount, encoded, &n_hash, &asset.identifier)
+			});
+			Self::do_mint(currency_id, who, amount, message_hash)?;
 		}
 
 		/// Allow a user to burn assets.
 		#[weight = 10_000]
 		fn burn(
 			origin,
+			currency_id: CurrencyId,
 			to: [u8; 20],
 			#[compact] amount: Balance,
 		) {
 			let sender = ensure_signed(origin)?;
+			let asset = Self::assets(currency_id).ok_or(Error::<T>::AssetNotFound)?;
+			ensure!(!asset.burn_paused, Error::<T>::BurnPaused);
+			ensure!(amount <= asset.burn_limit, Error::<T>::ExceedBurnLimit);
 
-			T::Currency::withdraw(&sender, amount)?;
-			BurnEvents::<T>::append(
-				<frame_system::Module<T>>::block_number() + T::BurnEventStoreDuration::get(),
-				(to, amount),
+			T::BridgeSafety::burn(currency_id, &sender, amount)?;
+
+			let now = <frame_system::Module<T>>::block_number();
+			let burn_nonce = Self::next_burn_nonce();
+			<Burns<T>>::insert(burn_nonce, BurnDetails {
+				block_number: now,
+				currency_id,
+				to,
+				amount,
+			});
+			NextBurnNonce::put(burn_nonce + 1);
+			BurnExpiries::<T>::append(now + T::BurnEventStoreDuration::get(), burn_nonce);
+
+			Self::deposit_event(RawEvent::Burnt(burn_nonce, currency_id, sender, to, amount));
+		}
+
+		/// Register a RenVM bridged asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn register_asset(
+			origin,
+			currency_id: CurrencyId,
+			identifier: [u8; 32],
+			signers: Vec<[u8; 20]>,
+			threshold: u32,
+			#[compact] mint_limit: Balance,
+			#[compact] burn_limit: Balance,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Assets::contains_key(currency_id), Error::<T>::AssetAlreadyRegistered);
+			let signers = Self::checked_signers(signers, threshold)?;
+
+			Assets::insert(currency_id, RenVmAsset {
+				identifier,
+				signers,
+				threshold,
+				mint_limit,
+				burn_limit,
+				mint_paused: false,
+				burn_paused: false,
+			});
+			Self::deposit_event(RawEvent::AssetRegistered(currency_id));
+		}
+
+		/// Update the mint and burn limits of the asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn update_asset_limits(
+			origin,
+			currency_id: CurrencyId,
+			#[compact] mint_limit: Balance,
+			#[compact] burn_limit: Balance,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Assets::try_mutate(currency_id, |maybe_asset| -> DispatchResult {
+				let asset = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+				asset.mint_limit = mint_limit;
+				asset.burn_limit = burn_limit;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AssetLimitsUpdated(currency_id, mint_limit, burn_limit));
+		}
+
+		/// Pause or unpause mint and burn of the asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn set_asset_paused(origin, currency_id: CurrencyId, mint_paused: bool, burn_paused: bool) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Assets::try_mutate(currency_id, |maybe_asset| -> DispatchResult {
+				let asset = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+				asset.mint_paused = mint_paused;
+				asset.burn_paused = burn_paused;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AssetPauseUpdated(currency_id, mint_paused, burn_paused));
+		}
+
+		/// Rotate the signers authorised to sign mints of the asset. The
+		/// previous signers remain valid for `SignersOverlapDuration` blocks,
+		/// and the signers can't be rotated again until they expire.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn rotate_signers(origin, currency_id: CurrencyId, signers: Vec<[u8; 20]>, threshold: u32) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let signers = Self::checked_signers(signers, threshold)?;
+			ensure!(
+				!<PreviousSigners<T>>::contains_key(currency_id),
+				Error::<T>::PreviousSignersNotExpired,
 			);
 
-			Self::deposit_event(RawEvent::Burnt(sender, to, amount));
+			let expiry = <frame_system::Module<T>>::block_number() + T::SignersOverlapDuration::get();
+			Assets::try_mutate(currency_id, |maybe_asset| -> DispatchResult {
+				let asset = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+				<PreviousSigners<T>>::insert(currency_id, (asset.signers.clone(), asset.threshold, expiry));
+				asset.signers = signers.clone();
+				asset.threshold = threshold;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::SignersRotated(currency_id, signers, threshold));
 		}
 
 		/// dummy `on_initialize` to return the weight used in `on_finalize`.
@@ -128,20 +353,142 @@ decl_module! {
 		}
 
 		fn on_finalize(now: T::BlockNumber) {
-			BurnEvents::<T>::remove(now);
+			for burn_nonce in BurnExpiries::<T>::take(now) {
+				<Burns<T>>::remove(burn_nonce);
+			}
+
+			for (currency_id, (signers, _, expiry)) in <PreviousSigners<T>>::iter() {
+				if expiry <= now {
+					<PreviousSigners<T>>::remove(currency_id);
+					Self::deposit_event(RawEvent::PreviousSignersExpired(currency_id, signers));
+				}
+			}
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
-	fn do_mint(sender: T::AccountId, amount: Balance, sig: EcdsaSignature) -> DispatchResult {
-		T::Currency::deposit(&sender, amount)?;
-		Signatures::insert(&sig, ());
+	/// The burns with nonce in `[from_nonce, from_nonce + limit)` that have
+	/// not expired, together with the storage key of each burn, so that the
+	/// relayers can request their storage proof.
+	pub fn get_pending_burns(
+		from_nonce: BurnNonce,
+		limit: u32,
+	) -> Vec<(BurnNonce, BurnDetails<T::BlockNumber>, Vec<u8>)> {
+		let end = from_nonce.saturating_add(limit.into()).min(Self::next_burn_nonce());
+		(from_nonce..end)
+			.filter_map(|burn_nonce| {
+				Self::burns(burn_nonce).map(|details| (burn_nonce, details, <Burns<T>>::hashed_key_for(burn_nonce)))
+			})
+			.collect()
+	}
+
+	/// Register the legacy asset with its public key as the only signer, if
+	/// it is not registered yet.
+	fn migrate_legacy_asset() -> Weight {
+		let (currency_id, identifier, public_key) = T::LegacyAsset::get();
+		if Assets::contains_key(currency_id) {
+			return T::DbWeight::get().reads(1);
+		}
+
+		Assets::insert(
+			currency_id,
+			RenVmAsset {
+				identifier,
+				signers: vec![public_key],
+				threshold: 1,
+				mint_limit: Balance::max_value(),
+				burn_limit: Balance::max_value(),
+				mint_paused: false,
+				burn_paused: false,
+			},
+		);
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// Move the burn events of the legacy asset, which are grouped by their
+	/// expiry block number, to `Burns` and `BurnExpiries`. The nonces are
+	/// assigned in the order they expire.
+	fn migrate_burn_events() -> Weight {
+		let mut burn_events = StorageIterator::<Vec<([u8; 20], Balance)>>::new(b"Template", b"BurnEvents")
+			.drain()
+			.filter_map(|(key, events)| {
+				// the key is the twox 64 hash followed by the encoded block number
+				let expiry = key.get(8..).and_then(|mut raw| T::BlockNumber::decode(&mut raw).ok())?;
+				Some((expiry, events))
+			})
+			.collect::<Vec<_>>();
+		if burn_events.is_empty() {
+			return T::DbWeight::get().reads(1);
+		}
+		burn_events.sort_by_key(|(expiry, _)| *expiry);
+
+		let (currency_id, _, _) = T::LegacyAsset::get();
+		let first_burn_nonce = Self::next_burn_nonce();
+		let mut burn_nonce = first_burn_nonce;
+		let expiry_count = burn_events.len() as Weight;
+		for (expiry, events) in burn_events {
+			let block_number = expiry.saturating_sub(T::BurnEventStoreDuration::get());
+			let mut burn_nonces = Self::burn_expiries(expiry);
+			for (to, amount) in events {
+				<Burns<T>>::insert(
+					burn_nonce,
+					BurnDetails {
+						block_number,
+						currency_id,
+						to,
+						amount,
+					},
+				);
+				burn_nonces.push(burn_nonce);
+				burn_nonce = burn_nonce.saturating_add(1);
+			}
+			BurnExpiries::<T>::insert(expiry, burn_nonces);
+		}
+		let burn_count = burn_nonce.saturating_sub(first_burn_nonce) as Weight;
+		NextBurnNonce::put(burn_nonce);
 
-		Self::deposit_event(RawEvent::Minted(sender, amount));
+		// drain and write back the expiries, and write the burns
+		let writes = expiry_count.saturating_mul(2).saturating_add(burn_count);
+		T::DbWeight::get().reads_writes(
+			expiry_count.saturating_mul(2).saturating_add(2),
+			writes.saturating_add(1),
+		)
+	}
+
+	fn do_mint(
+		currency_id: CurrencyId,
+		sender: T::AccountId,
+		amount: Balance,
+		message_hash: [u8; 32],
+	) -> DispatchResult {
+		T::BridgeSafety::mint(currency_id, &sender, amount)?;
+		SignedMessages::insert(&message_hash, ());
+
+		Self::deposit_event(RawEvent::Minted(currency_id, sender, amount));
 		Ok(())
 	}
 
+	// Sort and dedup the signers, and check the threshold.
+	fn checked_signers(signers: Vec<[u8; 20]>, threshold: u32) -> Result<Vec<[u8; 20]>, Error<T>> {
+		let mut signers = signers;
+		signers.sort();
+		signers.dedup();
+		ensure!(
+			threshold > 0 && threshold as usize <= signers.len(),
+			Error::<T>::InvalidThreshold,
+		);
+		Ok(signers)
+	}
+
+	// Check that the asset is registered and mint of the amount is allowed.
+	fn ensure_mint_allowed(currency_id: CurrencyId, amount: Balance) -> Result<RenVmAsset, Error<T>> {
+		let asset = Self::assets(currency_id).ok_or(Error::<T>::AssetNotFound)?;
+		ensure!(!asset.mint_paused, Error::<T>::MintPaused);
+		ensure!(amount <= asset.mint_limit, Error::<T>::ExceedMintLimit);
+		Ok(asset)
+	}
+
 	// ABI-encode the values for creating the signature hash.
 	fn signable_message(p_hash: &[u8; 32], amount: u128, to: &[u8], n_hash: &[u8; 32], token: &[u8; 32]) -> Vec<u8> {
 		// p_hash ++ amount ++ token ++ to ++ n_hash
@@ -156,24 +503,66 @@ impl<T: Trait> Module<T> {
 		v
 	}
 
-	// Verify that the signature has been signed by RenVM.
-	fn verify_signature(
+	// The hash of the message signed by RenVM.
+	fn signed_message_hash(
+		p_hash: &[u8; 32],
+		amount: u128,
+		to: &[u8],
+		n_hash: &[u8; 32],
+		token: &[u8; 32],
+	) -> [u8; 32] {
+		keccak_256(&Self::signable_message(p_hash, amount, to, n_hash, token))
+	}
+
+	// Whether the recovered addresses include at least `threshold` of the
+	// signers. At least one signature is required.
+	fn is_signed_by(addresses: &BTreeSet<[u8; 20]>, signers: &[[u8; 20]], threshold: u32) -> bool {
+		let signed = signers.iter().filter(|signer| addresses.contains(*signer)).count();
+		signed >= threshold.max(1) as usize
+	}
+
+	// Verify that the signatures have been signed by the current signers of
+	// the asset, or by the previous signers within the overlap duration.
+	fn verify_signatures(
+		currency_id: CurrencyId,
 		p_hash: &[u8; 32],
 		amount: u128,
 		to: &[u8],
 		n_hash: &[u8; 32],
-		sig: &[u8; 65],
+		sigs: &[EcdsaSignature],
 	) -> DispatchResult {
-		let ren_btc_identifier = T::CurrencyIdentifier::get();
+		let asset = Self::assets(currency_id).ok_or(Error::<T>::AssetNotFound)?;
+		let now = <frame_system::Module<T>>::block_number();
+		let previous_signers = Self::previous_signers(currency_id).filter(|(_, _, expiry)| now < *expiry);
 
-		let signed_message_hash = keccak_256(&Self::signable_message(p_hash, amount, to, n_hash, &ren_btc_identifier));
-		let recoverd =
-			secp256k1_ecdsa_recover(&sig, &signed_message_hash).map_err(|_| Error::<T>::InvalidMintSignature)?;
-		let addr = &keccak_256(&recoverd)[12..];
+		// each signature costs a recovery, no more than one per signer is needed
+		let max_signatures = previous_signers
+			.as_ref()
+			.map_or(0, |(signers, _, _)| signers.len())
+			.max(asset.signers.len());
+		ensure!(sigs.len() <= max_signatures, Error::<T>::TooManySignatures);
 
-		ensure!(addr == T::PublicKey::get(), Error::<T>::InvalidMintSignature);
+		let signed_message_hash = Self::signed_message_hash(p_hash, amount, to, n_hash, &asset.identifier);
+		let mut addresses: BTreeSet<[u8; 20]> = BTreeSet::new();
+		for sig in sigs {
+			let recoverd =
+				secp256k1_ecdsa_recover(&sig.0, &signed_message_hash).map_err(|_| Error::<T>::InvalidMintSignature)?;
+			let mut addr = [0u8; 20];
+			addr.copy_from_slice(&keccak_256(&recoverd)[12..]);
+			addresses.insert(addr);
+		}
 
-		Ok(())
+		if Self::is_signed_by(&addresses, &asset.signers, asset.threshold) {
+			return Ok(());
+		}
+
+		if let Some((signers, threshold, _)) = previous_signers {
+			if Self::is_signed_by(&addresses, &signers, threshold) {
+				return Ok(());
+			}
+		}
+
+		Err(Error::<T>::InvalidMintSignature.into())
 	}
 }
 
@@ -182,14 +571,25 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 	type Call = Call<T>;
 
 	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-		if let Call::mint(who, p_hash, amount, n_hash, sig) = call {
+		if let Call::mint(currency_id, who, p_hash, amount, n_hash, sigs) = call {
+			let asset = match Self::ensure_mint_allowed(*currency_id, *amount) {
+				Ok(asset) => asset,
+				Err(_) => return InvalidTransaction::Call.into(),
+			};
+			if T::BridgeSafety::ensure_can_mint(*currency_id, *amount).is_err() {
+				return InvalidTransaction::Call.into();
+			}
+			let message_hash = Encode::using_encoded(&who, |encoded| {
+				Self::signed_message_hash(&p_hash, *amount, encoded, &n_hash, &asset.identifier)
+			});
+
 			// check if already exists
-			if Signatures::contains_key(&sig) {
+			if SignedMessages::contains_key(&message_hash) || sigs.iter().any(Signatures::contains_key) {
 				return InvalidTransaction::Stale.into();
 			}
 
 			let verify_result = Encode::using_encoded(&who, |encoded| -> DispatchResult {
-				Self::verify_signature(&p_hash, *amount, encoded, &n_hash, &sig.0)
+				Self::verify_signatures(*currency_id, &p_hash, *amount, encoded, &n_hash, &sigs)
 			});
 
 			// verify signature
@@ -199,7 +599,7 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 
 			ValidTransaction::with_tag_prefix("renvm-bridge")
 				.priority(T::UnsignedPriority::get())
-				.and_provides(sig)
+				.and_provides(message_hash)
 				.longevity(64_u64)
 				.propagate(true)
 				.build()
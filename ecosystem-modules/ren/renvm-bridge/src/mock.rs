@@ -4,7 +4,7 @@
 
 use super::*;
 use frame_support::{impl_outer_event, impl_outer_origin, parameter_types};
-use orml_currencies::BasicCurrencyAdapter;
+use frame_system::EnsureRoot;
 use primitives::{Amount, CurrencyId};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
@@ -12,6 +12,8 @@ use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
 pub type AccountId = H256;
 pub type BlockNumber = u64;
 
+pub const RENBTC: CurrencyId = CurrencyId::RENBTC;
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
 
@@ -29,6 +31,7 @@ impl_outer_event! {
 		orml_tokens<T>,
 		frame_system<T>,
 		pallet_balances<T>,
+		module_bridge_safety<T>,
 		renvm<T>,
 	}
 }
@@ -69,6 +72,7 @@ impl frame_system::Trait for Runtime {
 	type BaseCallFilter = ();
 	type SystemWeightInfo = ();
 }
+pub type System = frame_system::Module<Runtime>;
 
 parameter_types! {
 	pub const ExistentialDeposit: Balance = 0;
@@ -90,6 +94,12 @@ pub type Balances = pallet_balances::Module<Runtime>;
 parameter_types! {
 	pub const UnsignedPriority: u64 = 1 << 20;
 	pub const BurnEventStoreDuration: BlockNumber = 10;
+	pub const SignersOverlapDuration: BlockNumber = 10;
+	pub const LegacyAsset: (CurrencyId, [u8; 32], [u8; 20]) = (
+		RENBTC,
+		hex_literal::hex!["0000000000000000000000000a9add98c076448cbcfacf5e457da12ddbef4a8f"],
+		hex_literal::hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"],
+	);
 }
 
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
@@ -115,14 +125,31 @@ impl orml_currencies::Trait for Runtime {
 	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type WeightInfo = ();
 }
+pub type Currencies = orml_currencies::Module<Runtime>;
+
+parameter_types! {
+	pub const WindowLength: BlockNumber = 10;
+	pub const MintDelay: BlockNumber = 5;
+}
+
+impl module_bridge_safety::Trait for Runtime {
+	type Event = TestEvent;
+	type Currency = Currencies;
+	type WindowLength = WindowLength;
+	type MintDelay = MintDelay;
+	type GuardianOrigin = EnsureRoot<AccountId>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+}
+pub type BridgeSafetyModule = module_bridge_safety::Module<Runtime>;
 
 impl Trait for Runtime {
 	type Event = TestEvent;
-	type Currency = BasicCurrencyAdapter<Runtime, Balances, i128, BlockNumber>;
-	type PublicKey = RenVmPublicKey;
-	type CurrencyIdentifier = RENBTCIdentifier;
+	type BridgeSafety = BridgeSafetyModule;
 	type UnsignedPriority = UnsignedPriority;
 	type BurnEventStoreDuration = BurnEventStoreDuration;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type SignersOverlapDuration = SignersOverlapDuration;
+	type LegacyAsset = LegacyAsset;
 }
 pub type RenVmBridge = Module<Runtime>;
 
@@ -136,9 +163,16 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
+
+		GenesisConfig {
+			assets: vec![(RENBTC, RENBTCIdentifier::get(), vec![RenVmPublicKey::get()], 1)],
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
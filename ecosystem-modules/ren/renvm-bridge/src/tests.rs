@@ -3,30 +3,48 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, traits::OnFinalize, unsigned::ValidateUnsigned};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::{OnFinalize, OnRuntimeUpgrade},
+	unsigned::ValidateUnsigned,
+	StorageHasher, Twox64Concat,
+};
 use hex_literal::hex;
-use mock::{AccountId, Balances, ExtBuilder, Origin, RenVmBridge, RenvmBridgeCall};
+use mock::{
+	AccountId, BridgeSafetyModule, Currencies, ExtBuilder, Origin, RenVmBridge, RenVmPublicKey, RenvmBridgeCall,
+	Runtime, System, TestEvent, RENBTC,
+};
 use sp_core::H256;
-use sp_runtime::transaction_validity::TransactionValidityError;
+use sp_runtime::{traits::BadOrigin, transaction_validity::TransactionValidityError};
 
 fn mint_ren_btc(
 	who: AccountId,
 	p_hash: [u8; 32],
 	amount: Balance,
 	n_hash: [u8; 32],
-	sig: EcdsaSignature,
+	sigs: Vec<EcdsaSignature>,
 ) -> Result<DispatchResult, TransactionValidityError> {
 	<RenVmBridge as ValidateUnsigned>::validate_unsigned(
 		TransactionSource::External,
-		&RenvmBridgeCall::mint(who.clone(), p_hash, amount, n_hash, sig.clone()),
+		&RenvmBridgeCall::mint(RENBTC, who.clone(), p_hash, amount, n_hash, sigs.clone()),
 	)?;
 
-	Ok(RenVmBridge::mint(Origin::none(), who, p_hash, amount, n_hash, sig))
+	Ok(RenVmBridge::mint(
+		Origin::none(),
+		RENBTC,
+		who,
+		p_hash,
+		amount,
+		n_hash,
+		sigs,
+	))
 }
 
 #[test]
 fn burn_works() {
 	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
 		let issuer: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
 		assert_ok!(
 			mint_ren_btc(
@@ -34,77 +52,162 @@ fn burn_works() {
 				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
 				5000,
 				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
-				EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"]),
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
 			)
 		);
-		assert_eq!(Balances::free_balance(issuer.clone()), 5000);
+		assert_eq!(Currencies::free_balance(RENBTC, &issuer), 5000);
 
 		let to: [u8; 20] = [0; 20];
-		assert_eq!(RenVmBridge::burn_events(10), vec![]);
-		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), to.clone(), 1000));
-		assert_eq!(Balances::free_balance(&issuer), 4000);
-		assert_eq!(RenVmBridge::burn_events(10), vec![(to.clone(), 1000)]);
+		assert_eq!(RenVmBridge::burn_expiries(11), vec![]);
+		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to.clone(), 1000));
+		assert_eq!(Currencies::free_balance(RENBTC, &issuer), 4000);
+		assert_eq!(
+			RenVmBridge::burns(0),
+			Some(BurnDetails {
+				block_number: 1,
+				currency_id: RENBTC,
+				to,
+				amount: 1000,
+			})
+		);
+		assert_eq!(RenVmBridge::next_burn_nonce(), 1);
+		assert_eq!(RenVmBridge::burn_expiries(11), vec![0]);
+
+		let burnt_event = TestEvent::renvm(RawEvent::Burnt(0, RENBTC, issuer.clone(), to, 1000));
+		assert!(System::events().iter().any(|record| record.event == burnt_event));
+
+		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to.clone(), 2000));
+		assert_eq!(Currencies::free_balance(RENBTC, &issuer), 2000);
+		assert_eq!(RenVmBridge::burns(1).map(|details| details.amount), Some(2000));
+		assert_eq!(RenVmBridge::next_burn_nonce(), 2);
+		assert_eq!(RenVmBridge::burn_expiries(11), vec![0, 1]);
+
+		RenVmBridge::on_finalize(11);
+		assert_eq!(RenVmBridge::burn_expiries(11), vec![]);
+		assert_eq!(RenVmBridge::burns(0), None);
+		assert_eq!(RenVmBridge::burns(1), None);
+		assert_eq!(RenVmBridge::next_burn_nonce(), 2);
+	});
+}
+
+#[test]
+fn get_pending_burns_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuer: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		assert_ok!(
+			mint_ren_btc(
+				issuer.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			)
+		);
 
-		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), to.clone(), 2000));
-		assert_eq!(Balances::free_balance(&issuer), 2000);
-		assert_eq!(RenVmBridge::burn_events(10), vec![(to.clone(), 1000), (to.clone(), 2000)]);
+		let to: [u8; 20] = [0; 20];
+		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 1000));
+		System::set_block_number(5);
+		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 2000));
+		assert_ok!(RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 500));
 
+		let pending_burns = RenVmBridge::get_pending_burns(1, 10);
+		assert_eq!(
+			pending_burns
+				.iter()
+				.map(|(nonce, details, _)| (*nonce, details.block_number, details.amount))
+				.collect::<Vec<_>>(),
+			vec![(1, 5, 2000), (2, 5, 500)]
+		);
+		assert_eq!(pending_burns[0].2, <Burns<Runtime>>::hashed_key_for(1));
+		assert_eq!(RenVmBridge::get_pending_burns(0, 2).len(), 2);
+		assert_eq!(RenVmBridge::get_pending_burns(3, 10), vec![]);
+
+		// expired burns are not pending
 		RenVmBridge::on_finalize(10);
-		assert_eq!(RenVmBridge::burn_events(10), vec![]);
+		assert_eq!(
+			RenVmBridge::get_pending_burns(0, 10)
+				.into_iter()
+				.map(|(nonce, _, _)| nonce)
+				.collect::<Vec<_>>(),
+			vec![1, 2]
+		);
 	});
 }
 
 #[test]
-fn verify_signature_works() {
-	assert_ok!(
-		RenVmBridge::verify_signature(
-			&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
-			5000,
-			&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
-			&hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
-			&hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"],
-		)
-	);
-
-	assert_ok!(
-		RenVmBridge::verify_signature(
-			&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
-			5000,
-			&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
-			&hex!["6d9b77f6070c8dd4e6e6ad2217d6aa6ef48a06e27a3c4a189e0a9f2c59db409e"],
-			&hex!["130bef45db4f2b7ccf2689cfd8214e7dbdeb4263de1c26bcd1c702ce4a4093b97d49c835f8225e52103047eef3feca2e41681ea5a27dc6ab84a26efc49f05f971b"],
-		)
-	);
-
-	assert_ok!(
-		RenVmBridge::verify_signature(
-			&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
-			6000,
-			&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
-			&hex!["7c5e9fad22654694c5bbbce509c2003b10cf90798cd84b1fb1851cdfba58d52e"],
-			&hex!["776abdea3287da906a5c72dd08f9be1b0a160374ae7045b028a17098f98970245d173aa73d1e8ae99adf23ccf92030e6c4a390c62952f1dffb37bbcfde4bef171b"],
-		)
-	);
-
-	assert_ok!(
-		RenVmBridge::verify_signature(
-			&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
-			95000,
-			&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
-			&hex!["81e25aafbe2fb3ea02de043f5e13118c087a12d6871198cc97d160180fafcca2"],
-			&hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"],
-		)
-	);
-
-	assert_ok!(
-		RenVmBridge::verify_signature(
-			&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
-			5000,
-			&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
-			&hex!["6bed1f11a3d904a7e5b555a2524b2ce1a8bdbfa10f68dcb93f32b25c8df74c5a"],
-			&hex!["0a8167a494b8e3e0e45e50f9650537ebecefb688bc870777c6ef5f3722d932a516c3cced274b6550384eba9c59556083312dd5f1fdebcfadf0cb04a372207e271c"],
-		)
-	);
+fn verify_signatures_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				&[EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			)
+		);
+
+		assert_ok!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["6d9b77f6070c8dd4e6e6ad2217d6aa6ef48a06e27a3c4a189e0a9f2c59db409e"],
+				&[EcdsaSignature(hex!["130bef45db4f2b7ccf2689cfd8214e7dbdeb4263de1c26bcd1c702ce4a4093b97d49c835f8225e52103047eef3feca2e41681ea5a27dc6ab84a26efc49f05f971b"])],
+			)
+		);
+
+		assert_ok!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				6000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["7c5e9fad22654694c5bbbce509c2003b10cf90798cd84b1fb1851cdfba58d52e"],
+				&[EcdsaSignature(hex!["776abdea3287da906a5c72dd08f9be1b0a160374ae7045b028a17098f98970245d173aa73d1e8ae99adf23ccf92030e6c4a390c62952f1dffb37bbcfde4bef171b"])],
+			)
+		);
+
+		assert_ok!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				95000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["81e25aafbe2fb3ea02de043f5e13118c087a12d6871198cc97d160180fafcca2"],
+				&[EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"])],
+			)
+		);
+
+		assert_ok!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["6bed1f11a3d904a7e5b555a2524b2ce1a8bdbfa10f68dcb93f32b25c8df74c5a"],
+				&[EcdsaSignature(hex!["0a8167a494b8e3e0e45e50f9650537ebecefb688bc870777c6ef5f3722d932a516c3cced274b6550384eba9c59556083312dd5f1fdebcfadf0cb04a372207e271c"])],
+			)
+		);
+
+		// no more signatures than signers
+		assert_noop!(
+			RenVmBridge::verify_signatures(
+				RENBTC,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				&[
+					EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"]),
+					EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"]),
+				],
+			),
+			Error::<Runtime>::TooManySignatures
+		);
+	});
 }
 
 #[test]
@@ -118,11 +221,11 @@ fn mint_works() {
 				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
 				5000,
 				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
-				EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"]),
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
 			)
 		);
 
-		assert_eq!(Balances::free_balance(to.clone()), 5000);
+		assert_eq!(Currencies::free_balance(RENBTC, &to), 5000);
 
 		assert_ok!(
 			mint_ren_btc(
@@ -130,11 +233,11 @@ fn mint_works() {
 				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
 				95000,
 				hex!["81e25aafbe2fb3ea02de043f5e13118c087a12d6871198cc97d160180fafcca2"],
-				EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"]),
+				vec![EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"])],
 			)
 		);
 
-		assert_eq!(Balances::free_balance(to.clone()), 5000 + 95000);
+		assert_eq!(Currencies::free_balance(RENBTC, &to), 5000 + 95000);
 
 		assert_noop!(
 			mint_ren_btc(
@@ -142,20 +245,354 @@ fn mint_works() {
 				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
 				95000,
 				hex!["81e25aafbe2fb3ea02de043f5e13118c087a12d6871198cc97d160180fafcca2"],
-				EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"]),
+				vec![EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"])],
 			),
 			TransactionValidityError::Invalid(InvalidTransaction::Stale)
 		);
 
+		assert_noop!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				6000,
+				hex!["7c5e9fad22654694c5bbbce509c2003b10cf90798cd84b1fb1851cdfba58d52e"],
+				vec![EcdsaSignature(hex!["0000000000000000005c72dd08f9be1b0a160374ae7045b028a17098f98970245d173aa73d1e8ae99adf23ccf92030e6c4a390c62952f1dffb37bbcfde4bef171b"])],
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::BadProof)
+		);
+	});
+}
+
+#[test]
+fn reject_signatures_used_before_signed_messages() {
+	ExtBuilder::default().build().execute_with(|| {
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		let sig = EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"]);
+		Signatures::insert(&sig, ());
+
+		assert_noop!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![sig],
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::Stale)
+		);
+	});
+}
+
+#[test]
+fn rotate_signers_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		let new_signer: [u8; 20] = [1; 20];
+		assert_eq!(RenVmBridge::assets(RENBTC).unwrap().signers, vec![RenVmPublicKey::get()]);
+		assert_eq!(RenVmBridge::assets(RENBTC).unwrap().threshold, 1);
+
+		assert_noop!(
+			RenVmBridge::rotate_signers(Origin::signed(to.clone()), RENBTC, vec![new_signer], 1),
+			BadOrigin
+		);
+		assert_noop!(
+			RenVmBridge::rotate_signers(Origin::root(), RENBTC, vec![new_signer], 0),
+			Error::<Runtime>::InvalidThreshold
+		);
+		assert_noop!(
+			RenVmBridge::rotate_signers(Origin::root(), RENBTC, vec![new_signer, new_signer], 2),
+			Error::<Runtime>::InvalidThreshold
+		);
+
+		assert_ok!(RenVmBridge::rotate_signers(Origin::root(), RENBTC, vec![new_signer], 1));
+		let rotated_event = TestEvent::renvm(RawEvent::SignersRotated(RENBTC, vec![new_signer], 1));
+		assert!(System::events().iter().any(|record| record.event == rotated_event));
+		assert_eq!(RenVmBridge::assets(RENBTC).unwrap().signers, vec![new_signer]);
+		assert_eq!(
+			RenVmBridge::previous_signers(RENBTC),
+			Some((vec![RenVmPublicKey::get()], 1, 11))
+		);
+		assert_noop!(
+			RenVmBridge::rotate_signers(Origin::root(), RENBTC, vec![[2; 20]], 1),
+			Error::<Runtime>::PreviousSignersNotExpired
+		);
+
+		// the previous signers remain valid in the overlap duration
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			)
+		);
+		assert_eq!(Currencies::free_balance(RENBTC, &to), 5000);
+
+		RenVmBridge::on_finalize(11);
+		let expired_event = TestEvent::renvm(RawEvent::PreviousSignersExpired(RENBTC, vec![RenVmPublicKey::get()]));
+		assert!(System::events().iter().any(|record| record.event == expired_event));
+		assert_eq!(RenVmBridge::previous_signers(RENBTC), None);
+
 		assert_noop!(
 			mint_ren_btc(
 				to.clone(),
 				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
 				95000,
 				hex!["81e25aafbe2fb3ea02de043f5e13118c087a12d6871198cc97d160180fafcca2"],
-				EcdsaSignature(hex!["000000000000000000e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"]),
+				vec![EcdsaSignature(hex!["09f05f67a282e483d7e064ad1f2382dfedf6df11f55d42c86a47e6f54e0dd004280b395a923a8a60a93b6986217bb67adb4cc066ad4444dc28ec92d1de23b5f11b"])],
 			),
 			TransactionValidityError::Invalid(InvalidTransaction::BadProof)
 		);
+
+		// rotate again after the previous signers expired
+		assert_ok!(RenVmBridge::rotate_signers(Origin::root(), RENBTC, vec![[2; 20]], 1));
+		assert_eq!(
+			RenVmBridge::previous_signers(RENBTC),
+			Some((vec![new_signer], 1, 11))
+		);
+	});
+}
+
+#[test]
+fn signers_threshold_works() {
+	let signers: Vec<[u8; 20]> = vec![[1; 20], [2; 20], [3; 20]];
+	let mut addresses: BTreeSet<[u8; 20]> = BTreeSet::new();
+	assert!(!RenVmBridge::is_signed_by(&addresses, &signers, 0));
+
+	addresses.insert([1; 20]);
+	addresses.insert([4; 20]);
+	assert!(RenVmBridge::is_signed_by(&addresses, &signers, 1));
+	assert!(!RenVmBridge::is_signed_by(&addresses, &signers, 2));
+
+	addresses.insert([3; 20]);
+	assert!(RenVmBridge::is_signed_by(&addresses, &signers, 2));
+	assert!(!RenVmBridge::is_signed_by(&addresses, &signers, 3));
+}
+
+#[test]
+fn migrate_legacy_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = RenVmBridge::assets(RENBTC).unwrap();
+		Assets::remove(RENBTC);
+
+		RenVmBridge::on_runtime_upgrade();
+		assert_eq!(RenVmBridge::assets(RENBTC), Some(asset.clone()));
+		assert_eq!(asset.signers, vec![RenVmPublicKey::get()]);
+		assert_eq!(asset.threshold, 1);
+
+		// the registered asset is not overridden
+		assert_ok!(RenVmBridge::set_asset_paused(Origin::root(), RENBTC, true, false));
+		RenVmBridge::on_runtime_upgrade();
+		assert_eq!(RenVmBridge::assets(RENBTC).map(|asset| asset.mint_paused), Some(true));
+	});
+}
+
+#[test]
+fn migrate_burn_events_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(5);
+		NextBurnNonce::put(2);
+		BurnExpiries::<Runtime>::insert(13, vec![1]);
+		put_storage_value(
+			b"Template",
+			b"BurnEvents",
+			&Twox64Concat::hash(&13u64.encode()),
+			vec![([2; 20], 300u128)],
+		);
+		put_storage_value(
+			b"Template",
+			b"BurnEvents",
+			&Twox64Concat::hash(&12u64.encode()),
+			vec![([0; 20], 100u128), ([1; 20], 200u128)],
+		);
+
+		RenVmBridge::on_runtime_upgrade();
+		assert_eq!(RenVmBridge::next_burn_nonce(), 5);
+		assert_eq!(
+			RenVmBridge::burns(2),
+			Some(BurnDetails {
+				block_number: 2,
+				currency_id: RENBTC,
+				to: [0; 20],
+				amount: 100,
+			})
+		);
+		assert_eq!(RenVmBridge::burns(3).map(|details| details.to), Some([1; 20]));
+		assert_eq!(
+			RenVmBridge::burns(4),
+			Some(BurnDetails {
+				block_number: 3,
+				currency_id: RENBTC,
+				to: [2; 20],
+				amount: 300,
+			})
+		);
+		assert_eq!(RenVmBridge::burn_expiries(12), vec![2, 3]);
+		assert_eq!(RenVmBridge::burn_expiries(13), vec![1, 4]);
+		assert_eq!(
+			get_storage_value::<Vec<([u8; 20], Balance)>>(
+				b"Template",
+				b"BurnEvents",
+				&Twox64Concat::hash(&12u64.encode())
+			),
+			None
+		);
+
+		RenVmBridge::on_finalize(12);
+		assert_eq!(RenVmBridge::burns(2), None);
+		assert_eq!(RenVmBridge::burns(3), None);
+		assert_eq!(RenVmBridge::burns(4).is_some(), true);
+	});
+}
+
+#[test]
+fn asset_registry_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let issuer: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		let to: [u8; 20] = [0; 20];
+		let renzec_identifier: [u8; 32] = [1; 32];
+
+		assert_noop!(
+			RenVmBridge::register_asset(
+				Origin::signed(issuer.clone()),
+				CurrencyId::DOT,
+				renzec_identifier,
+				vec![RenVmPublicKey::get()],
+				1,
+				1000,
+				1000
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			RenVmBridge::register_asset(
+				Origin::root(),
+				RENBTC,
+				renzec_identifier,
+				vec![RenVmPublicKey::get()],
+				1,
+				1000,
+				1000
+			),
+			Error::<Runtime>::AssetAlreadyRegistered
+		);
+		assert_ok!(RenVmBridge::register_asset(
+			Origin::root(),
+			CurrencyId::DOT,
+			renzec_identifier,
+			vec![RenVmPublicKey::get()],
+			1,
+			1000,
+			1000
+		));
+		let registered_event = TestEvent::renvm(RawEvent::AssetRegistered(CurrencyId::DOT));
+		assert!(System::events().iter().any(|record| record.event == registered_event));
+		assert_eq!(RenVmBridge::assets(CurrencyId::DOT).unwrap().identifier, renzec_identifier);
+
+		// the signature of renBTC is not valid for other assets
+		assert_noop!(
+			RenVmBridge::verify_signatures(
+				CurrencyId::DOT,
+				&hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				&hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"],
+				&hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				&[EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			),
+			Error::<Runtime>::InvalidMintSignature
+		);
+
+		assert_ok!(RenVmBridge::update_asset_limits(Origin::root(), RENBTC, 4000, 1000));
+		let limits_event = TestEvent::renvm(RawEvent::AssetLimitsUpdated(RENBTC, 4000, 1000));
+		assert!(System::events().iter().any(|record| record.event == limits_event));
+		assert_noop!(
+			mint_ren_btc(
+				issuer.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::Call)
+		);
+
+		assert_ok!(RenVmBridge::update_asset_limits(Origin::root(), RENBTC, 5000, 1000));
+		assert_ok!(
+			mint_ren_btc(
+				issuer.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			)
+		);
+		assert_noop!(
+			RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 2000),
+			Error::<Runtime>::ExceedBurnLimit
+		);
+
+		assert_ok!(RenVmBridge::set_asset_paused(Origin::root(), RENBTC, true, true));
+		let paused_event = TestEvent::renvm(RawEvent::AssetPauseUpdated(RENBTC, true, true));
+		assert!(System::events().iter().any(|record| record.event == paused_event));
+		assert_noop!(
+			RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 1000),
+			Error::<Runtime>::BurnPaused
+		);
+		assert_noop!(
+			RenVmBridge::burn(Origin::signed(issuer.clone()), CurrencyId::ACA, to, 1000),
+			Error::<Runtime>::AssetNotFound
+		);
+		assert_noop!(
+			RenVmBridge::set_asset_paused(Origin::root(), CurrencyId::ACA, true, true),
+			Error::<Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn mint_and_burn_through_bridge_safety() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuer: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		let to: [u8; 20] = [0; 20];
+
+		assert_ok!(BridgeSafetyModule::set_limits(Origin::root(), RENBTC, 4000, 1000, 4000));
+		assert_noop!(
+			mint_ren_btc(
+				issuer.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::Call)
+		);
+
+		// large mint is delayed
+		assert_ok!(BridgeSafetyModule::set_limits(Origin::root(), RENBTC, 10000, 1000, 4000));
+		assert_ok!(
+			mint_ren_btc(
+				issuer.clone(),
+				hex!["c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"],
+				5000,
+				hex!["e96cc92771222bd8f674ddf4ef6a4264e38030e90380fb215cb145591ed803e9"],
+				vec![EcdsaSignature(hex!["1beaeea7cb5433659979ba0ba17bc0174c87b6208ea0fa82e1478a74b3ded5a27324239b8f0ef31f54cc56deb32bb8962803ecf399eac7ade08f291ae03f6a1f1c"])],
+			)
+		);
+		assert_eq!(Currencies::free_balance(RENBTC, &issuer), 0);
+		BridgeSafetyModule::on_finalize(5);
+		assert_eq!(Currencies::free_balance(RENBTC, &issuer), 5000);
+
+		assert_noop!(
+			RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 2000),
+			module_bridge_safety::Error::<Runtime>::ExceedBurnCap
+		);
+		assert_ok!(BridgeSafetyModule::pause(Origin::root(), RENBTC));
+		assert_noop!(
+			RenVmBridge::burn(Origin::signed(issuer.clone()), RENBTC, to, 1000),
+			module_bridge_safety::Error::<Runtime>::AssetPaused
+		);
 	});
 }
@@ -0,0 +1,46 @@
+//! Runtime API definition for accounts module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_std::prelude::*;
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FreeCallInfo {
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_str"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_str"))]
+	pub pallet_name: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_str"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_str"))]
+	pub function_name: Vec<u8>,
+	/// The number of remaining free calls in current period.
+	pub remaining: u32,
+}
+
+#[cfg(feature = "std")]
+fn serialize_as_str<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&String::from_utf8_lossy(bytes))
+}
+
+#[cfg(feature = "std")]
+fn deserialize_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	Ok(s.into_bytes())
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AccountsApi<AccountId> where
+		AccountId: Codec,
+	{
+		fn get_remaining_free_calls(
+			account: AccountId
+		) -> Vec<FreeCallInfo>;
+	}
+}
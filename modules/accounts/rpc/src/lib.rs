@@ -0,0 +1,73 @@
+//! RPC interface for the accounts module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_accounts_rpc_runtime_api::FreeCallInfo;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as AccountsClient;
+pub use module_accounts_rpc_runtime_api::AccountsApi as AccountsRuntimeApi;
+
+#[rpc]
+pub trait AccountsApi<BlockHash, AccountId> {
+	#[rpc(name = "accounts_getRemainingFreeCalls")]
+	fn get_remaining_free_calls(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<FreeCallInfo>>;
+}
+
+/// A struct that implements the [`AccountsApi`].
+pub struct Accounts<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Accounts<C, B> {
+	/// Create new `Accounts` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Accounts {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId> AccountsApi<<Block as BlockT>::Hash, AccountId> for Accounts<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AccountsRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn get_remaining_free_calls(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<FreeCallInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_remaining_free_calls(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get remaining free calls.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
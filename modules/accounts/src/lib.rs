@@ -9,15 +9,15 @@
 
 use codec::{Decode, Encode};
 use frame_support::{
-	decl_error, decl_module, decl_storage,
-	dispatch::{DispatchResult, Dispatchable},
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::{DispatchError, DispatchResult, Dispatchable},
 	ensure,
+	storage::migration::{remove_storage_prefix, StorageIterator},
 	traits::{
-		Currency, ExistenceRequirement, Get, Happened, Imbalance, LockIdentifier, OnKilledAccount, OnUnbalanced,
-		StoredMap, Time, WithdrawReason, WithdrawReasons,
+		Currency, EnsureOrigin, ExistenceRequirement, Get, GetCallMetadata, Happened, Imbalance, LockIdentifier,
+		OnKilledAccount, OnUnbalanced, StoredMap, Time, WithdrawReason, WithdrawReasons,
 	},
-	weights::{DispatchInfo, PostDispatchInfo},
-	IsSubType,
+	weights::{DispatchInfo, PostDispatchInfo, Weight},
 };
 use frame_system::{self as system, ensure_signed, AccountInfo};
 use orml_traits::{MultiCurrency, MultiLockableCurrency, MultiReservableCurrency, OnReceived};
@@ -31,7 +31,7 @@ use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointOperand, ModuleId,
+	FixedPointOperand, ModuleId, RuntimeDebug,
 };
 use sp_std::convert::Infallible;
 use sp_std::prelude::*;
@@ -42,6 +42,44 @@ mod tests;
 
 const ACCOUNTS_ID: LockIdentifier = *b"ACA/acct";
 
+/// The max length of the swap path to pay fee in non-native currency.
+pub const MAX_FEE_SWAP_PATH_LENGTH: usize = 3;
+
+/// The max number of calls a sponsor can allow.
+pub const MAX_SPONSORED_CALLS: usize = 32;
+
+/// The max length of the pallet name or function name of a call.
+pub const MAX_CALL_NAME_LENGTH: usize = 64;
+
+/// The pallet name and function name of a call.
+pub type CallName = (Vec<u8>, Vec<u8>);
+
+/// The sponsor which pays fees of the allowed calls for users.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SponsorInfo<BlockNumber> {
+	/// The calls whose fees are paid by the sponsor
+	pub allowed_calls: Vec<CallName>,
+	/// The max amount of fees paid for each user
+	pub quota_per_user: Balance,
+	/// The max amount of fees paid for all users
+	pub total_budget: Balance,
+	/// The amount of fees the sponsor has paid
+	pub budget_used: Balance,
+	/// The block number at which the sponsorship expires
+	pub expiry: BlockNumber,
+}
+
+/// The quota of free calls of a call type.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FreeCallQuota<Moment> {
+	/// The number of free calls per period
+	pub count: u32,
+	/// The period to count free calls
+	pub period: Moment,
+	/// The min deposit locked to enable the free calls
+	pub deposit: Balance,
+}
+
 type MomentOf<T> = <<T as Trait>::Time as Time>::Moment;
 type PalletBalanceOf<T> =
 	<<T as pallet_transaction_payment::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
@@ -50,14 +88,7 @@ type NegativeImbalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currenc
 >>::NegativeImbalance;
 
 pub trait Trait: system::Trait + pallet_transaction_payment::Trait + orml_currencies::Trait {
-	/// The number of fee transfer times per period.
-	type FreeTransferCount: Get<u8>;
-
-	/// The period to count free transfer.
-	type FreeTransferPeriod: Get<MomentOf<Self>>;
-
-	/// Deposit for free transfer service.
-	type FreeTransferDeposit: Get<Balance>;
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
 	/// All non-native currency ids in Acala.
 	type AllNonNativeCurrencyIds: Get<Vec<CurrencyId>>;
@@ -96,6 +127,21 @@ pub trait Trait: system::Trait + pallet_transaction_payment::Trait + orml_curren
 
 	/// The max slippage allowed when swap open account deposit or fee with DEX
 	type MaxSlippageSwapWithDEX: Get<Ratio>;
+
+	/// The origin which may update the quotas of free calls.
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The deposit locked by the accounts which enabled free transfers before
+	/// free calls, used to migrate them to free call deposits.
+	type FreeTransferDeposit: Get<Balance>;
+
+	/// The number of free transfers per period before free calls, used to
+	/// migrate the quota of free transfers.
+	type FreeTransferCount: Get<u32>;
+
+	/// The period to count free transfers before free calls, used to migrate
+	/// the quota of free transfers.
+	type FreeTransferPeriod: Get<MomentOf<Self>>;
 }
 
 decl_error! {
@@ -107,16 +153,80 @@ decl_error! {
 		NonZeroRefCount,
 		/// Account still has active reserved(include non-native token and native token beyond new account deposit)
 		StillHasActiveReserved,
+		/// The swap path to pay fee is invalid
+		InvalidFeeSwapPath,
+		/// The swap has no liquidity or its slippage is unacceptable
+		UnacceptableSwap,
+		/// The sponsor does not exist
+		SponsorNotFound,
+		/// The expiry of the sponsorship has passed
+		InvalidSponsorExpiry,
+		/// The sponsor allows too many calls
+		TooManySponsoredCalls,
+		/// The pallet name or function name of the call is too long
+		CallNameTooLong,
+		/// The deposit to enable free calls is zero
+		InvalidFreeCallDeposit,
+		/// The count, period or deposit of the free call quota is zero
+		InvalidFreeCallQuota,
+		/// The currency to liquidate balances into is not supported
+		InvalidLiquidationCurrency,
 	}
 }
 
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+	{
+		/// Sponsor registered or updated \[sponsor\]
+		SponsorRegistered(AccountId),
+		/// Sponsor unregistered \[sponsor\]
+		SponsorUnregistered(AccountId),
+		/// Fee paid by the sponsor \[sponsor, user, fee\]
+		FeeSponsored(AccountId, AccountId, Balance),
+		/// The quota of free calls updated \[call_name\]
+		FreeCallQuotaUpdated(CallName),
+		/// The quota of free calls removed \[call_name\]
+		FreeCallQuotaRemoved(CallName),
+		/// Balance sold when closing account \[who, supply_currency_id, supply_amount, target_currency_id, target_amount\]
+		BalanceLiquidated(AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// Balance can't be sold when closing account, it's transferred as it is \[who, currency_id, amount\]
+		BalanceNotLiquidated(AccountId, CurrencyId, Balance),
+		/// Liquidity can't be withdrawn from DEX when closing account \[who, currency_id, share_amount\]
+		LiquidityNotWithdrawn(AccountId, CurrencyId, Balance),
+	}
+);
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Accounts {
-		/// Mapping from account id to free transfer records, record moment when a transfer tx occurs.
-		LastFreeTransfers get(fn last_free_transfers): map hasher(twox_64_concat) T::AccountId => Vec<MomentOf<T>>;
+		/// Mapping from call name to the quota of free calls.
+		FreeCallQuotas get(fn free_call_quotas): map hasher(twox_64_concat) CallName => Option<FreeCallQuota<MomentOf<T>>>;
+
+		/// Free call records of the account, record moment when a free call occurs: account id, call name => moments
+		LastFreeCalls get(fn last_free_calls): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) CallName => Vec<MomentOf<T>>;
+
+		/// Mapping from account id to the deposit locked for free calls.
+		FreeCallDeposits get(fn free_call_deposits): map hasher(twox_64_concat) T::AccountId => Option<Balance>;
 
-		/// Mapping from account id to flag for free transfer.
-		FreeTransferEnabledAccounts get(fn free_transfer_enabled_accounts): map hasher(twox_64_concat) T::AccountId => Option<()>;
+		/// Mapping from sponsor account id to sponsor info.
+		Sponsors get(fn sponsors): map hasher(twox_64_concat) T::AccountId => Option<SponsorInfo<T::BlockNumber>>;
+
+		/// The amount of fees paid by the sponsor for the user, kept after the
+		/// sponsor unregisters: sponsor, user => fees
+		SponsoredFees get(fn sponsored_fees): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId => Balance;
+	}
+
+	add_extra_genesis {
+		config(free_call_quotas): Vec<(CallName, u32, MomentOf<T>, Balance)>;
+		build(|config: &GenesisConfig<T>| {
+			for (call_name, count, period, deposit) in config.free_call_quotas.iter() {
+				<FreeCallQuotas<T>>::insert(call_name, FreeCallQuota {
+					count: *count,
+					period: *period,
+					deposit: *deposit,
+				});
+			}
+		})
 	}
 }
 
@@ -124,14 +234,7 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
-		/// The number of fee transfer times per period.
-		const FreeTransferCount: u8 = T::FreeTransferCount::get();
-
-		/// The period to count free transfer.
-		const FreeTransferPeriod: MomentOf<T> = T::FreeTransferPeriod::get();
-
-		/// Deposit for free transfer service.
-		const FreeTransferDeposit: Balance = T::FreeTransferDeposit::get();
+		fn deposit_event() = default;
 
 		/// All non-native currency ids in Acala.
 		const AllNonNativeCurrencyIds: Vec<CurrencyId> = T::AllNonNativeCurrencyIds::get();
@@ -148,43 +251,124 @@ decl_module! {
 		/// The max slippage allowed when swap open account deposit or fee with DEX
 		const MaxSlippageSwapWithDEX: Ratio = T::MaxSlippageSwapWithDEX::get();
 
-		/// Freeze some native currency to be able to free transfer.
+		/// Migrate the accounts which enabled free transfers to free calls.
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_free_transfers()
+		}
+
+		/// Update or remove the quota of free calls of a call type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `call_name`: the pallet name and function name of the call.
+		/// - `quota`: the quota of free calls, None means remove the quota.
+		#[weight = 10_000]
+		fn set_free_call_quota(origin, call_name: CallName, quota: Option<FreeCallQuota<MomentOf<T>>>) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_valid_call_name(&call_name), Error::<T>::CallNameTooLong);
+			if let Some(quota) = quota {
+				ensure!(
+					quota.count > 0 && !quota.period.is_zero() && !quota.deposit.is_zero(),
+					Error::<T>::InvalidFreeCallQuota,
+				);
+				<FreeCallQuotas<T>>::insert(&call_name, quota);
+				Self::deposit_event(RawEvent::FreeCallQuotaUpdated(call_name));
+			} else {
+				<FreeCallQuotas<T>>::remove(&call_name);
+				Self::deposit_event(RawEvent::FreeCallQuotaRemoved(call_name));
+			}
+		}
+
+		/// Freeze some native currency to be able to make free calls whose
+		/// deposit of quota is not greater than it.
 		///
 		/// The dispatch origin of this call must be Signed.
+		///
+		/// - `deposit`: the amount of native currency to freeze.
 		#[weight = 10_000]
-		fn enable_free_transfer(origin) {
+		fn enable_free_calls(origin, #[compact] deposit: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
+				ensure!(!deposit.is_zero(), Error::<T>::InvalidFreeCallDeposit);
 				let native_currency_id = T::NativeCurrencyId::get();
-				let free_transfer_deposit = T::FreeTransferDeposit::get();
-				ensure!(<T as Trait>::Currency::free_balance(native_currency_id, &who) > free_transfer_deposit, Error::<T>::NotEnoughBalance);
-				<T as Trait>::Currency::set_lock(ACCOUNTS_ID, native_currency_id, &who, T::FreeTransferDeposit::get());
-				<FreeTransferEnabledAccounts<T>>::insert(who, ());
+				ensure!(<T as Trait>::Currency::free_balance(native_currency_id, &who) > deposit, Error::<T>::NotEnoughBalance);
+				<T as Trait>::Currency::set_lock(ACCOUNTS_ID, native_currency_id, &who, deposit);
+				<FreeCallDeposits<T>>::insert(who, deposit);
 				Ok(())
 			})?;
 		}
 
-		/// Unlock free transfer deposit.
+		/// Unlock free calls deposit.
 		///
 		/// The dispatch origin of this call must be Signed.
 		#[weight = 10_000]
-		fn disable_free_transfers(origin) {
+		fn disable_free_calls(origin) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
 				<T as Trait>::Currency::remove_lock(ACCOUNTS_ID, T::NativeCurrencyId::get(), &who);
-				<FreeTransferEnabledAccounts<T>>::remove(who);
+				<FreeCallDeposits<T>>::remove(who);
 				Ok(())
 			})?;
 		}
 
+		/// Register as a sponsor to pay fees of the allowed calls for users, or
+		/// update the sponsorship and keep the used budget.
+		///
+		/// The dispatch origin of this call must be Signed.
+		///
+		/// - `allowed_calls`: the pallet name and function name of calls whose fees are paid.
+		/// - `quota_per_user`: the max amount of fees paid for each user.
+		/// - `total_budget`: the max amount of fees paid for all users.
+		/// - `expiry`: the block number at which the sponsorship expires.
+		#[weight = 10_000]
+		fn register_sponsor(
+			origin,
+			allowed_calls: Vec<CallName>,
+			#[compact] quota_per_user: Balance,
+			#[compact] total_budget: Balance,
+			expiry: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			ensure!(allowed_calls.len() <= MAX_SPONSORED_CALLS, Error::<T>::TooManySponsoredCalls);
+			ensure!(
+				allowed_calls.iter().all(Self::is_valid_call_name),
+				Error::<T>::CallNameTooLong,
+			);
+			ensure!(expiry > <system::Module<T>>::block_number(), Error::<T>::InvalidSponsorExpiry);
+
+			let budget_used = Self::sponsors(&who).map_or(Zero::zero(), |info| info.budget_used);
+			<Sponsors<T>>::insert(&who, SponsorInfo {
+				allowed_calls,
+				quota_per_user,
+				total_budget,
+				budget_used,
+				expiry,
+			});
+			Self::deposit_event(RawEvent::SponsorRegistered(who));
+		}
+
+		/// Stop paying fees for users. The fees paid for each user are kept, so
+		/// registering again does not reset the quotas of users.
+		///
+		/// The dispatch origin of this call must be Signed.
+		#[weight = 10_000]
+		fn unregister_sponsor(origin) {
+			let who = ensure_signed(origin)?;
+			ensure!(<Sponsors<T>>::contains_key(&who), Error::<T>::SponsorNotFound);
+			<Sponsors<T>>::remove(&who);
+			Self::deposit_event(RawEvent::SponsorUnregistered(who));
+		}
+
 		/// Kill self account from system.
 		///
 		/// The dispatch origin of this call must be Signed.
 		///
 		/// - `recipient`: the account as recipient to receive remaining currencies of the account will be killed,
 		///					None means no recipient is specified.
-		#[weight = 0]
-		fn close_account(origin, recipient: Option<T::AccountId>) {
+		/// - `liquidate_into`: the currency to sell all the non-native balances and liquidity of the account into
+		///					before closing, None means transfer them as they are.
+		#[weight = 10_000 + Module::<T>::close_account_weight(liquidate_into.is_some())]
+		fn close_account(origin, recipient: Option<T::AccountId>, liquidate_into: Option<CurrencyId>) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
 
@@ -205,6 +389,17 @@ decl_module! {
 					new_account_deposit >= total_reserved_native,
 					Error::<T>::StillHasActiveReserved,
 				);
+
+				// consolidate the balances into one currency before transfer
+				if let Some(target_currency_id) = liquidate_into {
+					ensure!(
+						target_currency_id == native_currency_id
+							|| T::AllNonNativeCurrencyIds::get().contains(&target_currency_id),
+						Error::<T>::InvalidLiquidationCurrency,
+					);
+					Self::liquidate_all(&who, target_currency_id);
+				}
+
 				let treasury_account = Self::treasury_account_id();
 				let recipient = recipient.unwrap_or_else(|| treasury_account.clone());
 
@@ -241,29 +436,270 @@ impl<T: Trait> Module<T> {
 		T::TreasuryModuleId::get().into_account()
 	}
 
-	/// Check if `who` could free transfer (but will not actually transfer),
-	/// if can transfer for free this time, record this moment.
-	pub fn try_record_free_transfer(who: &T::AccountId) -> bool {
-		let mut last_free_transfer = Self::last_free_transfers(who);
-		let now = T::Time::now();
-		let free_transfer_period = T::FreeTransferPeriod::get();
+	/// Get the pallet name and function name of the call.
+	pub fn call_name_of<Call: GetCallMetadata>(call: &Call) -> CallName {
+		let metadata = call.get_call_metadata();
+		(
+			metadata.pallet_name.as_bytes().to_vec(),
+			metadata.function_name.as_bytes().to_vec(),
+		)
+	}
+
+	/// Move the accounts which enabled free transfers to free call deposits
+	/// with `FreeTransferDeposit`, which is locked already, and drop the free
+	/// transfer records. If there were free transfers, the quota of
+	/// `Currencies::transfer` is seeded with the old free transfer settings
+	/// unless it is set already, so the migrated accounts keep them.
+	fn migrate_free_transfers() -> Weight {
+		let deposit = T::FreeTransferDeposit::get();
+		let mut migrated: Weight = 0;
+
+		for (key, _) in StorageIterator::<()>::new(b"Accounts", b"FreeTransferEnabledAccounts").drain() {
+			// the key is the twox 64 hash followed by the encoded account id
+			if let Some(who) = key.get(8..).and_then(|mut raw| T::AccountId::decode(&mut raw).ok()) {
+				<FreeCallDeposits<T>>::insert(who, deposit);
+			}
+			migrated = migrated.saturating_add(1);
+		}
+		remove_storage_prefix(b"Accounts", b"LastFreeTransfers", &[]);
+
+		let mut weight = T::DbWeight::get().reads_writes(migrated, migrated.saturating_mul(2).saturating_add(1));
+		let transfer: CallName = (b"Currencies".to_vec(), b"transfer".to_vec());
+		if !migrated.is_zero() && !<FreeCallQuotas<T>>::contains_key(&transfer) {
+			<FreeCallQuotas<T>>::insert(
+				transfer,
+				FreeCallQuota {
+					count: T::FreeTransferCount::get(),
+					period: T::FreeTransferPeriod::get(),
+					deposit,
+				},
+			);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+		weight
+	}
+
+	/// Whether the pallet name and function name of the call are not too
+	/// long.
+	fn is_valid_call_name(call_name: &CallName) -> bool {
+		call_name.0.len() <= MAX_CALL_NAME_LENGTH && call_name.1.len() <= MAX_CALL_NAME_LENGTH
+	}
+
+	/// Get the quota of the call type and the unexpired free call records of
+	/// `who`, `None` if `who` can't make free calls of the call type.
+	fn free_call_records(
+		who: &T::AccountId,
+		call_name: &CallName,
+	) -> Option<(FreeCallQuota<MomentOf<T>>, Vec<MomentOf<T>>)> {
+		let quota = Self::free_call_quotas(call_name)?;
+		let deposit = Self::free_call_deposits(who)?;
+		if deposit < quota.deposit {
+			return None;
+		}
 
 		// remove all the expired entries
-		last_free_transfer.retain(|&x| x.saturating_add(free_transfer_period) > now);
-
-		// check if can transfer for free
-		if <FreeTransferEnabledAccounts<T>>::contains_key(who)
-			&& last_free_transfer.len() < T::FreeTransferCount::get() as usize
-		{
-			// add entry to last_free_transfer
-			last_free_transfer.push(now);
-			<LastFreeTransfers<T>>::insert(who, last_free_transfer);
-			true
-		} else {
-			false
+		let now = T::Time::now();
+		let mut last_free_calls = Self::last_free_calls(who, call_name);
+		last_free_calls.retain(|&x| x.saturating_add(quota.period) > now);
+		Some((quota, last_free_calls))
+	}
+
+	/// Get the number of remaining free calls of the call type for `who` in
+	/// current period.
+	pub fn remaining_free_calls(who: &T::AccountId, call_name: &CallName) -> u32 {
+		Self::free_call_records(who, call_name).map_or(0, |(quota, last_free_calls)| {
+			quota.count.saturating_sub(last_free_calls.len() as u32)
+		})
+	}
+
+	/// Get the number of remaining free calls of all call types with quota
+	/// for `who`.
+	pub fn get_remaining_free_calls(who: &T::AccountId) -> Vec<(CallName, u32)> {
+		<FreeCallQuotas<T>>::iter()
+			.map(|(call_name, _)| {
+				let remaining = Self::remaining_free_calls(who, &call_name);
+				(call_name, remaining)
+			})
+			.collect()
+	}
+
+	/// Check if `who` could make the call for free (but will not actually
+	/// call), if can call for free this time, record this moment.
+	pub fn try_record_free_call(who: &T::AccountId, call_name: &CallName) -> bool {
+		match Self::free_call_records(who, call_name) {
+			Some((quota, mut last_free_calls)) if (last_free_calls.len() as u32) < quota.count => {
+				// add entry to last_free_calls
+				last_free_calls.push(T::Time::now());
+				<LastFreeCalls<T>>::insert(who, call_name, last_free_calls);
+				true
+			}
+			_ => false,
+		}
+	}
+
+	/// Whether the sponsor can pay `fee` of the call for `who`.
+	pub fn can_sponsor<Call: GetCallMetadata>(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		call: &Call,
+		fee: Balance,
+	) -> bool {
+		Self::sponsors(sponsor).map_or(false, |info| {
+			<system::Module<T>>::block_number() < info.expiry
+				&& info.allowed_calls.contains(&Self::call_name_of(call))
+				&& Self::sponsored_fees(sponsor, who).saturating_add(fee) <= info.quota_per_user
+				&& info.budget_used.saturating_add(fee) <= info.total_budget
+		})
+	}
+
+	/// Record the fee paid by the sponsor for `who`.
+	fn record_sponsored_fee(sponsor: &T::AccountId, who: &T::AccountId, fee: Balance) {
+		<Sponsors<T>>::mutate(sponsor, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.budget_used = info.budget_used.saturating_add(fee);
+			}
+		});
+		<SponsoredFees<T>>::mutate(sponsor, who, |fees| *fees = fees.saturating_add(fee));
+		Self::deposit_event(RawEvent::FeeSponsored(sponsor.clone(), who.clone(), fee));
+	}
+
+	/// Whether the path can be used to swap non-native currency to native
+	/// currency to pay fee.
+	fn is_valid_fee_swap_path(path: &[CurrencyId]) -> bool {
+		let native_currency_id = T::NativeCurrencyId::get();
+		path.len() >= 2
+			&& path.len() <= MAX_FEE_SWAP_PATH_LENGTH
+			&& path[0] != native_currency_id
+			&& path.last() == Some(&native_currency_id)
+	}
+
+	/// Get the amounts to supply at each step of the path to get
+	/// `target_amount` of the last currency in the path. Returns `None` if
+	/// any step has no liquidity or unacceptable slippage.
+	fn get_swap_amounts(path: &[CurrencyId], target_amount: Balance) -> Option<Vec<Balance>> {
+		let mut amounts = vec![target_amount; path.len()];
+		for i in (0..path.len() - 1).rev() {
+			let supply_amount = T::DEX::get_supply_amount(path[i], path[i + 1], amounts[i + 1]);
+			let is_slippage_acceptable = !supply_amount.is_zero()
+				&& T::DEX::get_exchange_slippage(path[i], path[i + 1], supply_amount)
+					.map_or(false, |s| s <= T::MaxSlippageSwapWithDEX::get());
+			if !is_slippage_acceptable {
+				return None;
+			}
+			amounts[i] = supply_amount;
+		}
+		Some(amounts)
+	}
+
+	/// Quote the fee in the first currency of the swap path, `fee` is in
+	/// native currency.
+	pub fn quote_fee(path: &[CurrencyId], fee: Balance) -> Option<Balance> {
+		if !Self::is_valid_fee_swap_path(path) {
+			return None;
+		}
+		if fee.is_zero() {
+			return Some(Zero::zero());
+		}
+		Self::get_swap_amounts(path, fee).map(|amounts| amounts[0])
+	}
+
+	/// Swap along the path to get `target_amount` native currency for `who`.
+	fn swap_for_fee(who: &T::AccountId, path: &[CurrencyId], target_amount: Balance) -> DispatchResult {
+		ensure!(Self::is_valid_fee_swap_path(path), Error::<T>::InvalidFeeSwapPath);
+		let amounts = Self::get_swap_amounts(path, target_amount).ok_or(Error::<T>::UnacceptableSwap)?;
+		ensure!(
+			<T as Trait>::Currency::free_balance(path[0], who) >= amounts[0],
+			Error::<T>::NotEnoughBalance
+		);
+
+		with_transaction_result(|| {
+			for (i, pair) in path.windows(2).enumerate() {
+				T::DEX::exchange_currency(who.clone(), pair[0], amounts[i], pair[1], amounts[i + 1])?;
+			}
+			Ok(())
+		})
+	}
+
+	/// The weight of `close_account`, which visits every non-native currency
+	/// to transfer it, and to withdraw its liquidity and sell it if the
+	/// balances are liquidated.
+	fn close_account_weight(liquidate: bool) -> Weight {
+		let currency_count = T::AllNonNativeCurrencyIds::get().len() as Weight;
+		// the reserved balance and the transfer of each currency
+		let mut per_currency = T::DbWeight::get().reads_writes(3, 2);
+		if liquidate {
+			// the shares, pools and balances of withdrawing liquidity and
+			// selling through DEX
+			per_currency = per_currency.saturating_add(T::DbWeight::get().reads_writes(12, 9));
+		}
+
+		// the ref count, the native reserve and transfer, and the account kill
+		T::DbWeight::get()
+			.reads_writes(5, 4)
+			.saturating_add(per_currency.saturating_mul(currency_count))
+	}
+
+	/// Withdraw all the liquidity of `who` from DEX and sell all the
+	/// non-native balances of `who` into `target_currency_id`. The positions
+	/// that can't be liquidated are kept and reported by events.
+	fn liquidate_all(who: &T::AccountId, target_currency_id: CurrencyId) {
+		let non_native_currency_ids = T::AllNonNativeCurrencyIds::get();
+
+		for currency_id in non_native_currency_ids.iter().copied() {
+			let share_amount = T::DEX::get_shares(currency_id, who);
+			if !share_amount.is_zero()
+				&& with_transaction_result(|| T::DEX::withdraw_liquidity(who.clone(), currency_id, share_amount))
+					.is_err()
+			{
+				Self::deposit_event(RawEvent::LiquidityNotWithdrawn(who.clone(), currency_id, share_amount));
+			}
+		}
+
+		for currency_id in non_native_currency_ids {
+			let supply_amount = <T as Trait>::Currency::free_balance(currency_id, who);
+			if currency_id == target_currency_id || supply_amount.is_zero() {
+				continue;
+			}
+
+			match Self::sell_for_liquidation(who, currency_id, supply_amount, target_currency_id) {
+				Ok(target_amount) => Self::deposit_event(RawEvent::BalanceLiquidated(
+					who.clone(),
+					currency_id,
+					supply_amount,
+					target_currency_id,
+					target_amount,
+				)),
+				Err(_) => Self::deposit_event(RawEvent::BalanceNotLiquidated(who.clone(), currency_id, supply_amount)),
+			}
 		}
 	}
 
+	/// Sell all the `supply_amount` of `supply_currency_id` into
+	/// `target_currency_id` through DEX, fails if the target amount is zero or
+	/// the slippage is unacceptable.
+	fn sell_for_liquidation(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		supply_amount: Balance,
+		target_currency_id: CurrencyId,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let target_amount = T::DEX::get_target_amount(supply_currency_id, target_currency_id, supply_amount);
+		let is_slippage_acceptable = !target_amount.is_zero()
+			&& T::DEX::get_exchange_slippage(supply_currency_id, target_currency_id, supply_amount)
+				.map_or(false, |s| s <= T::MaxSlippageSwapWithDEX::get());
+		ensure!(is_slippage_acceptable, Error::<T>::UnacceptableSwap);
+
+		with_transaction_result(|| {
+			T::DEX::exchange_currency(
+				who.clone(),
+				supply_currency_id,
+				supply_amount,
+				target_currency_id,
+				target_amount,
+			)
+		})
+	}
+
 	/// Open account by reserve native token.
 	///
 	/// If not enough free balance to reserve, all the balance would be
@@ -430,19 +866,27 @@ pub fn split_inner<T, R, S>(option: Option<T>, splitter: impl FnOnce(T) -> (R, S
 
 impl<T: Trait> OnKilledAccount<T::AccountId> for Module<T> {
 	fn on_killed_account(who: &T::AccountId) {
-		<LastFreeTransfers<T>>::remove(who);
+		<LastFreeCalls<T>>::remove_prefix(who);
+		<FreeCallDeposits<T>>::remove(who);
 	}
 }
 
 /// Require the transactor pay for themselves and maybe include a tip to gain
-/// additional priority in the queue.
+/// additional priority in the queue. The transactor can specify the swap path
+/// from a non-native currency to native currency to pay fee, which starts with
+/// the fee currency and ends with native currency. Or specify a sponsor to pay
+/// the fee of the transaction without tip.
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
-pub struct ChargeTransactionPayment<T: Trait + Send + Sync>(#[codec(compact)] PalletBalanceOf<T>);
+pub struct ChargeTransactionPayment<T: Trait + Send + Sync>(
+	#[codec(compact)] PalletBalanceOf<T>,
+	Option<Vec<CurrencyId>>,
+	Option<T::AccountId>,
+);
 
 impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeTransactionPayment<T> {
 	#[cfg(feature = "std")]
 	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
-		write!(f, "ChargeTransactionPayment<{:?}>", self.0)
+		write!(f, "ChargeTransactionPayment<{:?}, {:?}, {:?}>", self.0, self.1, self.2)
 	}
 	#[cfg(not(feature = "std"))]
 	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
@@ -452,12 +896,29 @@ impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeTransactionPayment<T>
 
 impl<T: Trait + Send + Sync> ChargeTransactionPayment<T>
 where
-	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<orml_currencies::Call<T>>,
+	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
 	PalletBalanceOf<T>: Send + Sync + FixedPointOperand,
 {
 	/// utility constructor. Used only in client/factory code.
 	pub fn from(fee: PalletBalanceOf<T>) -> Self {
-		Self(fee)
+		Self(fee, None, None)
+	}
+
+	/// utility constructor to pay fee in the first currency of the swap path.
+	/// Used only in client/factory code.
+	pub fn with_fee_swap_path(fee: PalletBalanceOf<T>, fee_swap_path: Vec<CurrencyId>) -> Self {
+		Self(fee, Some(fee_swap_path), None)
+	}
+
+	/// utility constructor to let the sponsor pay fee. Used only in
+	/// client/factory code.
+	pub fn with_sponsor(fee: PalletBalanceOf<T>, sponsor: T::AccountId) -> Self {
+		Self(fee, None, Some(sponsor))
+	}
+
+	/// The swap path to pay fee, if specified.
+	pub fn fee_swap_path(&self) -> Option<&[CurrencyId]> {
+		self.1.as_deref()
 	}
 
 	fn withdraw_fee(
@@ -466,16 +927,31 @@ where
 		call: &T::Call,
 		info: &DispatchInfoOf<T::Call>,
 		len: usize,
-	) -> Result<(PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
+	) -> Result<(PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>, Option<T::AccountId>), TransactionValidityError> {
 		// pay any fees.
 		let tip = self.0;
 
-		// check call type
-		let skip_pay_fee = match call.is_sub_type() {
-			// only orml_currencies::Call::transfer can be free for fee
-			Some(orml_currencies::Call::transfer(..)) => <Module<T>>::try_record_free_transfer(who),
-			_ => false,
-		};
+		// the sponsor only pays the fee of allowed calls without tip
+		if let Some(sponsor) = &self.2 {
+			let fee = <pallet_transaction_payment::Module<T>>::compute_fee(len as u32, info, tip);
+			ensure!(
+				tip.is_zero() && <Module<T>>::can_sponsor(sponsor, who, call, fee.unique_saturated_into()),
+				InvalidTransaction::Payment
+			);
+
+			return match <T as pallet_transaction_payment::Trait>::Currency::withdraw(
+				sponsor,
+				fee,
+				WithdrawReasons::from(WithdrawReason::TransactionPayment),
+				ExistenceRequirement::KeepAlive,
+			) {
+				Ok(imbalance) => Ok((fee, Some(imbalance), Some(sponsor.clone()))),
+				Err(_) => Err(InvalidTransaction::Payment.into()),
+			};
+		}
+
+		// only the call types with quota can be free for fee
+		let skip_pay_fee = <Module<T>>::try_record_free_call(who, &<Module<T>>::call_name_of(call));
 
 		let pay_fee = !skip_pay_fee;
 		let pay_tip = !tip.is_zero();
@@ -510,33 +986,32 @@ where
 			// try to use non-native currency to swap native currency by exchange with DEX
 			if !native_is_enough {
 				let native_currency_id = T::NativeCurrencyId::get();
-				let other_currency_ids = T::AllNonNativeCurrencyIds::get();
+				let balance_fee: Balance = fee.unique_saturated_into();
+				let free_native: Balance =
+					<T as pallet_transaction_payment::Trait>::Currency::free_balance(who).unique_saturated_into();
 				// Note: in fact, just obtain the gap between of fee and usable native currency
 				// amount, but `Currency` does not expose interface to get usable balance by
-				// specific reason. Here try to swap the whole fee by non-native currency.
-				let balance_fee: Balance = fee.unique_saturated_into();
+				// specific reason. Here swap the gap between fee and free native currency, or
+				// the whole fee if free native currency is enough but not usable.
+				let shortfall = if free_native < balance_fee {
+					balance_fee - free_native
+				} else {
+					balance_fee
+				};
 
-				// iterator non-native currencies to get enough fee
-				for currency_id in other_currency_ids {
-					let currency_amount = <T as Trait>::Currency::free_balance(currency_id, who);
-					let supply_amount_needed = T::DEX::get_supply_amount(currency_id, native_currency_id, balance_fee);
-
-					// the balance is sufficient and slippage is acceptable
-					if !supply_amount_needed.is_zero()
-						&& currency_amount >= supply_amount_needed
-						&& T::DEX::get_exchange_slippage(currency_id, native_currency_id, supply_amount_needed)
-							.map_or(false, |s| s <= T::MaxSlippageSwapWithDEX::get())
-						&& T::DEX::exchange_currency(
-							who.clone(),
-							currency_id,
-							supply_amount_needed,
-							native_currency_id,
-							balance_fee,
-						)
-						.is_ok()
-					{
-						// successfully swap, break iteration
-						break;
+				match self.fee_swap_path() {
+					// only use the specified fee currency
+					Some(path) => {
+						<Module<T>>::swap_for_fee(who, path, shortfall).map_err(|_| InvalidTransaction::Payment)?;
+					}
+					None => {
+						// iterator non-native currencies to get enough fee
+						for currency_id in T::AllNonNativeCurrencyIds::get() {
+							if <Module<T>>::swap_for_fee(who, &[currency_id, native_currency_id], shortfall).is_ok() {
+								// successfully swap, break iteration
+								break;
+							}
+						}
 					}
 				}
 			}
@@ -548,11 +1023,11 @@ where
 				reason,
 				ExistenceRequirement::KeepAlive,
 			) {
-				Ok(imbalance) => Ok((fee, Some(imbalance))),
+				Ok(imbalance) => Ok((fee, Some(imbalance), None)),
 				Err(_) => Err(InvalidTransaction::Payment.into()),
 			}
 		} else {
-			Ok((Zero::zero(), None))
+			Ok((Zero::zero(), None, None))
 		}
 	}
 }
@@ -560,7 +1035,7 @@ where
 impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T>
 where
 	PalletBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
-	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<orml_currencies::Call<T>>,
+	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
 {
 	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
 	type AccountId = T::AccountId;
@@ -571,6 +1046,7 @@ where
 		Self::AccountId,
 		Option<NegativeImbalanceOf<T>>,
 		PalletBalanceOf<T>,
+		Option<Self::AccountId>,
 	);
 
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
@@ -584,7 +1060,7 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (fee, _, _) = self.withdraw_fee(who, call, info, len)?;
 
 		let mut r = ValidTransaction::default();
 		// NOTE: we probably want to maximize the _fee (of any type) per weight unit
@@ -601,8 +1077,8 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee))
+		let (fee, imbalance, sponsor) = self.withdraw_fee(who, call, info, len)?;
+		Ok((self.0, who.clone(), imbalance, fee, sponsor))
 	}
 
 	fn post_dispatch(
@@ -612,13 +1088,15 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance, fee) = pre;
+		let (tip, who, imbalance, fee, sponsor) = pre;
 		if let Some(payed) = imbalance {
 			let actual_fee =
 				<pallet_transaction_payment::Module<T>>::compute_actual_fee(len as u32, info, post_info, tip);
 			let refund = fee.saturating_sub(actual_fee);
+			// refund to the sponsor if the fee is paid by it
+			let payer = sponsor.as_ref().unwrap_or(&who);
 			let actual_payment =
-				match <T as pallet_transaction_payment::Trait>::Currency::deposit_into_existing(&who, refund) {
+				match <T as pallet_transaction_payment::Trait>::Currency::deposit_into_existing(payer, refund) {
 					Ok(refund_imbalance) => {
 						// The refund cannot be larger than the up front payed max weight.
 						// `PostDispatchInfo::calc_unspent` guards against such a case.
@@ -631,6 +1109,9 @@ where
 					// is gone in that case.
 					Err(_) => payed,
 				};
+			if let Some(sponsor) = &sponsor {
+				<Module<T>>::record_sponsored_fee(sponsor, &who, actual_payment.peek().unique_saturated_into());
+			}
 			let imbalances = actual_payment.split(tip);
 
 			// distribute fee by `pallet_transaction_payment`
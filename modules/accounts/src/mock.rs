@@ -25,6 +25,10 @@ pub const ACA: CurrencyId = CurrencyId::ACA;
 pub const AUSD: CurrencyId = CurrencyId::AUSD;
 pub const BTC: CurrencyId = CurrencyId::XBTC;
 
+mod accounts {
+	pub use super::super::*;
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
 
@@ -46,6 +50,7 @@ impl_outer_event! {
 		pallet_balances<T>,
 		orml_currencies<T>,
 		dex<T>,
+		accounts<T>,
 	}
 }
 
@@ -219,20 +224,18 @@ impl dex::Trait for Runtime {
 pub type DEXModule = dex::Module<Runtime>;
 
 parameter_types! {
-	pub const FreeTransferCount: u8 = 3;
-	pub const FreeTransferPeriod: Moment = 100;
-	pub const FreeTransferDeposit: Balance = 200;
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![AUSD, BTC];
 	pub const NewAccountDeposit: Balance = 100;
 	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
+	pub const FreeTransferDeposit: Balance = 200;
+	pub const FreeTransferCount: u32 = 3;
+	pub const FreeTransferPeriod: Moment = 100;
 }
 
 impl Trait for Runtime {
-	type FreeTransferCount = FreeTransferCount;
-	type FreeTransferPeriod = FreeTransferPeriod;
+	type Event = TestEvent;
 	type Time = TimeModule;
-	type FreeTransferDeposit = FreeTransferDeposit;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type Currency = Currencies;
@@ -242,6 +245,10 @@ impl Trait for Runtime {
 	type NewAccountDeposit = NewAccountDeposit;
 	type TreasuryModuleId = TreasuryModuleId;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type FreeTransferDeposit = FreeTransferDeposit;
+	type FreeTransferCount = FreeTransferCount;
+	type FreeTransferPeriod = FreeTransferPeriod;
 }
 pub type Accounts = Module<Runtime>;
 
@@ -275,6 +282,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		GenesisConfig::<Runtime> {
+			free_call_quotas: vec![((b"Currencies".to_vec(), b"transfer".to_vec()), 3, 100, 200)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -5,67 +5,216 @@
 use super::*;
 use frame_support::{
 	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::OnRuntimeUpgrade,
 	weights::{DispatchClass, DispatchInfo, Pays},
+	StorageHasher, Twox64Concat,
 };
 use mock::{
-	Accounts, Call, Currencies, DEXModule, ExtBuilder, Moment, NewAccountDeposit, Origin, Runtime, System, TimeModule,
-	ACA, ALICE, AUSD, BOB, BTC, CAROL,
+	Accounts, Call, Currencies, DEXModule, ExtBuilder, FreeTransferDeposit, Moment, NewAccountDeposit, Origin, Runtime,
+	System, TestEvent, TimeModule, ACA, ALICE, AUSD, BOB, BTC, CAROL,
 };
 use orml_traits::MultiCurrency;
+use sp_runtime::traits::BadOrigin;
+
+fn currencies_transfer() -> CallName {
+	(b"Currencies".to_vec(), b"transfer".to_vec())
+}
+
+#[test]
+fn set_free_call_quota_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let quota = FreeCallQuota {
+			count: 1,
+			period: 10,
+			deposit: 100,
+		};
+		let swap_currency = (b"Dex".to_vec(), b"swap_currency".to_vec());
+		assert_noop!(
+			Accounts::set_free_call_quota(Origin::signed(ALICE), swap_currency.clone(), Some(quota.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			Accounts::set_free_call_quota(
+				Origin::root(),
+				(b"Dex".to_vec(), vec![0u8; MAX_CALL_NAME_LENGTH + 1]),
+				Some(quota.clone())
+			),
+			Error::<Runtime>::CallNameTooLong
+		);
+		for invalid_quota in vec![
+			FreeCallQuota {
+				count: 0,
+				..quota.clone()
+			},
+			FreeCallQuota {
+				period: 0,
+				..quota.clone()
+			},
+			FreeCallQuota {
+				deposit: 0,
+				..quota.clone()
+			},
+		] {
+			assert_noop!(
+				Accounts::set_free_call_quota(Origin::root(), swap_currency.clone(), Some(invalid_quota)),
+				Error::<Runtime>::InvalidFreeCallQuota
+			);
+		}
+
+		assert_ok!(Accounts::set_free_call_quota(
+			Origin::root(),
+			swap_currency.clone(),
+			Some(quota.clone())
+		));
+		let update_event = TestEvent::accounts(RawEvent::FreeCallQuotaUpdated(swap_currency.clone()));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(Accounts::free_call_quotas(&swap_currency), Some(quota));
+
+		assert_ok!(Accounts::set_free_call_quota(
+			Origin::root(),
+			swap_currency.clone(),
+			None
+		));
+		let remove_event = TestEvent::accounts(RawEvent::FreeCallQuotaRemoved(swap_currency.clone()));
+		assert!(System::events().iter().any(|record| record.event == remove_event));
+		assert_eq!(Accounts::free_call_quotas(&swap_currency), None);
+	});
+}
+
+#[test]
+fn migrate_free_transfers_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let key = Twox64Concat::hash(&ALICE.encode());
+		put_storage_value(b"Accounts", b"FreeTransferEnabledAccounts", &key, ());
+		put_storage_value(b"Accounts", b"LastFreeTransfers", &key, vec![1u64, 2u64]);
+		let transfer: CallName = (b"Currencies".to_vec(), b"transfer".to_vec());
+		<FreeCallQuotas<Runtime>>::remove(&transfer);
+
+		Accounts::on_runtime_upgrade();
+		assert_eq!(
+			Accounts::free_call_quotas(&transfer),
+			Some(FreeCallQuota {
+				count: 3,
+				period: 100,
+				deposit: FreeTransferDeposit::get(),
+			})
+		);
+		assert_eq!(Accounts::free_call_deposits(ALICE), Some(FreeTransferDeposit::get()));
+		assert_eq!(Accounts::free_call_deposits(BOB), None);
+		assert_eq!(
+			get_storage_value::<()>(b"Accounts", b"FreeTransferEnabledAccounts", &key),
+			None
+		);
+		assert_eq!(
+			get_storage_value::<Vec<u64>>(b"Accounts", b"LastFreeTransfers", &key),
+			None
+		);
+
+		// the quota removed by governance is not seeded again
+		<FreeCallQuotas<Runtime>>::remove(&transfer);
+		Accounts::on_runtime_upgrade();
+		assert_eq!(Accounts::free_call_quotas(&transfer), None);
+	});
+}
 
 #[test]
-fn enable_free_transfer_require_deposit() {
+fn enable_free_calls_require_deposit() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			Accounts::enable_free_transfer(Origin::signed(BOB)),
+			Accounts::enable_free_calls(Origin::signed(BOB), 200),
 			Error::<Runtime>::NotEnoughBalance
 		);
+		assert_noop!(
+			Accounts::enable_free_calls(Origin::signed(ALICE), 0),
+			Error::<Runtime>::InvalidFreeCallDeposit
+		);
 	});
 }
 
 #[test]
-fn enable_free_transfer_work() {
+fn enable_free_calls_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(Accounts::free_transfer_enabled_accounts(ALICE), None);
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
-		assert_eq!(Accounts::free_transfer_enabled_accounts(ALICE), Some(()));
+		assert_eq!(Accounts::free_call_deposits(ALICE), None);
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
+		assert_eq!(Accounts::free_call_deposits(ALICE), Some(200));
 	});
 }
 
 #[test]
-fn disable_free_transfers_work() {
+fn disable_free_calls_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
-		assert_eq!(Accounts::free_transfer_enabled_accounts(ALICE), Some(()));
-		assert_ok!(Accounts::disable_free_transfers(Origin::signed(ALICE)));
-		assert_eq!(Accounts::free_transfer_enabled_accounts(ALICE), None);
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
+		assert_eq!(Accounts::free_call_deposits(ALICE), Some(200));
+		assert_ok!(Accounts::disable_free_calls(Origin::signed(ALICE)));
+		assert_eq!(Accounts::free_call_deposits(ALICE), None);
 	});
 }
 
 #[test]
-fn try_record_free_transfer_when_no_lock() {
+fn try_record_free_call_when_no_lock() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(TimeModule::now(), 0);
-		assert_eq!(Accounts::free_transfer_enabled_accounts(ALICE), None);
-		assert_eq!(Accounts::last_free_transfers(ALICE), Vec::<Moment>::new());
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), false);
+		assert_eq!(Accounts::free_call_deposits(ALICE), None);
+		assert_eq!(
+			Accounts::last_free_calls(ALICE, currencies_transfer()),
+			Vec::<Moment>::new()
+		);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), false);
 	});
 }
 
 #[test]
-fn try_record_free_transfer_over_cap() {
+fn try_record_free_call_when_deposit_not_enough() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 199));
+		assert_eq!(Accounts::remaining_free_calls(&ALICE, &currencies_transfer()), 0);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), false);
+	});
+}
+
+#[test]
+fn try_record_free_call_without_quota() {
+	ExtBuilder::default().build().execute_with(|| {
+		let swap_currency = (b"Dex".to_vec(), b"swap_currency".to_vec());
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &swap_currency), false);
+
+		assert_ok!(Accounts::set_free_call_quota(
+			Origin::root(),
+			swap_currency.clone(),
+			Some(FreeCallQuota {
+				count: 1,
+				period: 10,
+				deposit: 100,
+			})
+		));
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &swap_currency), true);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &swap_currency), false);
+		assert_eq!(Accounts::remaining_free_calls(&ALICE, &currencies_transfer()), 3);
+	});
+}
+
+#[test]
+fn try_record_free_call_over_cap() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(TimeModule::now(), 0);
-		assert_eq!(Accounts::last_free_transfers(ALICE), Vec::<Moment>::new());
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![0]);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![0, 0]);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![0, 0, 0]);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), false);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![0, 0, 0]);
+		assert_eq!(
+			Accounts::last_free_calls(ALICE, currencies_transfer()),
+			Vec::<Moment>::new()
+		);
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
+		assert_eq!(Accounts::remaining_free_calls(&ALICE, &currencies_transfer()), 3);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![0]);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![0, 0]);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![0, 0, 0]);
+		assert_eq!(Accounts::remaining_free_calls(&ALICE, &currencies_transfer()), 0);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), false);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![0, 0, 0]);
 	});
 }
 
@@ -73,16 +222,23 @@ fn try_record_free_transfer_over_cap() {
 fn remove_expired_entry() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(TimeModule::now(), 0);
-		assert_eq!(Accounts::last_free_transfers(ALICE), Vec::<Moment>::new());
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![0, 0, 0]);
+		assert_eq!(
+			Accounts::last_free_calls(ALICE, currencies_transfer()),
+			Vec::<Moment>::new()
+		);
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![0, 0, 0]);
 		TimeModule::set_timestamp(100);
 		assert_eq!(TimeModule::now(), 100);
-		assert_eq!(Accounts::try_record_free_transfer(&ALICE), true);
-		assert_eq!(Accounts::last_free_transfers(ALICE), vec![100]);
+		assert_eq!(
+			Accounts::get_remaining_free_calls(&ALICE),
+			vec![(currencies_transfer(), 3)]
+		);
+		assert_eq!(Accounts::try_record_free_call(&ALICE, &currencies_transfer()), true);
+		assert_eq!(Accounts::last_free_calls(ALICE, currencies_transfer()), vec![100]);
 	});
 }
 
@@ -133,7 +289,7 @@ fn charges_fee() {
 #[test]
 fn enabled_free_transaction_not_charges_fee() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
 
 		assert_eq!(
 			ChargeTransactionPayment::<Runtime>::from(0)
@@ -149,7 +305,7 @@ fn enabled_free_transaction_not_charges_fee() {
 #[test]
 fn enabled_free_transaction_charges_tip() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
 
 		assert_eq!(
 			ChargeTransactionPayment::<Runtime>::from(100)
@@ -168,7 +324,7 @@ fn enabled_free_transaction_charges_tip() {
 #[test]
 fn enabled_free_transaction_charges_other_call() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
 
 		let fee = 23 * 2 + 1000; // len * byte + weight
 		assert_eq!(
@@ -188,7 +344,7 @@ fn enabled_free_transaction_charges_other_call() {
 #[test]
 fn enabled_free_transaction_charges_other_call_with_tip_and_native_currency_is_enough() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Accounts::enable_free_transfer(Origin::signed(ALICE)));
+		assert_ok!(Accounts::enable_free_calls(Origin::signed(ALICE), 200));
 
 		let fee = 23 * 2 + 1000 + 100; // len * byte + weight + tip
 		assert_eq!(
@@ -307,7 +463,7 @@ fn close_account_failed_when_not_allowed_death() {
 		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 200));
 		System::inc_ref(&BOB);
 		assert_noop!(
-			Accounts::close_account(Origin::signed(BOB), None),
+			Accounts::close_account(Origin::signed(BOB), None, None),
 			Error::<Runtime>::NonZeroRefCount,
 		);
 	});
@@ -324,7 +480,7 @@ fn close_account_failed_when_still_has_active_reserved() {
 			10 + NewAccountDeposit::get(),
 		);
 		assert_noop!(
-			Accounts::close_account(Origin::signed(BOB), None),
+			Accounts::close_account(Origin::signed(BOB), None, None),
 			Error::<Runtime>::StillHasActiveReserved,
 		);
 
@@ -333,7 +489,7 @@ fn close_account_failed_when_still_has_active_reserved() {
 		assert_ok!(<Currencies as MultiReservableCurrency<_>>::reserve(BTC, &CAROL, 1));
 		assert_eq!(System::allow_death(&CAROL), true);
 		assert_noop!(
-			Accounts::close_account(Origin::signed(CAROL), None),
+			Accounts::close_account(Origin::signed(CAROL), None, None),
 			Error::<Runtime>::StillHasActiveReserved,
 		);
 	});
@@ -371,7 +527,7 @@ fn close_account_and_does_not_specific_receiver() {
 			0
 		);
 
-		assert_ok!(Accounts::close_account(Origin::signed(BOB), None));
+		assert_ok!(Accounts::close_account(Origin::signed(BOB), None, None));
 		assert_eq!(Accounts::is_explicit(&BOB), false);
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 0);
 		assert_eq!(
@@ -441,7 +597,7 @@ fn close_account_and_specific_receiver() {
 			0
 		);
 
-		assert_ok!(Accounts::close_account(Origin::signed(BOB), Some(CAROL)));
+		assert_ok!(Accounts::close_account(Origin::signed(BOB), Some(CAROL), None));
 
 		assert_eq!(Accounts::is_explicit(&BOB), false);
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 0);
@@ -462,6 +618,80 @@ fn close_account_and_specific_receiver() {
 	});
 }
 
+#[test]
+fn close_account_with_invalid_liquidation_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_noop!(
+			Accounts::close_account(Origin::signed(BOB), None, Some(CurrencyId::DOT)),
+			Error::<Runtime>::InvalidLiquidationCurrency,
+		);
+	});
+}
+
+#[test]
+fn close_account_and_liquidate_into_specific_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), ACA, 10000, 1000));
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), BTC, 500, 5000));
+
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 2000));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(BTC, &ALICE, &BOB, 300));
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(BOB), BTC, 100, 1000));
+		assert_eq!(DEXModule::shares(BTC, BOB), 1000);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &BOB), 200);
+
+		assert_ok!(Accounts::close_account(Origin::signed(BOB), Some(CAROL), Some(ACA)));
+
+		// the liquidity is withdrawn and all the non-native balances are sold into ACA
+		assert_eq!(DEXModule::shares(BTC, BOB), 0);
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			TestEvent::accounts(RawEvent::BalanceLiquidated(BOB, AUSD, _, ACA, _))
+		)));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			TestEvent::accounts(RawEvent::BalanceLiquidated(BOB, BTC, _, ACA, _))
+		)));
+
+		assert_eq!(Accounts::is_explicit(&BOB), false);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 0);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 0);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &BOB), 0);
+		assert!(<Currencies as MultiCurrency<_>>::total_balance(ACA, &CAROL) > 500);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &CAROL), 0);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &CAROL), 0);
+	});
+}
+
+#[test]
+fn close_account_and_report_balances_not_liquidated() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(BTC, &ALICE, &BOB, 300));
+
+		// no liquidity in DEX
+		assert_ok!(Accounts::close_account(Origin::signed(BOB), Some(CAROL), Some(ACA)));
+
+		let ausd_event = TestEvent::accounts(RawEvent::BalanceNotLiquidated(BOB, AUSD, 1000));
+		assert!(System::events().iter().any(|record| record.event == ausd_event));
+		let btc_event = TestEvent::accounts(RawEvent::BalanceNotLiquidated(BOB, BTC, 300));
+		assert!(System::events().iter().any(|record| record.event == btc_event));
+
+		// the balances not liquidated are transferred as they are
+		assert_eq!(Accounts::is_explicit(&BOB), false);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 0);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &BOB), 0);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &CAROL), 1000);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &CAROL), 300);
+	});
+}
+
 #[test]
 fn charges_fee_when_validate_and_native_is_not_enough() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -494,3 +724,274 @@ fn charges_fee_when_validate_and_native_is_not_enough() {
 		assert_eq!(DEXModule::liquidity_pool(ACA), (10000 - 7 - 2000, 1251));
 	});
 }
+
+#[test]
+fn charges_fee_in_specified_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		// open account for BOB
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 100));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(BTC, &ALICE, &BOB, 100));
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 0);
+
+		// add liquidity to DEX
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), ACA, 10000, 1000));
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), BTC, 100, 1000));
+
+		let fee = 500 * 2 + 1000; // len * byte + weight
+		let btc_fee = Accounts::quote_fee(&[BTC, AUSD, ACA], fee).unwrap();
+		assert!(btc_fee > 0 && btc_fee < 100);
+
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_fee_swap_path(0, vec![BTC, AUSD, ACA])
+				.validate(&BOB, CALL2, &INFO, 500)
+				.unwrap()
+				.priority,
+			fee
+		);
+
+		// pay fee by BTC rather than AUSD
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(BTC, &BOB), 100 - btc_fee);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 1000);
+	});
+}
+
+#[test]
+fn charges_fee_only_swap_shortfall() {
+	ExtBuilder::default().build().execute_with(|| {
+		// open account for BOB
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 1600));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 1500);
+
+		// add liquidity to DEX
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), ACA, 10000, 1000));
+
+		let fee = 500 * 2 + 1000; // len * byte + weight
+		let ausd_for_shortfall = Accounts::quote_fee(&[AUSD, ACA], fee - 1500).unwrap();
+		assert!(ausd_for_shortfall < Accounts::quote_fee(&[AUSD, ACA], fee).unwrap());
+
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_fee_swap_path(0, vec![AUSD, ACA])
+				.validate(&BOB, CALL2, &INFO, 500)
+				.unwrap()
+				.priority,
+			fee
+		);
+		assert_eq!(
+			<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB),
+			1000 - ausd_for_shortfall
+		);
+	});
+}
+
+#[test]
+fn charges_fee_with_invalid_fee_swap_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		// open account for BOB
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 100));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(DEXModule::add_liquidity(Origin::signed(ALICE), ACA, 10000, 1000));
+
+		assert_eq!(Accounts::quote_fee(&[AUSD, BTC], 2000), None);
+		assert_eq!(Accounts::quote_fee(&[ACA], 2000), None);
+		assert_eq!(Accounts::quote_fee(&[BTC, AUSD, BTC, ACA], 2000), None);
+
+		for path in vec![vec![AUSD, BTC], vec![ACA], vec![BTC, AUSD, BTC, ACA]] {
+			assert_eq!(
+				ChargeTransactionPayment::<Runtime>::with_fee_swap_path(0, path).validate(&BOB, CALL2, &INFO, 500),
+				Err(InvalidTransaction::Payment.into())
+			);
+		}
+
+		// the specified fee currency is not enough, do not fallback to other currencies
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_fee_swap_path(0, vec![BTC, AUSD, ACA])
+				.validate(&BOB, CALL2, &INFO, 500),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 1000);
+	});
+}
+
+#[test]
+fn register_and_unregister_sponsor_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Accounts::register_sponsor(Origin::signed(ALICE), vec![currencies_transfer()], 2000, 10000, 1),
+			Error::<Runtime>::InvalidSponsorExpiry,
+		);
+		assert_noop!(
+			Accounts::register_sponsor(
+				Origin::signed(ALICE),
+				vec![currencies_transfer(); MAX_SPONSORED_CALLS + 1],
+				2000,
+				10000,
+				100
+			),
+			Error::<Runtime>::TooManySponsoredCalls,
+		);
+		assert_noop!(
+			Accounts::register_sponsor(
+				Origin::signed(ALICE),
+				vec![(b"Currencies".to_vec(), vec![0u8; MAX_CALL_NAME_LENGTH + 1])],
+				2000,
+				10000,
+				100
+			),
+			Error::<Runtime>::CallNameTooLong,
+		);
+
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			2000,
+			10000,
+			100
+		));
+		let register_event = TestEvent::accounts(RawEvent::SponsorRegistered(ALICE));
+		assert!(System::events().iter().any(|record| record.event == register_event));
+		assert_eq!(
+			Accounts::sponsors(ALICE),
+			Some(SponsorInfo {
+				allowed_calls: vec![currencies_transfer()],
+				quota_per_user: 2000,
+				total_budget: 10000,
+				budget_used: 0,
+				expiry: 100,
+			})
+		);
+
+		assert_noop!(
+			Accounts::unregister_sponsor(Origin::signed(BOB)),
+			Error::<Runtime>::SponsorNotFound,
+		);
+		assert_ok!(Accounts::unregister_sponsor(Origin::signed(ALICE)));
+		let unregister_event = TestEvent::accounts(RawEvent::SponsorUnregistered(ALICE));
+		assert!(System::events().iter().any(|record| record.event == unregister_event));
+		assert_eq!(Accounts::sponsors(ALICE), None);
+	});
+}
+
+#[test]
+fn sponsor_pays_fee_of_allowed_call() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			2000,
+			10000,
+			100
+		));
+
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE)
+			.pre_dispatch(&BOB, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+
+		// refund to the sponsor and record the actual fee
+		let actual_fee = 23 * 2 + 800;
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - actual_fee);
+		assert_eq!(Accounts::sponsored_fees(ALICE, BOB), actual_fee);
+		assert_eq!(Accounts::sponsors(ALICE).unwrap().budget_used, actual_fee);
+
+		let sponsor_event = TestEvent::accounts(RawEvent::FeeSponsored(ALICE, BOB, actual_fee));
+		assert!(System::events().iter().any(|record| record.event == sponsor_event));
+
+		// the fees paid for the user are kept after unregister
+		assert_ok!(Accounts::unregister_sponsor(Origin::signed(ALICE)));
+		assert_eq!(Accounts::sponsored_fees(ALICE, BOB), actual_fee);
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			1500,
+			10000,
+			100
+		));
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&BOB, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+	});
+}
+
+#[test]
+fn sponsor_rejects_invalid_transaction() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		// sponsor not found
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&BOB, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			1500,
+			10000,
+			100
+		));
+
+		// call not allowed
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&BOB, CALL2, &INFO, 18),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// sponsor does not pay tip
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>(100, None, Some(ALICE)).validate(&BOB, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// exceed quota of the user
+		let pre = ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE)
+			.pre_dispatch(&BOB, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&BOB, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert!(ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE)
+			.validate(&CAROL, CALL, &INFO, 23)
+			.is_ok());
+
+		// exceed total budget, the used budget is kept on update
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			1500,
+			1800,
+			100
+		));
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&CAROL, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_ok!(Accounts::register_sponsor(
+			Origin::signed(ALICE),
+			vec![currencies_transfer()],
+			1500,
+			10000,
+			100
+		));
+		assert!(ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE)
+			.validate(&CAROL, CALL, &INFO, 23)
+			.is_ok());
+
+		// expired
+		System::set_block_number(100);
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::with_sponsor(0, ALICE).validate(&CAROL, CALL, &INFO, 23),
+			Err(InvalidTransaction::Payment.into())
+		);
+	});
+}
@@ -122,6 +122,8 @@ parameter_types! {
 	pub const AuctionDurationSoftCap: u64 = 2000;
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const MaxAutoBidsPerCollateral: u32 = 10;
+	pub const MaxAutoBidAuctionsPerBlock: u32 = 10;
 }
 
 impl auction_manager::Trait for Runtime {
@@ -138,6 +140,8 @@ impl auction_manager::Trait for Runtime {
 	type PriceSource = prices::Module<Runtime>;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdownModule;
+	type MaxAutoBidsPerCollateral = MaxAutoBidsPerCollateral;
+	type MaxAutoBidAuctionsPerBlock = MaxAutoBidAuctionsPerBlock;
 }
 pub type AuctionManagerModule = auction_manager::Module<Runtime>;
 
@@ -170,6 +174,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = EmergencyShutdownModule;
+	type PriceSource = prices::Module<Runtime>;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -215,15 +221,27 @@ parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::one();
 }
 
+parameter_types! {
+	pub const DexTwapPeriod: u32 = 10;
+	pub const DefaultFeedExpiresIn: u64 = 60 * 60 * 1000;
+}
+
 impl prices::Trait for Runtime {
 	type Event = ();
 	type Source = orml_oracle::Module<Runtime, orml_oracle::Instance1>;
+	type Time = pallet_timestamp::Module<Runtime>;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
+	type DEX = ();
+	type DexTwapPeriod = DexTwapPeriod;
+	type FeedSources = ();
+	type DefaultFeedExpiresIn = DefaultFeedExpiresIn;
+	type PriceHistoryCurrencyIds = ();
+	type PriceHistoryLength = ();
 }
 
 pub struct MockConvert;
@@ -255,6 +273,7 @@ impl emergency_shutdown::Trait for Runtime {
 	type Event = ();
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = prices::Module<Runtime>;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = AuctionManagerModule;
 	type ShutdownOrigin = EnsureRoot<AccountId>;
@@ -10,6 +10,11 @@
 //!     burn by auction
 //!   - `debit auction`: inflation some native token to sell for getting stable
 //!     coin to eliminate excessive bad debit by auction
+//!
+//! Bidders can bid on multiple auctions in one transaction, or register an
+//! auto bid strategy for a collateral type, which reserves stable currency and
+//! bids on the owner's behalf when new collateral auctions start or when the
+//! owner is outbid, up to a maximum price per unit collateral.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -17,13 +22,14 @@ use codec::{Decode, Encode};
 use frame_support::{
 	debug, decl_error, decl_event, decl_module, decl_storage, ensure,
 	traits::Get,
-	weights::{constants::WEIGHT_PER_MICROS, DispatchClass},
+	weights::{constants::WEIGHT_PER_MICROS, DispatchClass, Weight},
+	IterableStorageDoubleMap, IterableStorageMap,
 };
 use frame_system::{
-	self as system, ensure_none,
+	self as system, ensure_none, ensure_signed,
 	offchain::{SendTransactionTypes, SubmitTransaction},
 };
-use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
+use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, MultiReservableCurrency, OnNewBidResult};
 use orml_utilities::{with_transaction_result, IterableStorageMapExtended, OffchainErr};
 use primitives::{AuctionId, Balance, CurrencyId};
 use sp_runtime::{
@@ -32,7 +38,7 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, CheckedDiv, Hash, Saturating, Zero},
+	traits::{BlakeTwo256, CheckedDiv, Hash, One, Saturating, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -42,7 +48,10 @@ use sp_std::{
 	cmp::{Eq, PartialEq},
 	prelude::*,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, CollateralCircuitBreaker, DEXManager, EmergencyShutdown, Price,
+	PriceProvider, Rate,
+};
 
 mod mock;
 mod tests;
@@ -151,6 +160,17 @@ pub struct SurplusAuctionItem<BlockNumber> {
 	start_time: BlockNumber,
 }
 
+/// Auto bid strategy for collateral auctions under specific collateral type
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct AutoBidStrategy {
+	/// The maximum price(stable currency per unit collateral) to bid
+	max_price: Price,
+	/// Remaining stable currency reserved for auto bid
+	#[codec(compact)]
+	budget: Balance,
+}
+
 pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
@@ -170,8 +190,8 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	/// The native currency id
 	type GetNativeCurrencyId: Get<CurrencyId>;
 
-	/// Currency to transfer assets
-	type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+	/// Currency to transfer assets and reserve the budget of auto bid
+	type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
 	/// Auction to manager the auction process
 	type Auction: Auction<Self::AccountId, Self::BlockNumber, AuctionId = AuctionId, Balance = Balance>;
@@ -191,8 +211,16 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	/// multiple modules send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
-	/// Emergency shutdown.
-	type EmergencyShutdown: EmergencyShutdown;
+	/// Emergency shutdown, and the circuit breaker to freeze specific
+	/// collateral.
+	type EmergencyShutdown: EmergencyShutdown + CollateralCircuitBreaker<CurrencyId>;
+
+	/// The cap of auto bid strategies under specific collateral type.
+	type MaxAutoBidsPerCollateral: Get<u32>;
+
+	/// The maximum number of pending collateral auctions to auto bid in one
+	/// block, the rest are left for the following blocks.
+	type MaxAutoBidAuctionsPerBlock: Get<u32>;
 }
 
 decl_event!(
@@ -219,6 +247,14 @@ decl_event!(
 		DebitAuctionDealt(AuctionId, Balance, AccountId, Balance),
 		/// Dex take collateral auction. \[auction_id, collateral_type, collateral_amount, turnover\]
 		DEXTakeCollateralAuction(AuctionId, CurrencyId, Balance, Balance),
+		/// Bid placed by batch bid. \[auction_id, bidder, bid_price\]
+		Bid(AuctionId, AccountId, Balance),
+		/// Auto bid strategy set. \[collateral_type, owner, max_price, budget\]
+		AutoBidSet(CurrencyId, AccountId, Price, Balance),
+		/// Auto bid strategy cancelled. \[collateral_type, owner\]
+		AutoBidCancelled(CurrencyId, AccountId),
+		/// Bid placed by auto bid strategy. \[auction_id, bidder, bid_price\]
+		AutoBidPlaced(AuctionId, AccountId, Balance),
 	}
 );
 
@@ -237,6 +273,20 @@ decl_error! {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// The auction has not started
+		AuctionNotStarted,
+		/// The bid is not accepted by the auction
+		BidNotAccepted,
+		/// The auto bid strategy does not exist
+		AutoBidNotExists,
+		/// The count of auto bid strategies exceeds the cap
+		TooManyAutoBids,
+		/// The reserved budget of auto bid is not enough
+		AutoBidBudgetNotEnough,
+		/// The collateral has been frozen
+		CollateralFrozen,
+		/// Auto bid is only available for collateral types
+		InvalidCurrencyType,
 	}
 }
 
@@ -258,6 +308,10 @@ decl_storage! {
 		/// CollateralType -> TotalAmount
 		pub TotalCollateralInAuction get(fn total_collateral_in_auction): map hasher(twox_64_concat) CurrencyId => Balance;
 
+		/// Record of the number of active collateral auctions under specific collateral type
+		/// CollateralType -> AuctionsCount
+		pub CollateralAuctionsCount get(fn collateral_auctions_count): map hasher(twox_64_concat) CurrencyId => u32;
+
 		/// Record of total target sales of all active collateral auctions
 		pub TotalTargetInAuction get(fn total_target_in_auction): Balance;
 
@@ -266,6 +320,20 @@ decl_storage! {
 
 		/// Record of total surplus amount of all active surplus auctions
 		pub TotalSurplusInAuction get(fn total_surplus_in_auction): Balance;
+
+		/// Auto bid strategies under specific collateral type
+		/// CollateralType -> Owner -> AutoBidStrategy
+		pub AutoBids get(fn auto_bids): double_map hasher(twox_64_concat) CurrencyId, hasher(twox_64_concat) T::AccountId =>
+			Option<AutoBidStrategy>;
+
+		/// Record of the number of auto bid strategies under specific collateral type
+		pub AutoBidsCount get(fn auto_bids_count): map hasher(twox_64_concat) CurrencyId => u32;
+
+		/// Mapping from auction id to the owner of auto bid strategy which holds the last bid
+		pub AutoBidOf get(fn auto_bid_of): map hasher(twox_64_concat) AuctionId => Option<T::AccountId>;
+
+		/// Collateral auctions waiting for auto bid at the beginning of the following blocks
+		pub PendingAutoBidAuctions get(fn pending_auto_bid_auctions): map hasher(twox_64_concat) AuctionId => ();
 	}
 }
 
@@ -290,6 +358,12 @@ decl_module! {
 		/// The native currency id
 		const GetNativeCurrencyId: CurrencyId = T::GetNativeCurrencyId::get();
 
+		/// The cap of auto bid strategies under specific collateral type
+		const MaxAutoBidsPerCollateral: u32 = T::MaxAutoBidsPerCollateral::get();
+
+		/// The maximum number of pending collateral auctions to auto bid in one block
+		const MaxAutoBidAuctionsPerBlock: u32 = T::MaxAutoBidAuctionsPerBlock::get();
+
 		/// Cancel active auction after system shutdown
 		///
 		/// The dispatch origin of this call must be _None_.
@@ -327,6 +401,101 @@ decl_module! {
 			})?;
 		}
 
+		/// Bid on multiple auctions in one transaction. All bids succeed or
+		/// none of them.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `bids`: list of (auction_id, bid_price)
+		#[weight = (bids.len() as Weight).saturating_mul(10_000)]
+		pub fn batch_bid(origin, bids: Vec<(AuctionId, Balance)>) {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| {
+				for (id, bid_price) in bids {
+					Self::do_bid(who.clone(), id, bid_price)?;
+					Self::deposit_event(RawEvent::Bid(id, who.clone(), bid_price));
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Set auto bid strategy for collateral auctions under specific collateral type,
+		/// replace the previous one if exists.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: collateral type
+		/// - `max_price`: the maximum price of stable currency per unit collateral to bid
+		/// - `budget`: the amount of stable currency reserved for auto bid
+		#[weight = 10_000]
+		pub fn set_auto_bid(origin, currency_id: CurrencyId, max_price: Price, budget: Balance) {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| {
+				let stable_currency_id = T::GetStableCurrencyId::get();
+				ensure!(currency_id != stable_currency_id, Error::<T>::InvalidCurrencyType);
+				ensure!(!max_price.is_zero() && !budget.is_zero(), Error::<T>::InvalidAmount);
+
+				if let Some(strategy) = Self::auto_bids(currency_id, &who) {
+					// release the budget of previous strategy
+					T::Currency::unreserve(stable_currency_id, &who, strategy.budget);
+				} else {
+					AutoBidsCount::try_mutate(currency_id, |count| -> DispatchResult {
+						ensure!(*count < T::MaxAutoBidsPerCollateral::get(), Error::<T>::TooManyAutoBids);
+						*count = count.saturating_add(1);
+						Ok(())
+					})?;
+				}
+
+				T::Currency::reserve(stable_currency_id, &who, budget)?;
+				<AutoBids<T>>::insert(currency_id, &who, AutoBidStrategy { max_price, budget });
+
+				Self::deposit_event(RawEvent::AutoBidSet(currency_id, who, max_price, budget));
+				Ok(())
+			})?;
+		}
+
+		/// Cancel auto bid strategy under specific collateral type and release the remaining budget.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: collateral type
+		#[weight = 10_000]
+		pub fn cancel_auto_bid(origin, currency_id: CurrencyId) {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| {
+				let strategy = <AutoBids<T>>::take(currency_id, &who).ok_or(Error::<T>::AutoBidNotExists)?;
+				T::Currency::unreserve(T::GetStableCurrencyId::get(), &who, strategy.budget);
+				AutoBidsCount::mutate(currency_id, |count| *count = count.saturating_sub(1));
+
+				Self::deposit_event(RawEvent::AutoBidCancelled(currency_id, who));
+				Ok(())
+			})?;
+		}
+
+		/// Place auto bids for at most `MaxAutoBidAuctionsPerBlock` pending
+		/// collateral auctions when block begin.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let is_shutdown = T::EmergencyShutdown::is_shutdown();
+			let pending_auctions = PendingAutoBidAuctions::iter()
+				.take(T::MaxAutoBidAuctionsPerBlock::get() as usize)
+				.map(|(auction_id, _)| auction_id)
+				.collect::<Vec<_>>();
+			let count = pending_auctions.len() as Weight;
+
+			for auction_id in pending_auctions {
+				PendingAutoBidAuctions::remove(auction_id);
+				if !is_shutdown {
+					Self::auto_bid_collateral_auction(auction_id);
+				}
+			}
+
+			// the auction, strategies of the collateral and balances are read to auto bid
+			let per_auction = T::DbWeight::get().reads_writes(10 + T::MaxAutoBidsPerCollateral::get() as Weight, 8);
+			T::DbWeight::get()
+				.reads(2)
+				.saturating_add(per_auction.saturating_mul(count))
+		}
+
 		/// Start offchain worker in order to submit unsigned tx to cancel active auction after system shutdown.
 		fn offchain_worker(now: T::BlockNumber) {
 			if T::EmergencyShutdown::is_shutdown() && sp_io::offchain::is_validator() {
@@ -451,6 +620,128 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
+	/// Bid on the auction on behalf of `who`, going through the same checks
+	/// and handlers as bidding by `T::Auction`.
+	fn do_bid(who: T::AccountId, id: AuctionId, value: Balance) -> DispatchResult {
+		let mut auction = T::Auction::auction_info(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <system::Module<T>>::block_number();
+		ensure!(now >= auction.start, Error::<T>::AuctionNotStarted);
+		if let Some((_, current_bid_price)) = auction.bid.as_ref() {
+			ensure!(value > *current_bid_price, Error::<T>::InvalidBidPrice);
+		} else {
+			ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
+		}
+
+		let bid_result = <Self as AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>>::on_new_bid(
+			now,
+			id,
+			(who.clone(), value),
+			auction.bid.clone(),
+		);
+		ensure!(bid_result.accept_bid, Error::<T>::BidNotAccepted);
+
+		if let Change::NewValue(new_end) = bid_result.auction_end_change {
+			auction.end = new_end;
+		}
+		auction.bid = Some((who, value));
+		T::Auction::update_auction(id, auction)
+	}
+
+	/// Add collateral auction to the pending list of auto bid
+	fn schedule_auto_bid(id: AuctionId) {
+		PendingAutoBidAuctions::insert(id, ());
+	}
+
+	/// Return the minimum bid price which is accepted by the auction.
+	fn minimum_bid_price(last_bid_price: Balance, target_price: Balance, minimum_increment: Rate) -> Balance {
+		let increment = minimum_increment.saturating_mul_int(sp_std::cmp::max(target_price, last_bid_price));
+		last_bid_price.saturating_add(sp_std::cmp::max(increment, One::one()))
+	}
+
+	/// Place the minimum valid bid for the collateral auction by the auto bid
+	/// strategy which offers the highest price and can afford it. Auto bid
+	/// never pushes the collateral auction into reverse stage.
+	fn auto_bid_collateral_auction(id: AuctionId) {
+		let (collateral_auction, last_bid) = match (Self::collateral_auctions(id), T::Auction::auction_info(id)) {
+			(Some(collateral_auction), Some(auction_info)) => (collateral_auction, auction_info.bid),
+			_ => return,
+		};
+		if T::EmergencyShutdown::is_frozen(collateral_auction.currency_id) {
+			return;
+		}
+		let last_bidder = last_bid.as_ref().map(|(who, _)| who.clone());
+		let last_bid_price = last_bid.map_or(Zero::zero(), |(_, price)| price);
+		let now = <system::Module<T>>::block_number();
+
+		let new_bid_price = Self::minimum_bid_price(
+			last_bid_price,
+			collateral_auction.target,
+			Self::get_minimum_increment_size(now, collateral_auction.start_time),
+		);
+		if !collateral_auction.always_forward() && new_bid_price > collateral_auction.target {
+			return;
+		}
+		let payment = collateral_auction.payment_amount(new_bid_price);
+
+		let winner = <AutoBids<T>>::iter_prefix(collateral_auction.currency_id)
+			.filter(|(who, strategy)| {
+				Some(who) != last_bidder.as_ref()
+					&& strategy.budget >= payment
+					&& strategy
+						.max_price
+						.checked_mul_int(collateral_auction.amount)
+						.map_or(false, |max_bid_price| max_bid_price >= new_bid_price)
+			})
+			.max_by_key(|(_, strategy)| strategy.max_price);
+
+		if let Some((bidder, _)) = winner {
+			let currency_id = collateral_auction.currency_id;
+			let result = with_transaction_result(|| -> DispatchResult {
+				// release the reserved budget to pay for the bid
+				let remaining = T::Currency::unreserve(T::GetStableCurrencyId::get(), &bidder, payment);
+				ensure!(remaining.is_zero(), Error::<T>::AutoBidBudgetNotEnough);
+
+				Self::do_bid(bidder.clone(), id, new_bid_price)?;
+
+				<AutoBids<T>>::mutate(currency_id, &bidder, |maybe_strategy| {
+					if let Some(strategy) = maybe_strategy {
+						strategy.budget = strategy.budget.saturating_sub(payment);
+					}
+				});
+				<AutoBidOf<T>>::insert(id, &bidder);
+				Ok(())
+			});
+
+			if result.is_ok() {
+				Self::deposit_event(RawEvent::AutoBidPlaced(id, bidder, new_bid_price));
+			}
+		}
+	}
+
+	/// Return the refund to the budget if the outbid bid was placed by auto bid,
+	/// and schedule auto bid if the last bidder has auto bid strategy.
+	fn on_collateral_auction_outbid(
+		id: AuctionId,
+		currency_id: CurrencyId,
+		last_bidder: &T::AccountId,
+		refund: Balance,
+	) {
+		if Self::auto_bid_of(id).as_ref() == Some(last_bidder) {
+			<AutoBidOf<T>>::remove(id);
+			<AutoBids<T>>::mutate(currency_id, last_bidder, |maybe_strategy| {
+				if let Some(strategy) = maybe_strategy {
+					if T::Currency::reserve(T::GetStableCurrencyId::get(), last_bidder, refund).is_ok() {
+						strategy.budget = strategy.budget.saturating_add(refund);
+					}
+				}
+			});
+		}
+
+		if <AutoBids<T>>::contains_key(currency_id, last_bidder) {
+			Self::schedule_auto_bid(id);
+		}
+	}
+
 	fn cancel_surplus_auction(id: AuctionId, surplus_auction: SurplusAuctionItem<T::BlockNumber>) -> DispatchResult {
 		// if there's bid
 		if let Some((bidder, bid_price)) = Self::get_last_bid(id) {
@@ -535,6 +826,7 @@ impl<T: Trait> Module<T> {
 			*balance = balance.saturating_sub(collateral_auction.amount)
 		});
 		TotalTargetInAuction::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+		CollateralAuctionsCount::mutate(collateral_auction.currency_id, |count| *count = count.saturating_sub(1));
 
 		Ok(())
 	}
@@ -599,6 +891,11 @@ impl<T: Trait> Module<T> {
 				id,
 				|collateral_auction| -> sp_std::result::Result<T::BlockNumber, DispatchError> {
 					let mut collateral_auction = collateral_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+					// the price of frozen collateral is not reliable, do not accept bids until it is unfrozen
+					ensure!(
+						!T::EmergencyShutdown::is_frozen(collateral_auction.currency_id),
+						Error::<T>::CollateralFrozen
+					);
 					let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price); // get last bid price
 
 					// ensure new bid price is valid
@@ -620,6 +917,7 @@ impl<T: Trait> Module<T> {
 					if let Some(last_bidder) = last_bidder {
 						let refund = collateral_auction.payment_amount(last_bid_price);
 						T::Currency::transfer(T::GetStableCurrencyId::get(), &new_bidder, last_bidder, refund)?;
+						Self::on_collateral_auction_outbid(id, collateral_auction.currency_id, last_bidder, refund);
 
 						payment = payment
 							.checked_sub(refund)
@@ -841,6 +1139,7 @@ impl<T: Trait> Module<T> {
 			*balance = balance.saturating_sub(collateral_auction.amount)
 		});
 		TotalTargetInAuction::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+		CollateralAuctionsCount::mutate(collateral_auction.currency_id, |count| *count = count.saturating_sub(1));
 	}
 
 	fn debit_auction_end_handler(
@@ -938,6 +1237,7 @@ impl<T: Trait> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 		}
 
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
+			<AutoBidOf<T>>::remove(id);
 			Self::collateral_auction_end_handler(id, collateral_auction, winner);
 		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
 			Self::debit_auction_end_handler(id, debit_auction, winner);
@@ -991,6 +1291,11 @@ impl<T: Trait> AuctionManager<T::AccountId> for Module<T> {
 
 		// increment recipient account reference
 		system::Module::<T>::inc_ref(&refund_recipient);
+		CollateralAuctionsCount::mutate(currency_id, |count| *count = count.saturating_add(1));
+
+		if !Self::auto_bids_count(currency_id).is_zero() {
+			Self::schedule_auto_bid(auction_id);
+		}
 
 		<Module<T>>::deposit_event(RawEvent::NewCollateralAuction(auction_id, currency_id, amount, target));
 		Ok(())
@@ -1046,6 +1351,7 @@ impl<T: Trait> AuctionManager<T::AccountId> for Module<T> {
 
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
+			<AutoBidOf<T>>::remove(id);
 			Self::cancel_collateral_auction(id, collateral_auction)?;
 		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
 			Self::cancel_debit_auction(id, debit_auction)?;
@@ -1062,6 +1368,10 @@ impl<T: Trait> AuctionManager<T::AccountId> for Module<T> {
 		Self::total_collateral_in_auction(id)
 	}
 
+	fn get_collateral_auctions_count(id: Self::CurrencyId) -> u32 {
+		Self::collateral_auctions_count(id)
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Self::total_surplus_in_auction()
 	}
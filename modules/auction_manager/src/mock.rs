@@ -129,6 +129,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = MockEmergencyShutdown;
+	type PriceSource = MockPriceSource;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -178,12 +180,17 @@ pub type DEXModule = dex::Module<Runtime>;
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static FROZEN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_freeze(currency_id: CurrencyId) {
+	FROZEN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
@@ -191,12 +198,20 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+impl CollateralCircuitBreaker<CurrencyId> for MockEmergencyShutdown {
+	fn is_frozen(currency_id: CurrencyId) -> bool {
+		FROZEN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
+}
+
 parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const MaxAutoBidsPerCollateral: u32 = 2;
+	pub const MaxAutoBidAuctionsPerBlock: u32 = 1;
 }
 
 impl Trait for Runtime {
@@ -213,6 +228,8 @@ impl Trait for Runtime {
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type MaxAutoBidsPerCollateral = MaxAutoBidsPerCollateral;
+	type MaxAutoBidAuctionsPerBlock = MaxAutoBidAuctionsPerBlock;
 }
 pub type AuctionManagerModule = Module<Runtime>;
 
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use mock::*;
 
 #[test]
@@ -69,6 +69,7 @@ fn new_collateral_auction_work() {
 
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 10);
 		assert_eq!(AuctionManagerModule::total_target_in_auction(), 100);
+		assert_eq!(AuctionManagerModule::collateral_auctions_count(BTC), 1);
 		assert_eq!(AuctionModule::auctions_index(), 1);
 		assert_eq!(System::refs(&ALICE), 1);
 
@@ -184,6 +185,20 @@ fn collateral_auction_bid_handler_work() {
 	});
 }
 
+#[test]
+fn collateral_auction_bid_handler_on_frozen_collateral() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		mock_freeze(BTC);
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 5), None),
+			Error::<Runtime>::CollateralFrozen,
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+	});
+}
+
 #[test]
 fn debit_auction_bid_handler_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -328,6 +343,7 @@ fn collateral_auction_end_handler_without_bid() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_target_in_auction(), 200);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::collateral_auctions_count(BTC), 1);
 		assert_eq!(System::refs(&ALICE), 1);
 
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), true);
@@ -341,6 +357,7 @@ fn collateral_auction_end_handler_without_bid() {
 		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
 		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::collateral_auctions_count(BTC), 0);
 		assert_eq!(System::refs(&ALICE), 0);
 	});
 }
@@ -716,3 +733,210 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
 	});
 }
+
+#[test]
+fn batch_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+
+		assert_ok!(AuctionManagerModule::batch_bid(
+			Origin::signed(BOB),
+			vec![(0, 100), (1, 100)]
+		));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((BOB, 100)));
+		assert_eq!(AuctionModule::auction_info(1).unwrap().bid, Some((BOB, 100)));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 800);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+
+		let bid_event = TestEvent::auction_manager(RawEvent::Bid(1, BOB, 100));
+		assert!(System::events().iter().any(|record| record.event == bid_event));
+
+		// all bids fail if any bid fails
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(CAROL), vec![(0, 200), (2, 100)]),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(CAROL), vec![(0, 200), (1, 101)]),
+			Error::<Runtime>::BidNotAccepted,
+		);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((BOB, 100)));
+	});
+}
+
+#[test]
+fn set_auto_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_auto_bid(Origin::signed(ALICE), AUSD, Price::saturating_from_integer(3), 500),
+			Error::<Runtime>::InvalidCurrencyType,
+		);
+		assert_noop!(
+			AuctionManagerModule::set_auto_bid(Origin::signed(ALICE), BTC, Price::saturating_from_integer(3), 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_integer(3),
+			500
+		));
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 500);
+		assert_eq!(AuctionManagerModule::auto_bids_count(BTC), 1);
+		let auto_bid_set_event =
+			TestEvent::auction_manager(RawEvent::AutoBidSet(BTC, ALICE, Price::saturating_from_integer(3), 500));
+		assert!(System::events().iter().any(|record| record.event == auto_bid_set_event));
+
+		// replace the previous strategy
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_integer(2),
+			300
+		));
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 300);
+		assert_eq!(AuctionManagerModule::auto_bids_count(BTC), 1);
+
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(BOB),
+			BTC,
+			Price::saturating_from_integer(2),
+			300
+		));
+		assert_noop!(
+			AuctionManagerModule::set_auto_bid(Origin::signed(CAROL), BTC, Price::saturating_from_integer(2), 300),
+			Error::<Runtime>::TooManyAutoBids,
+		);
+	});
+}
+
+#[test]
+fn cancel_auto_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::cancel_auto_bid(Origin::signed(ALICE), BTC),
+			Error::<Runtime>::AutoBidNotExists,
+		);
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_integer(3),
+			500
+		));
+		assert_ok!(AuctionManagerModule::cancel_auto_bid(Origin::signed(ALICE), BTC));
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1000);
+		assert_eq!(AuctionManagerModule::auto_bids_count(BTC), 0);
+		assert_eq!(AuctionManagerModule::auto_bids(BTC, ALICE), None);
+
+		let auto_bid_cancelled_event = TestEvent::auction_manager(RawEvent::AutoBidCancelled(BTC, ALICE));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == auto_bid_cancelled_event));
+	});
+}
+
+#[test]
+fn auto_bid_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_integer(3),
+			500
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&CAROL, BTC, 100, 200));
+		assert!(PendingAutoBidAuctions::contains_key(0));
+
+		// auto bid when new auction starts
+		AuctionManagerModule::on_initialize(1);
+		assert!(!PendingAutoBidAuctions::contains_key(0));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((ALICE, 10)));
+		assert_eq!(AuctionManagerModule::auto_bid_of(0), Some(ALICE));
+		assert_eq!(AuctionManagerModule::auto_bids(BTC, ALICE).unwrap().budget, 490);
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 490);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 500);
+		let auto_bid_placed_event = TestEvent::auction_manager(RawEvent::AutoBidPlaced(0, ALICE, 10));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == auto_bid_placed_event));
+
+		// outbid by BOB, refund returns to the budget
+		System::set_block_number(2);
+		assert_ok!(AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 50)]));
+		assert_eq!(AuctionManagerModule::auto_bid_of(0), None);
+		assert_eq!(AuctionManagerModule::auto_bids(BTC, ALICE).unwrap().budget, 500);
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 500);
+		assert!(PendingAutoBidAuctions::contains_key(0));
+
+		// auto bid again when outbid
+		AuctionManagerModule::on_initialize(2);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((ALICE, 60)));
+		assert_eq!(AuctionManagerModule::auto_bids(BTC, ALICE).unwrap().budget, 440);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+
+		// do not bid into reverse stage
+		assert_ok!(AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 195)]));
+		AuctionManagerModule::on_initialize(3);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((BOB, 195)));
+		assert_eq!(AuctionManagerModule::auto_bids(BTC, ALICE).unwrap().budget, 500);
+	});
+}
+
+#[test]
+fn auto_bid_pending_auctions_limited_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_integer(3),
+			500
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&CAROL, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&CAROL, BTC, 100, 200));
+		assert!(PendingAutoBidAuctions::contains_key(0));
+		assert!(PendingAutoBidAuctions::contains_key(1));
+
+		// only one pending auction is processed per block
+		AuctionManagerModule::on_initialize(2);
+		assert_eq!(PendingAutoBidAuctions::iter().count(), 1);
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 490);
+
+		AuctionManagerModule::on_initialize(3);
+		assert_eq!(PendingAutoBidAuctions::iter().count(), 0);
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 480);
+		assert_eq!(AuctionManagerModule::auto_bid_of(0), Some(ALICE));
+		assert_eq!(AuctionManagerModule::auto_bid_of(1), Some(ALICE));
+	});
+}
+
+#[test]
+fn auto_bid_limited_by_max_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_auto_bid(
+			Origin::signed(ALICE),
+			BTC,
+			Price::saturating_from_rational(1, 2),
+			500
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&CAROL, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 50)]));
+
+		// the minimum bid 60 exceeds max bid 50 of ALICE
+		AuctionManagerModule::on_initialize(1);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((BOB, 50)));
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 500);
+	});
+}
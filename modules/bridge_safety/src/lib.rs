@@ -0,0 +1,399 @@
+//! # Bridge Safety Module
+//!
+//! ## Overview
+//!
+//! Bridges mint and burn bridged assets through this module, which limits the
+//! damage a compromised bridge key can cause:
+//! - mints and burns of each asset are capped over a rolling window, the used
+//!   amount is released linearly over `WindowLength` blocks.
+//! - the guardian origin can pause mints and burns of an asset instantly, and
+//!   only the governance can unpause it.
+//! - mints not less than the delay threshold are executed after `MintDelay`
+//!   blocks, during which the governance can cancel them. At most
+//!   `MaxPendingMintsPerBlock` pending mints are executed per block, a mint
+//!   which fails is retried after `MintDelay` blocks until it is cancelled.
+//!
+//! Assets without limits are not capped and their mints are not delayed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get},
+	weights::Weight,
+	IterableStorageDoubleMap,
+};
+use frame_system::{self as system};
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	DispatchResult, Perbill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::BridgeSafety;
+
+mod mock;
+mod tests;
+
+pub type PendingMintId = u64;
+
+/// The safety limits of a bridged asset.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SafetyLimits {
+	/// The max amount minted in a window
+	pub mint_cap: Balance,
+	/// The max amount burnt in a window
+	pub burn_cap: Balance,
+	/// Mints not less than it are delayed
+	pub delay_threshold: Balance,
+}
+
+/// A delayed mint.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingMint<AccountId, BlockNumber> {
+	pub currency_id: CurrencyId,
+	pub who: AccountId,
+	pub amount: Balance,
+	/// The block number at which the mint is executed
+	pub execute_at: BlockNumber,
+}
+
+pub trait Trait: system::Trait {
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The currency used to mint and burn the bridged assets
+	type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+	/// The length of the window over which mints and burns are capped
+	type WindowLength: Get<Self::BlockNumber>;
+
+	/// The number of blocks large mints are delayed
+	type MintDelay: Get<Self::BlockNumber>;
+
+	/// The max number of pending mints executed in a block
+	type MaxPendingMintsPerBlock: Get<u32>;
+
+	/// The origin which may pause the bridged assets
+	type GuardianOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The origin which may update limits, unpause the bridged assets and
+	/// cancel pending mints
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as BridgeSafety {
+		/// The safety limits of bridged assets
+		Limits get(fn limits): map hasher(twox_64_concat) CurrencyId => Option<SafetyLimits>;
+
+		/// Whether mints and burns of the asset are paused
+		Paused get(fn paused): map hasher(twox_64_concat) CurrencyId => bool;
+
+		/// The amount minted in the window and the block number it was last
+		/// updated at
+		MintUsage get(fn mint_usage): map hasher(twox_64_concat) CurrencyId => (Balance, T::BlockNumber);
+
+		/// The amount burnt in the window and the block number it was last
+		/// updated at
+		BurnUsage get(fn burn_usage): map hasher(twox_64_concat) CurrencyId => (Balance, T::BlockNumber);
+
+		/// The delayed mints
+		PendingMints get(fn pending_mints): map hasher(twox_64_concat) PendingMintId => Option<PendingMint<T::AccountId, T::BlockNumber>>;
+
+		/// The delayed mints to execute at the block number
+		PendingMintQueue get(fn pending_mint_queue): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) PendingMintId => ();
+
+		/// The id of the next pending mint
+		NextPendingMintId get(fn next_pending_mint_id): PendingMintId;
+
+		/// The block number from which the queued pending mints are executed
+		NextExecuteBlock get(fn next_execute_block): T::BlockNumber;
+	}
+
+	add_extra_genesis {
+		config(limits): Vec<(CurrencyId, Balance, Balance, Balance)>;
+		build(|config: &GenesisConfig| {
+			for (currency_id, mint_cap, burn_cap, delay_threshold) in config.limits.iter() {
+				Limits::insert(currency_id, SafetyLimits {
+					mint_cap: *mint_cap,
+					burn_cap: *burn_cap,
+					delay_threshold: *delay_threshold,
+				});
+			}
+		})
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as system::Trait>::BlockNumber,
+	{
+		/// Safety limits updated \[currency_id, mint_cap, burn_cap, delay_threshold\]
+		LimitsUpdated(CurrencyId, Balance, Balance, Balance),
+		/// Mints and burns of the asset paused \[currency_id\]
+		Paused(CurrencyId),
+		/// Mints and burns of the asset unpaused \[currency_id\]
+		Unpaused(CurrencyId),
+		/// Large mint delayed \[pending_mint_id, currency_id, who, amount, execute_at\]
+		MintDelayed(PendingMintId, CurrencyId, AccountId, Balance, BlockNumber),
+		/// Pending mint executed \[pending_mint_id\]
+		PendingMintExecuted(PendingMintId),
+		/// Pending mint failed and is retried later \[pending_mint_id, retry_at\]
+		PendingMintFailed(PendingMintId, BlockNumber),
+		/// Pending mint cancelled \[pending_mint_id\]
+		PendingMintCancelled(PendingMintId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Mints and burns of the asset are paused.
+		AssetPaused,
+		/// The amount exceeds the mint cap of the window.
+		ExceedMintCap,
+		/// The amount exceeds the burn cap of the window.
+		ExceedBurnCap,
+		/// The pending mint does not exist.
+		PendingMintNotFound,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// The length of the window over which mints and burns are capped
+		const WindowLength: T::BlockNumber = T::WindowLength::get();
+
+		/// The number of blocks large mints are delayed
+		const MintDelay: T::BlockNumber = T::MintDelay::get();
+
+		/// The max number of pending mints executed in a block
+		const MaxPendingMintsPerBlock: u32 = T::MaxPendingMintsPerBlock::get();
+
+		/// Execute at most `MaxPendingMintsPerBlock` pending mints queued up to
+		/// current block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// skip the blocks without pending mints when the queue is empty
+			if <PendingMintQueue<T>>::iter().next().is_none() {
+				<NextExecuteBlock<T>>::put(now);
+				return T::DbWeight::get().reads_writes(1, 1);
+			}
+
+			let max_executions = T::MaxPendingMintsPerBlock::get();
+			let mut block = Self::next_execute_block();
+			let mut executions: u32 = 0;
+
+			while block <= now && executions < max_executions {
+				let pending_mint_ids = <PendingMintQueue<T>>::iter_prefix(block)
+					.take(max_executions.saturating_sub(executions) as usize)
+					.map(|(id, _)| id)
+					.collect::<Vec<_>>();
+				// a block without pending mints is counted as one execution to bound the
+				// blocks visited
+				executions = executions.saturating_add((pending_mint_ids.len() as u32).max(1));
+
+				for pending_mint_id in pending_mint_ids {
+					<PendingMintQueue<T>>::remove(block, pending_mint_id);
+					if let Some(pending_mint) = <PendingMints<T>>::take(pending_mint_id) {
+						Self::execute_pending_mint(now, pending_mint_id, pending_mint);
+					}
+				}
+				if <PendingMintQueue<T>>::iter_prefix(block).next().is_some() {
+					break;
+				}
+				block = block.saturating_add(One::one());
+			}
+			<NextExecuteBlock<T>>::put(block);
+
+			// the pending mint, pause switch, balance and total issuance are read and
+			// updated to execute each pending mint
+			T::DbWeight::get().reads_writes(3, 1).saturating_add(
+				T::DbWeight::get()
+					.reads_writes(5, 5)
+					.saturating_mul(executions as Weight),
+			)
+		}
+
+		/// Update the safety limits of the bridged asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn set_limits(
+			origin,
+			currency_id: CurrencyId,
+			#[compact] mint_cap: Balance,
+			#[compact] burn_cap: Balance,
+			#[compact] delay_threshold: Balance,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Limits::insert(currency_id, SafetyLimits {
+				mint_cap,
+				burn_cap,
+				delay_threshold,
+			});
+			Self::deposit_event(RawEvent::LimitsUpdated(currency_id, mint_cap, burn_cap, delay_threshold));
+		}
+
+		/// Pause mints and burns of the bridged asset.
+		///
+		/// The dispatch origin of this call must be `GuardianOrigin`.
+		#[weight = 10_000]
+		fn pause(origin, currency_id: CurrencyId) {
+			T::GuardianOrigin::ensure_origin(origin)?;
+			Paused::insert(currency_id, true);
+			Self::deposit_event(RawEvent::Paused(currency_id));
+		}
+
+		/// Unpause mints and burns of the bridged asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn unpause(origin, currency_id: CurrencyId) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Paused::remove(currency_id);
+			Self::deposit_event(RawEvent::Unpaused(currency_id));
+		}
+
+		/// Cancel the pending mint before it is executed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[weight = 10_000]
+		fn cancel_pending_mint(origin, #[compact] pending_mint_id: PendingMintId) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let pending_mint = <PendingMints<T>>::take(pending_mint_id).ok_or(Error::<T>::PendingMintNotFound)?;
+			<PendingMintQueue<T>>::remove(pending_mint.execute_at, pending_mint_id);
+			Self::deposit_event(RawEvent::PendingMintCancelled(pending_mint_id));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	// The amount used in the window after the release since last update, plus
+	// `amount`. Returns `None` if it exceeds `cap`.
+	fn checked_usage(usage: (Balance, T::BlockNumber), cap: Balance, amount: Balance) -> Option<Balance> {
+		let (used, updated_at) = usage;
+		let now = <frame_system::Module<T>>::block_number();
+		let elapsed = now.saturating_sub(updated_at);
+		let window = T::WindowLength::get();
+
+		let used = if elapsed >= window {
+			Zero::zero()
+		} else {
+			used.saturating_sub(Perbill::from_rational_approximation(elapsed, window) * cap)
+		};
+
+		used.checked_add(amount).filter(|used| *used <= cap)
+	}
+
+	// Check the pause switch and the mint cap, returns the limits and the new
+	// mint usage.
+	fn check_mint(currency_id: CurrencyId, amount: Balance) -> Result<Option<(SafetyLimits, Balance)>, Error<T>> {
+		ensure!(!Self::paused(currency_id), Error::<T>::AssetPaused);
+		match Self::limits(currency_id) {
+			Some(limits) => {
+				let used = Self::checked_usage(Self::mint_usage(currency_id), limits.mint_cap, amount)
+					.ok_or(Error::<T>::ExceedMintCap)?;
+				Ok(Some((limits, used)))
+			}
+			None => Ok(None),
+		}
+	}
+
+	fn execute_pending_mint(
+		now: T::BlockNumber,
+		pending_mint_id: PendingMintId,
+		mut pending_mint: PendingMint<T::AccountId, T::BlockNumber>,
+	) {
+		// postpone the mints of paused assets, to leave time for the governance
+		// to cancel them after unpause
+		if Self::paused(pending_mint.currency_id) {
+			Self::postpone_pending_mint(now, pending_mint_id, &mut pending_mint);
+			return;
+		}
+
+		match T::Currency::deposit(pending_mint.currency_id, &pending_mint.who, pending_mint.amount) {
+			Ok(_) => Self::deposit_event(RawEvent::PendingMintExecuted(pending_mint_id)),
+			Err(_) => {
+				// keep the failed mint, it is retried later until the governance cancels it
+				Self::postpone_pending_mint(now, pending_mint_id, &mut pending_mint);
+				Self::deposit_event(RawEvent::PendingMintFailed(pending_mint_id, pending_mint.execute_at));
+			}
+		}
+	}
+
+	fn postpone_pending_mint(
+		now: T::BlockNumber,
+		pending_mint_id: PendingMintId,
+		pending_mint: &mut PendingMint<T::AccountId, T::BlockNumber>,
+	) {
+		pending_mint.execute_at = now.saturating_add(T::MintDelay::get());
+		<PendingMintQueue<T>>::insert(pending_mint.execute_at, pending_mint_id, ());
+		<PendingMints<T>>::insert(pending_mint_id, pending_mint.clone());
+	}
+}
+
+impl<T: Trait> BridgeSafety<T::AccountId, CurrencyId, Balance> for Module<T> {
+	fn ensure_can_mint(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::check_mint(currency_id, amount)?;
+		Ok(())
+	}
+
+	fn mint(currency_id: CurrencyId, who: &T::AccountId, amount: Balance) -> DispatchResult {
+		let now = <frame_system::Module<T>>::block_number();
+
+		match Self::check_mint(currency_id, amount)? {
+			Some((limits, used)) => {
+				if amount >= limits.delay_threshold {
+					let pending_mint_id = Self::next_pending_mint_id();
+					let execute_at = now.saturating_add(T::MintDelay::get());
+					<PendingMints<T>>::insert(
+						pending_mint_id,
+						PendingMint {
+							currency_id,
+							who: who.clone(),
+							amount,
+							execute_at,
+						},
+					);
+					<PendingMintQueue<T>>::insert(execute_at, pending_mint_id, ());
+					NextPendingMintId::put(pending_mint_id.saturating_add(1));
+					Self::deposit_event(RawEvent::MintDelayed(
+						pending_mint_id,
+						currency_id,
+						who.clone(),
+						amount,
+						execute_at,
+					));
+				} else {
+					T::Currency::deposit(currency_id, who, amount)?;
+				}
+				<MintUsage<T>>::insert(currency_id, (used, now));
+			}
+			None => T::Currency::deposit(currency_id, who, amount)?,
+		}
+
+		Ok(())
+	}
+
+	fn burn(currency_id: CurrencyId, who: &T::AccountId, amount: Balance) -> DispatchResult {
+		ensure!(!Self::paused(currency_id), Error::<T>::AssetPaused);
+
+		match Self::limits(currency_id) {
+			Some(limits) => {
+				let used = Self::checked_usage(Self::burn_usage(currency_id), limits.burn_cap, amount)
+					.ok_or(Error::<T>::ExceedBurnCap)?;
+				T::Currency::withdraw(currency_id, who, amount)?;
+				<BurnUsage<T>>::insert(currency_id, (used, <frame_system::Module<T>>::block_number()));
+			}
+			None => T::Currency::withdraw(currency_id, who, amount)?,
+		}
+
+		Ok(())
+	}
+}
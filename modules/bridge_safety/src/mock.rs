@@ -0,0 +1,138 @@
+//! Mocks for bridge safety module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use primitives::Amount;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 0;
+pub const BOB: AccountId = 1;
+pub const GUARDIAN: AccountId = 2;
+pub const RENBTC: CurrencyId = CurrencyId::RENBTC;
+pub const DOT: CurrencyId = CurrencyId::DOT;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Runtime;
+
+mod bridge_safety {
+	pub use super::super::*;
+}
+
+impl_outer_origin! {
+	pub enum Origin for Runtime {}
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Runtime {
+		frame_system<T>,
+		bridge_safety<T>,
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: u32 = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+}
+pub type System = frame_system::Module<Runtime>;
+
+impl orml_tokens::Trait for Runtime {
+	type Event = ();
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type OnReceived = ();
+	type WeightInfo = ();
+}
+pub type Tokens = orml_tokens::Module<Runtime>;
+
+ord_parameter_types! {
+	pub const Guardian: AccountId = GUARDIAN;
+}
+
+parameter_types! {
+	pub const WindowLength: BlockNumber = 10;
+	pub const MintDelay: BlockNumber = 5;
+	pub const MaxPendingMintsPerBlock: u32 = 2;
+}
+
+impl Trait for Runtime {
+	type Event = TestEvent;
+	type Currency = Tokens;
+	type WindowLength = WindowLength;
+	type MintDelay = MintDelay;
+	type MaxPendingMintsPerBlock = MaxPendingMintsPerBlock;
+	type GuardianOrigin = EnsureSignedBy<Guardian, AccountId>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+}
+pub type BridgeSafetyModule = Module<Runtime>;
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(ALICE, RENBTC, 1000), (ALICE, DOT, 1000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		GenesisConfig {
+			limits: vec![(RENBTC, 1000, 500, 300)],
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,273 @@
+//! Unit tests for bridge safety module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{
+	BridgeSafetyModule, ExtBuilder, Origin, Runtime, System, TestEvent, Tokens, ALICE, BOB, DOT, GUARDIAN, RENBTC,
+};
+use sp_runtime::traits::BadOrigin;
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		BridgeSafetyModule::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn set_limits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			BridgeSafetyModule::set_limits(Origin::signed(ALICE), DOT, 100, 100, 50),
+			BadOrigin,
+		);
+		assert_eq!(BridgeSafetyModule::limits(DOT), None);
+		assert_ok!(BridgeSafetyModule::set_limits(Origin::root(), DOT, 100, 100, 50));
+		assert_eq!(
+			BridgeSafetyModule::limits(DOT),
+			Some(SafetyLimits {
+				mint_cap: 100,
+				burn_cap: 100,
+				delay_threshold: 50,
+			})
+		);
+
+		let limits_updated_event = TestEvent::bridge_safety(RawEvent::LimitsUpdated(DOT, 100, 100, 50));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == limits_updated_event));
+	});
+}
+
+#[test]
+fn pause_and_unpause_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(BridgeSafetyModule::pause(Origin::signed(ALICE), RENBTC), BadOrigin);
+		assert_ok!(BridgeSafetyModule::pause(Origin::signed(GUARDIAN), RENBTC));
+		assert!(BridgeSafetyModule::paused(RENBTC));
+
+		let paused_event = TestEvent::bridge_safety(RawEvent::Paused(RENBTC));
+		assert!(System::events().iter().any(|record| record.event == paused_event));
+
+		assert_noop!(
+			BridgeSafetyModule::ensure_can_mint(RENBTC, 10),
+			Error::<Runtime>::AssetPaused,
+		);
+		assert_noop!(
+			BridgeSafetyModule::mint(RENBTC, &BOB, 10),
+			Error::<Runtime>::AssetPaused,
+		);
+		assert_noop!(
+			BridgeSafetyModule::burn(RENBTC, &ALICE, 10),
+			Error::<Runtime>::AssetPaused,
+		);
+
+		// the guardian can not unpause
+		assert_noop!(BridgeSafetyModule::unpause(Origin::signed(GUARDIAN), RENBTC), BadOrigin);
+		assert_ok!(BridgeSafetyModule::unpause(Origin::root(), RENBTC));
+		assert!(!BridgeSafetyModule::paused(RENBTC));
+
+		let unpaused_event = TestEvent::bridge_safety(RawEvent::Unpaused(RENBTC));
+		assert!(System::events().iter().any(|record| record.event == unpaused_event));
+
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 10));
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 10);
+	});
+}
+
+#[test]
+fn mint_cap_released_over_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 299));
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 299));
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 299));
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 100));
+		assert_eq!(BridgeSafetyModule::mint_usage(RENBTC), (997, 1));
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 997);
+
+		assert_noop!(
+			BridgeSafetyModule::ensure_can_mint(RENBTC, 4),
+			Error::<Runtime>::ExceedMintCap,
+		);
+		assert_noop!(
+			BridgeSafetyModule::mint(RENBTC, &BOB, 4),
+			Error::<Runtime>::ExceedMintCap,
+		);
+
+		// half of the window passed, half of the cap is released
+		System::set_block_number(6);
+		assert_ok!(BridgeSafetyModule::ensure_can_mint(RENBTC, 503));
+		assert_noop!(
+			BridgeSafetyModule::ensure_can_mint(RENBTC, 504),
+			Error::<Runtime>::ExceedMintCap,
+		);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 200));
+		assert_eq!(BridgeSafetyModule::mint_usage(RENBTC), (697, 6));
+
+		// the whole window passed
+		System::set_block_number(16);
+		assert_ok!(BridgeSafetyModule::ensure_can_mint(RENBTC, 1000));
+	});
+}
+
+#[test]
+fn burn_cap_released_over_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::burn(RENBTC, &ALICE, 500));
+		assert_eq!(Tokens::free_balance(RENBTC, &ALICE), 500);
+		assert_noop!(
+			BridgeSafetyModule::burn(RENBTC, &ALICE, 1),
+			Error::<Runtime>::ExceedBurnCap,
+		);
+
+		System::set_block_number(11);
+		assert_ok!(BridgeSafetyModule::burn(RENBTC, &ALICE, 500));
+		assert_eq!(Tokens::free_balance(RENBTC, &ALICE), 0);
+	});
+}
+
+#[test]
+fn large_mint_delayed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 0);
+		assert_eq!(BridgeSafetyModule::mint_usage(RENBTC), (300, 1));
+		assert_eq!(
+			BridgeSafetyModule::pending_mints(0),
+			Some(PendingMint {
+				currency_id: RENBTC,
+				who: BOB,
+				amount: 300,
+				execute_at: 6,
+			})
+		);
+		assert_eq!(BridgeSafetyModule::next_pending_mint_id(), 1);
+
+		let mint_delayed_event = TestEvent::bridge_safety(RawEvent::MintDelayed(0, RENBTC, BOB, 300, 6));
+		assert!(System::events().iter().any(|record| record.event == mint_delayed_event));
+
+		run_to_block(5);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 0);
+
+		run_to_block(6);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 300);
+		assert_eq!(BridgeSafetyModule::pending_mints(0), None);
+
+		let executed_event = TestEvent::bridge_safety(RawEvent::PendingMintExecuted(0));
+		assert!(System::events().iter().any(|record| record.event == executed_event));
+	});
+}
+
+#[test]
+fn cancel_pending_mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+
+		assert_noop!(
+			BridgeSafetyModule::cancel_pending_mint(Origin::signed(GUARDIAN), 0),
+			BadOrigin,
+		);
+		assert_ok!(BridgeSafetyModule::cancel_pending_mint(Origin::root(), 0));
+		assert_eq!(BridgeSafetyModule::pending_mints(0), None);
+
+		let cancelled_event = TestEvent::bridge_safety(RawEvent::PendingMintCancelled(0));
+		assert!(System::events().iter().any(|record| record.event == cancelled_event));
+
+		assert_noop!(
+			BridgeSafetyModule::cancel_pending_mint(Origin::root(), 0),
+			Error::<Runtime>::PendingMintNotFound,
+		);
+
+		run_to_block(6);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 0);
+	});
+}
+
+#[test]
+fn pending_mint_of_paused_asset_postponed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+		assert_ok!(BridgeSafetyModule::pause(Origin::signed(GUARDIAN), RENBTC));
+
+		run_to_block(6);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 0);
+		assert_eq!(
+			BridgeSafetyModule::pending_mints(0).map(|mint| mint.execute_at),
+			Some(11)
+		);
+
+		assert_ok!(BridgeSafetyModule::unpause(Origin::root(), RENBTC));
+		run_to_block(11);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 300);
+	});
+}
+
+#[test]
+fn pending_mints_executed_up_to_max_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+		assert_ok!(BridgeSafetyModule::mint(RENBTC, &BOB, 300));
+
+		run_to_block(6);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 600);
+		assert_eq!(BridgeSafetyModule::next_execute_block(), 6);
+
+		run_to_block(7);
+		assert_eq!(Tokens::free_balance(RENBTC, &BOB), 900);
+		assert_eq!(BridgeSafetyModule::next_execute_block(), 8);
+		assert_eq!(PendingMintQueue::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn failed_pending_mint_kept_and_retried() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::set_limits(
+			Origin::root(),
+			DOT,
+			Balance::max_value(),
+			Balance::max_value(),
+			0
+		));
+		// overflows the total issuance when executed
+		assert_ok!(BridgeSafetyModule::mint(DOT, &BOB, Balance::max_value()));
+
+		run_to_block(6);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 0);
+		assert_eq!(
+			BridgeSafetyModule::pending_mints(0).map(|mint| mint.execute_at),
+			Some(11)
+		);
+
+		let failed_event = TestEvent::bridge_safety(RawEvent::PendingMintFailed(0, 11));
+		assert!(System::events().iter().any(|record| record.event == failed_event));
+
+		assert_ok!(BridgeSafetyModule::cancel_pending_mint(Origin::root(), 0));
+		run_to_block(11);
+		assert_eq!(BridgeSafetyModule::pending_mints(0), None);
+	});
+}
+
+#[test]
+fn asset_without_limits_not_capped() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BridgeSafetyModule::mint(DOT, &BOB, 10000));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 10000);
+		assert_eq!(BridgeSafetyModule::next_pending_mint_id(), 0);
+		assert_ok!(BridgeSafetyModule::burn(DOT, &BOB, 10000));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 0);
+	});
+}
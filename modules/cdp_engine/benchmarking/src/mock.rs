@@ -160,6 +160,10 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		Default::default()
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Default::default()
 	}
@@ -184,6 +188,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = DexModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = EmergencyShutdownModule;
+	type PriceSource = prices::Module<Runtime>;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -285,15 +291,27 @@ parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::one();
 }
 
+parameter_types! {
+	pub const DexTwapPeriod: u32 = 10;
+	pub const DefaultFeedExpiresIn: u64 = 60 * 60 * 1000;
+}
+
 impl prices::Trait for Runtime {
 	type Event = ();
 	type Source = orml_oracle::Module<Runtime>;
+	type Time = pallet_timestamp::Module<Runtime>;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
+	type DEX = ();
+	type DexTwapPeriod = DexTwapPeriod;
+	type FeedSources = ();
+	type DefaultFeedExpiresIn = DefaultFeedExpiresIn;
+	type PriceHistoryCurrencyIds = ();
+	type PriceHistoryLength = ();
 }
 
 pub struct MockConvert;
@@ -311,6 +329,7 @@ impl emergency_shutdown::Trait for Runtime {
 	type Event = ();
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = prices::Module<Runtime>;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = AuctionManagerModule;
 	type ShutdownOrigin = EnsureRoot<AccountId>;
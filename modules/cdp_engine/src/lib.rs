@@ -36,8 +36,8 @@ use sp_runtime::{
 };
 use sp_std::{marker, prelude::*};
 use support::{
-	CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio,
-	RiskManager,
+	CDPTreasury, CDPTreasuryExtended, CollateralCircuitBreaker, DEXManager, EmergencyShutdown, ExchangeRate, Price,
+	PriceProvider, Rate, Ratio, RiskManager,
 };
 
 mod debit_exchange_rate_convertor;
@@ -96,8 +96,9 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait + loans::Trait
 	/// multiple modules send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
-	/// Emergency shutdown.
-	type EmergencyShutdown: EmergencyShutdown;
+	/// Emergency shutdown, and the circuit breaker to freeze specific
+	/// collateral.
+	type EmergencyShutdown: EmergencyShutdown + CollateralCircuitBreaker<CurrencyId>;
 }
 
 /// Liquidation strategy available
@@ -148,8 +149,8 @@ decl_event!(
 		CurrencyId = CurrencyId,
 		Balance = Balance,
 	{
-		/// Liquidate the unsafe CDP. \[collateral_type, owner, collateral_amount, bad_debt_value, liquidation_strategy\]
-		LiquidateUnsafeCDP(CurrencyId, AccountId, Balance, Balance, LiquidationStrategy),
+		/// Liquidate the unsafe CDP. \[collateral_type, owner, collateral_amount, bad_debt_value, liquidation_strategy, collateral_price\]
+		LiquidateUnsafeCDP(CurrencyId, AccountId, Balance, Balance, LiquidationStrategy, Price),
 		/// Settle the CDP has debit. [collateral_type, owner]
 		SettleCDPInDebit(CurrencyId, AccountId),
 		/// The stability fee for specific collateral type updated. \[collateral_type, new_stability_fee\]
@@ -190,6 +191,8 @@ decl_error! {
 		AlreadyShutdown,
 		/// Must after system shutdown
 		MustAfterShutdown,
+		/// The collateral has been frozen
+		CollateralFrozen,
 	}
 }
 
@@ -518,6 +521,7 @@ impl<T: Trait> Module<T> {
 
 		let currency_id = collateral_currency_ids[(collateral_position as usize)];
 		let is_shutdown = T::EmergencyShutdown::is_shutdown();
+		let is_frozen = T::EmergencyShutdown::is_frozen(currency_id);
 
 		let mut map_iterator = <loans::Positions<T> as IterableStorageDoubleMapExtended<_, _, _>>::iter_prefix(
 			currency_id,
@@ -525,8 +529,8 @@ impl<T: Trait> Module<T> {
 			start_key,
 		);
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
-			if !is_shutdown && Self::is_cdp_unsafe(currency_id, collateral, debit) {
-				// liquidate unsafe CDPs before emergency shutdown occurs
+			if !is_shutdown && !is_frozen && Self::is_cdp_unsafe(currency_id, collateral, debit) {
+				// liquidate unsafe CDPs before emergency shutdown occurs, unless the collateral is frozen
 				Self::submit_unsigned_liquidation_tx(currency_id, who);
 			} else if is_shutdown && !debit.is_zero() {
 				// settle CDPs with debit after emergency shutdown occurs.
@@ -651,14 +655,22 @@ impl<T: Trait> Module<T> {
 
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		// the price of frozen collateral is not reliable, do not liquidate at it
+		ensure!(
+			!T::EmergencyShutdown::is_frozen(currency_id),
+			Error::<T>::CollateralFrozen
+		);
+
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		let stable_currency_id = T::GetStableCurrencyId::get();
 
-		// ensure the cdp is unsafe
-		ensure!(
-			Self::is_cdp_unsafe(currency_id, collateral, debit),
-			Error::<T>::MustBeUnsafe
-		);
+		// ensure the cdp is unsafe, and keep the price used for the event
+		let feed_price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+			.filter(|feed_price| {
+				Self::calculate_collateral_ratio(currency_id, collateral, debit, *feed_price)
+					< Self::get_liquidation_ratio(currency_id)
+			})
+			.ok_or(Error::<T>::MustBeUnsafe)?;
 
 		// confiscate all collateral and debit of unsafe cdp to cdp treasury
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
@@ -712,6 +724,7 @@ impl<T: Trait> Module<T> {
 			collateral,
 			bad_debt_value,
 			liquidation_strategy,
+			feed_price,
 		));
 		Ok(())
 	}
@@ -776,7 +789,10 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 		match call {
 			Call::liquidate(currency_id, who) => {
 				let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
-				if !Self::is_cdp_unsafe(*currency_id, collateral, debit) || T::EmergencyShutdown::is_shutdown() {
+				if !Self::is_cdp_unsafe(*currency_id, collateral, debit)
+					|| T::EmergencyShutdown::is_shutdown()
+					|| T::EmergencyShutdown::is_frozen(*currency_id)
+				{
 					return InvalidTransaction::Stale.into();
 				}
 
@@ -12,7 +12,7 @@ use sp_runtime::{
 	ModuleId, Perbill,
 };
 use sp_std::cell::RefCell;
-use support::{AuctionManager, EmergencyShutdown};
+use support::{AuctionManager, CollateralCircuitBreaker, EmergencyShutdown};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -175,7 +175,16 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
+thread_local! {
+	static COLLATERAL_AUCTIONS_COUNT: RefCell<u32> = RefCell::new(0);
+}
+
 pub struct MockAuctionManager;
+impl MockAuctionManager {
+	pub fn set_collateral_auctions_count(count: u32) {
+		COLLATERAL_AUCTIONS_COUNT.with(|v| *v.borrow_mut() = count);
+	}
+}
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
 	type CurrencyId = CurrencyId;
@@ -214,6 +223,10 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		COLLATERAL_AUCTIONS_COUNT.with(|v| *v.borrow())
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Default::default()
 	}
@@ -234,6 +247,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = MockEmergencyShutdown;
+	type PriceSource = MockPriceSource;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -259,12 +274,17 @@ pub type DEXModule = dex::Module<Runtime>;
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static FROZEN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_freeze(currency_id: CurrencyId) {
+	FROZEN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
@@ -272,6 +292,12 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+impl CollateralCircuitBreaker<CurrencyId> for MockEmergencyShutdown {
+	fn is_frozen(currency_id: CurrencyId) -> bool {
+		FROZEN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -401,6 +401,38 @@ fn remain_debit_value_too_small_check() {
 	});
 }
 
+#[test]
+fn liquidate_unsafe_cdp_of_frozen_collateral() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		mock_freeze(BTC);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::CollateralFrozen,
+		);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+	});
+}
+
 #[test]
 fn liquidate_unsafe_cdp_by_collateral_auction() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -440,6 +472,7 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 			100,
 			50,
 			LiquidationStrategy::Auction,
+			Price::one(),
 		));
 		assert!(System::events()
 			.iter()
@@ -459,6 +492,46 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn liquidate_unsafe_cdp_when_collateral_auctions_reach_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(1),
+		));
+		MockAuctionManager::set_collateral_auctions_count(1);
+
+		// the liquidation succeeds and the collateral is queued for auction
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::total_collateral_pending(BTC), 100);
+	});
+}
+
 #[test]
 fn on_finalize_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -0,0 +1,72 @@
+//! RPC interface for the cdp treasury module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_cdp_treasury_rpc_runtime_api::TreasuryHealth;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as CDPTreasuryClient;
+pub use module_cdp_treasury_rpc_runtime_api::CDPTreasuryApi as CDPTreasuryRuntimeApi;
+
+#[rpc]
+pub trait CDPTreasuryApi<BlockHash, ResponseType> {
+	#[rpc(name = "cdpTreasury_getTreasuryHealth")]
+	fn get_treasury_health(&self, at: Option<BlockHash>) -> Result<ResponseType>;
+}
+
+/// A struct that implements the [`CDPTreasuryApi`].
+pub struct CDPTreasury<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CDPTreasury<C, B> {
+	/// Create new `CDPTreasury` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		CDPTreasury {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, Balance> CDPTreasuryApi<<Block as BlockT>::Hash, TreasuryHealth<Balance>> for CDPTreasury<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CDPTreasuryRuntimeApi<Block, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_treasury_health(&self, at: Option<<Block as BlockT>::Hash>) -> Result<TreasuryHealth<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_treasury_health(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get treasury health.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -18,6 +18,20 @@ benchmarks! {
 	set_collateral_auction_maximum_size {
 		let u in 0 .. 1000;
 	}: _(RawOrigin::Root, CurrencyId::DOT, dollar(100))
+
+	set_collateral_auction_params {
+		let u in 0 .. 1000;
+	}: _(RawOrigin::Root, CurrencyId::DOT, Change::NewValue(dollar(10000)), Change::NewValue(10))
+
+	set_debit_and_surplus_handle_params {
+		let u in 0 .. 1000;
+	}: _(
+		RawOrigin::Root,
+		Change::NewValue(dollar(1000)),
+		Change::NewValue(dollar(200)),
+		Change::NewValue(dollar(100)),
+		Change::NewValue(dollar(100))
+	)
 }
 
 #[cfg(test)]
@@ -32,4 +46,18 @@ mod tests {
 			assert_ok!(test_benchmark_set_collateral_auction_maximum_size::<Runtime>());
 		});
 	}
+
+	#[test]
+	fn set_collateral_auction_params() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_collateral_auction_params::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn set_debit_and_surplus_handle_params() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_debit_and_surplus_handle_params::<Runtime>());
+		});
+	}
 }
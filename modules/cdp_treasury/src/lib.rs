@@ -12,17 +12,18 @@
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
 	traits::{EnsureOrigin, Get},
-	weights::{constants::WEIGHT_PER_MICROS, DispatchClass},
+	weights::{constants::WEIGHT_PER_MICROS, DispatchClass, Weight},
+	IterableStorageDoubleMap, IterableStorageMap,
 };
 use frame_system::{self as system};
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{Change, MultiCurrency, MultiCurrencyExtended};
 use orml_utilities::with_transaction_result;
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
+	traits::{AccountIdConversion, One, SaturatedConversion, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, ModuleId,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio};
+use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Ratio};
 
 mod benchmarking;
 mod mock;
@@ -51,19 +52,56 @@ pub trait Trait: system::Trait {
 
 	/// The cap of lots number when create collateral auction on a liquidation
 	/// or to create debit/surplus auction on block end.
-	/// If set to 0, does not work.
+	/// At least one lot is created, so 0 works as 1.
 	type MaxAuctionsCount: Get<u32>;
 
 	/// The CDP treasury's module id, keep surplus and collateral assets from
 	/// liquidation.
 	type ModuleId: Get<ModuleId>;
+
+	/// Emergency shutdown.
+	type EmergencyShutdown: EmergencyShutdown;
+
+	/// The price source of currencies, used to size collateral auction lots
+	/// by value.
+	type PriceSource: PriceProvider<CurrencyId>;
 }
 
+// typedef to help polkadot.js disambiguate Change with different generic
+// parameters
+type ChangeBalance = Change<Balance>;
+type ChangeU32 = Change<u32>;
+
 decl_event!(
 	pub enum Event {
 		/// The fixed size for collateral auction under specific collateral type
 		/// updated. \[collateral_type, new_size\]
 		CollateralAuctionMaximumSizeUpdated(CurrencyId, Balance),
+		/// The maximum value(in stable currency) of collateral for sale per
+		/// collateral auction under specific collateral type updated.
+		/// \[collateral_type, new_value\]
+		CollateralAuctionMaximumValueUpdated(CurrencyId, Balance),
+		/// The cap of concurrent collateral auctions under specific collateral
+		/// type updated. \[collateral_type, new_count\]
+		MaxConcurrentCollateralAuctionsUpdated(CurrencyId, u32),
+		/// The buffer size of surplus pool updated. \[new_size\]
+		SurplusBufferSizeUpdated(Balance),
+		/// The initial supply amount of native currency per debit auction
+		/// updated. \[new_amount\]
+		InitialAmountPerDebitAuctionUpdated(Balance),
+		/// The fixed size of debit auction updated. \[new_size\]
+		DebitAuctionFixedSizeUpdated(Balance),
+		/// The fixed size of surplus auction updated. \[new_size\]
+		SurplusAuctionFixedSizeUpdated(Balance),
+		/// Surplus auctions created automatically when block end.
+		/// \[auctions_count, fixed_size\]
+		SurplusAuctionsTriggered(u32, Balance),
+		/// Debit auctions created automatically when block end.
+		/// \[auctions_count, fixed_size\]
+		DebitAuctionsTriggered(u32, Balance),
+		/// Collateral queued until the running collateral auctions finish.
+		/// \[collateral_type, amount, target\]
+		CollateralAuctionQueued(CurrencyId, Balance, Balance),
 	}
 );
 
@@ -86,9 +124,43 @@ decl_storage! {
 		/// The maximum amount of collateral amount for sale per collateral auction
 		pub CollateralAuctionMaximumSize get(fn collateral_auction_maximum_size): map hasher(twox_64_concat) CurrencyId => Balance;
 
+		/// The maximum value(in stable currency) of collateral for sale per collateral auction,
+		/// it's converted to collateral amount by the current price when create collateral auctions.
+		pub CollateralAuctionMaximumValue get(fn collateral_auction_maximum_value): map hasher(twox_64_concat) CurrencyId => Balance;
+
+		/// The cap of active collateral auctions under specific collateral type,
+		/// the collateral is queued when it's reached and auctioned after the
+		/// running auctions finish. If set to 0, the count is not capped.
+		pub MaxConcurrentCollateralAuctions get(fn max_concurrent_collateral_auctions): map hasher(twox_64_concat) CurrencyId => u32;
+
+		/// The collateral waiting for the running collateral auctions to finish:
+		/// collateral type, pending id => (refund receiver, amount, target)
+		pub PendingCollateralAuctions get(fn pending_collateral_auctions): double_map hasher(twox_64_concat) CurrencyId, hasher(twox_64_concat) u32 => Option<(T::AccountId, Balance, Balance)>;
+
+		/// The total collateral amount queued under specific collateral type.
+		pub TotalCollateralPending get(fn total_collateral_pending): map hasher(twox_64_concat) CurrencyId => Balance;
+
+		/// The id of the next queued collateral auction.
+		NextPendingCollateralAuctionId get(fn next_pending_collateral_auction_id): u32;
+
 		/// Current total debit value of system. It's not same as debit in CDP engine,
 		/// it is the bad debt of the system.
 		pub DebitPool get(fn debit_pool): Balance;
+
+		/// The amount of surplus kept in surplus pool, surplus exceed this buffer
+		/// will be sold by surplus auction when block end.
+		pub SurplusBufferSize get(fn surplus_buffer_size) config(): Balance;
+
+		/// Initial amount of native currency for sale per debit auction.
+		pub InitialAmountPerDebitAuction get(fn initial_amount_per_debit_auction) config(): Balance;
+
+		/// The fixed amount of stable currency to get per debit auction.
+		/// If set to 0, debit auction will not be created automatically.
+		pub DebitAuctionFixedSize get(fn debit_auction_fixed_size) config(): Balance;
+
+		/// The fixed amount of stable currency for sale per surplus auction.
+		/// If set to 0, surplus auction will not be created automatically.
+		pub SurplusAuctionFixedSize get(fn surplus_auction_fixed_size) config(): Balance;
 	}
 
 	add_extra_genesis {
@@ -178,10 +250,126 @@ decl_module! {
 			})?;
 		}
 
+		/// Update parameters related to collateral auction lots sizing under specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `maximum_value`: the maximum value(in stable currency) of collateral per collateral auction,
+		///   `None` means do not update.
+		/// - `max_concurrent_auctions`: the cap of active collateral auctions, `None` means do not update.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 2
+		/// -------------------
+		/// Base Weight: 27.27 µs
+		/// # </weight>
+		#[weight = (27 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(0, 2), DispatchClass::Operational)]
+		pub fn set_collateral_auction_params(
+			origin,
+			currency_id: CurrencyId,
+			maximum_value: ChangeBalance,
+			max_concurrent_auctions: ChangeU32,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				if let Change::NewValue(value) = maximum_value {
+					CollateralAuctionMaximumValue::insert(currency_id, value);
+					Self::deposit_event(Event::CollateralAuctionMaximumValueUpdated(currency_id, value));
+				}
+				if let Change::NewValue(count) = max_concurrent_auctions {
+					MaxConcurrentCollateralAuctions::insert(currency_id, count);
+					Self::deposit_event(Event::MaxConcurrentCollateralAuctionsUpdated(currency_id, count));
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Update parameters related to surplus and debit auction
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `surplus_buffer_size`: the surplus amount kept in surplus pool,
+		///   `None` means do not update.
+		/// - `initial_amount_per_debit_auction`: initial native currency amount for sale per debit auction,
+		///   `None` means do not update.
+		/// - `debit_auction_fixed_size`: the fixed stable currency amount per debit auction,
+		///   `None` means do not update.
+		/// - `surplus_auction_fixed_size`: the fixed surplus amount per surplus auction,
+		///   `None` means do not update.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 4
+		/// -------------------
+		/// Base Weight: 32.34 µs
+		/// # </weight>
+		#[weight = (32 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(0, 4), DispatchClass::Operational)]
+		pub fn set_debit_and_surplus_handle_params(
+			origin,
+			surplus_buffer_size: ChangeBalance,
+			initial_amount_per_debit_auction: ChangeBalance,
+			debit_auction_fixed_size: ChangeBalance,
+			surplus_auction_fixed_size: ChangeBalance,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				if let Change::NewValue(amount) = surplus_buffer_size {
+					SurplusBufferSize::put(amount);
+					Self::deposit_event(Event::SurplusBufferSizeUpdated(amount));
+				}
+				if let Change::NewValue(amount) = initial_amount_per_debit_auction {
+					InitialAmountPerDebitAuction::put(amount);
+					Self::deposit_event(Event::InitialAmountPerDebitAuctionUpdated(amount));
+				}
+				if let Change::NewValue(amount) = debit_auction_fixed_size {
+					DebitAuctionFixedSize::put(amount);
+					Self::deposit_event(Event::DebitAuctionFixedSizeUpdated(amount));
+				}
+				if let Change::NewValue(amount) = surplus_auction_fixed_size {
+					SurplusAuctionFixedSize::put(amount);
+					Self::deposit_event(Event::SurplusAuctionFixedSizeUpdated(amount));
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Create at most `MaxAuctionsCount` queued collateral auctions, and
+		/// account the weight of handling excessive surplus or debits when
+		/// block end, at most `MaxAuctionsCount` auctions are created.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let pending_weight = if T::EmergencyShutdown::is_shutdown() {
+				0
+			} else {
+				// the pending collateral and quota are read to create each queued lot
+				let created_lots = Self::create_pending_collateral_auctions() as Weight;
+				T::DbWeight::get().reads_writes(5, 6).saturating_mul(created_lots)
+			};
+
+			// the pools, auction totals and params are read, and the pools are offset
+			let max_auctions_count = T::MaxAuctionsCount::get().max(1) as Weight;
+			T::DbWeight::get()
+				.reads_writes(11, 2)
+				.saturating_add(pending_weight)
+				.saturating_add(
+					T::DbWeight::get()
+						.reads_writes(3, 4)
+						.saturating_mul(max_auctions_count),
+				)
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: T::BlockNumber) {
 			// offset the same amount between debit pool and surplus pool
 			Self::offset_surplus_and_debit();
+
+			// stop creating surplus auction and debit auction after emergency shutdown happened
+			if !T::EmergencyShutdown::is_shutdown() {
+				Self::handle_excessive_surplus_and_debit();
+			}
 		}
 	}
 }
@@ -202,6 +390,257 @@ impl<T: Trait> Module<T> {
 		T::Currency::free_balance(currency_id, &Self::account_id())
 	}
 
+	/// Get the maximum collateral amount for sale per collateral auction under
+	/// specific collateral type. It's the smaller one of
+	/// `CollateralAuctionMaximumSize` and the amount of collateral valued
+	/// `CollateralAuctionMaximumValue` at current price, zero means unlimited.
+	pub fn collateral_auction_lot_size(currency_id: CurrencyId) -> Balance {
+		let maximum_size = Self::collateral_auction_maximum_size(currency_id);
+		let maximum_value = Self::collateral_auction_maximum_value(currency_id);
+
+		// the collateral amount equivalent to maximum value, at least 1 if the price is
+		// available
+		let value_limited_size = if maximum_value.is_zero() {
+			Zero::zero()
+		} else {
+			T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
+				.and_then(|price| price.checked_mul_int(maximum_value))
+				.map(|amount| sp_std::cmp::max(amount, One::one()))
+				.unwrap_or_else(Zero::zero)
+		};
+
+		if maximum_size.is_zero() {
+			value_limited_size
+		} else if value_limited_size.is_zero() {
+			maximum_size
+		} else {
+			sp_std::cmp::min(maximum_size, value_limited_size)
+		}
+	}
+
+	/// Get the surplus amount that is not in auction and exceeds the surplus
+	/// buffer size.
+	pub fn excessive_surplus() -> Balance {
+		Self::surplus_pool()
+			.saturating_sub(T::AuctionManagerHandler::get_total_surplus_in_auction())
+			.saturating_sub(Self::surplus_buffer_size())
+	}
+
+	/// Get the debit amount that is not in auction.
+	pub fn unhandled_debit() -> Balance {
+		Self::debit_pool().saturating_sub(T::AuctionManagerHandler::get_total_debit_in_auction())
+	}
+
+	/// Create surplus auctions for the surplus exceeding the buffer, and debit
+	/// auctions for the debit not in auction, in fixed size lots. The total
+	/// number of auctions created at once is limited by `MaxAuctionsCount`.
+	fn handle_excessive_surplus_and_debit() {
+		let max_auctions_count = T::MaxAuctionsCount::get().max(1);
+		let mut created_lots: u32 = 0;
+
+		// create surplus auction requires:
+		// surplus_pool >= total_surplus_in_auction + surplus_buffer_size +
+		// surplus_auction_fixed_size
+		let surplus_auction_fixed_size = Self::surplus_auction_fixed_size();
+		if !surplus_auction_fixed_size.is_zero() {
+			let mut remain_surplus = Self::excessive_surplus();
+			let mut surplus_lots: u32 = 0;
+			while remain_surplus >= surplus_auction_fixed_size && created_lots < max_auctions_count {
+				if T::AuctionManagerHandler::new_surplus_auction(surplus_auction_fixed_size).is_err() {
+					break;
+				}
+				created_lots = created_lots.saturating_add(1);
+				surplus_lots = surplus_lots.saturating_add(1);
+				remain_surplus = remain_surplus.saturating_sub(surplus_auction_fixed_size);
+			}
+
+			if !surplus_lots.is_zero() {
+				Self::deposit_event(Event::SurplusAuctionsTriggered(
+					surplus_lots,
+					surplus_auction_fixed_size,
+				));
+			}
+		}
+
+		// create debit auction requires:
+		// debit_pool >= total_debit_in_auction + debit_auction_fixed_size
+		let debit_auction_fixed_size = Self::debit_auction_fixed_size();
+		let initial_amount_per_debit_auction = Self::initial_amount_per_debit_auction();
+		if !debit_auction_fixed_size.is_zero() && !initial_amount_per_debit_auction.is_zero() {
+			let mut remain_debit = Self::unhandled_debit();
+			let mut debit_lots: u32 = 0;
+			while remain_debit >= debit_auction_fixed_size && created_lots < max_auctions_count {
+				if T::AuctionManagerHandler::new_debit_auction(
+					initial_amount_per_debit_auction,
+					debit_auction_fixed_size,
+				)
+				.is_err()
+				{
+					break;
+				}
+				created_lots = created_lots.saturating_add(1);
+				debit_lots = debit_lots.saturating_add(1);
+				remain_debit = remain_debit.saturating_sub(debit_auction_fixed_size);
+			}
+
+			if !debit_lots.is_zero() {
+				Self::deposit_event(Event::DebitAuctionsTriggered(debit_lots, debit_auction_fixed_size));
+			}
+		}
+	}
+
+	/// The remaining quota of concurrent collateral auctions under specific
+	/// collateral type, `None` means no cap.
+	fn remaining_collateral_auctions_quota(currency_id: CurrencyId) -> Option<Balance> {
+		let max_concurrent_auctions = Self::max_concurrent_collateral_auctions(currency_id);
+		if max_concurrent_auctions.is_zero() {
+			None
+		} else {
+			Some(
+				max_concurrent_auctions
+					.saturating_sub(T::AuctionManagerHandler::get_collateral_auctions_count(currency_id))
+					.into(),
+			)
+		}
+	}
+
+	/// Create collateral auctions in lots, at most `max_lots` lots are created
+	/// and the lots exceeding the remaining quota of concurrent collateral
+	/// auctions are queued. Returns the number of lots created.
+	fn auction_or_queue_collateral(
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+		refund_receiver: &T::AccountId,
+		splited: bool,
+		max_lots: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let mut unhandled_collateral_amount = amount;
+		let mut unhandled_target = target;
+		let collateral_auction_lot_size = Self::collateral_auction_lot_size(currency_id);
+		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
+		let lots_count = if !splited
+			|| max_auctions_count.is_zero()
+			|| collateral_auction_lot_size.is_zero()
+			|| amount <= collateral_auction_lot_size
+		{
+			One::one()
+		} else {
+			let mut count = amount
+				.checked_div(collateral_auction_lot_size)
+				.expect("collateral auction lot size is not zero; qed");
+
+			let remainder = amount
+				.checked_rem(collateral_auction_lot_size)
+				.expect("collateral auction lot size is not zero; qed");
+			if !remainder.is_zero() {
+				count = count.saturating_add(One::one());
+			}
+			sp_std::cmp::min(count, max_auctions_count)
+		};
+
+		// limit the lots created now by the remaining quota of concurrent collateral
+		// auctions, the rest is queued
+		let auctions_count = Self::remaining_collateral_auctions_quota(currency_id)
+			.map_or(lots_count, |quota| sp_std::cmp::min(lots_count, quota));
+		let auctions_count = sp_std::cmp::min(auctions_count, max_lots);
+
+		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
+		let mut created_lots: Balance = Zero::zero();
+
+		while created_lots < auctions_count && !unhandled_collateral_amount.is_zero() {
+			created_lots = created_lots.saturating_add(One::one());
+			let (lot_collateral_amount, lot_target) = if created_lots == lots_count {
+				// the last lot may be have some remnant than average
+				(unhandled_collateral_amount, unhandled_target)
+			} else {
+				// split target in proportion to the collateral amount of the lot
+				let lot_target = Ratio::checked_from_rational(average_amount_per_lot, amount)
+					.and_then(|n| n.checked_mul_int(target))
+					.unwrap_or_default();
+				(average_amount_per_lot, sp_std::cmp::min(lot_target, unhandled_target))
+			};
+
+			T::AuctionManagerHandler::new_collateral_auction(
+				refund_receiver,
+				currency_id,
+				lot_collateral_amount,
+				lot_target,
+			)?;
+
+			unhandled_collateral_amount = unhandled_collateral_amount.saturating_sub(lot_collateral_amount);
+			unhandled_target = unhandled_target.saturating_sub(lot_target);
+		}
+
+		if !unhandled_collateral_amount.is_zero() {
+			let pending_id = Self::next_pending_collateral_auction_id();
+			NextPendingCollateralAuctionId::put(pending_id.wrapping_add(1));
+			<PendingCollateralAuctions<T>>::insert(
+				currency_id,
+				pending_id,
+				(refund_receiver.clone(), unhandled_collateral_amount, unhandled_target),
+			);
+			TotalCollateralPending::mutate(currency_id, |pending| {
+				*pending = pending.saturating_add(unhandled_collateral_amount)
+			});
+			Self::deposit_event(Event::CollateralAuctionQueued(
+				currency_id,
+				unhandled_collateral_amount,
+				unhandled_target,
+			));
+		}
+
+		Ok(created_lots)
+	}
+
+	/// Create the queued collateral auctions within the remaining quota of
+	/// concurrent collateral auctions, at most `MaxAuctionsCount` lots are
+	/// created. Returns the number of lots created plus the queued collateral
+	/// visited.
+	fn create_pending_collateral_auctions() -> u32 {
+		let max_lots: Balance = T::MaxAuctionsCount::get().max(1).into();
+		let mut handled: Balance = Zero::zero();
+
+		for (currency_id, _) in TotalCollateralPending::iter().collect::<Vec<_>>() {
+			while handled < max_lots
+				&& Self::remaining_collateral_auctions_quota(currency_id).map_or(true, |quota| !quota.is_zero())
+			{
+				let (pending_id, (refund_receiver, amount, target)) =
+					match <PendingCollateralAuctions<T>>::iter_prefix(currency_id).next() {
+						Some(pending) => pending,
+						None => break,
+					};
+
+				let result = with_transaction_result(|| {
+					<PendingCollateralAuctions<T>>::remove(currency_id, pending_id);
+					TotalCollateralPending::mutate_exists(currency_id, |maybe_pending| {
+						let pending = maybe_pending.unwrap_or_default().saturating_sub(amount);
+						*maybe_pending = Some(pending).filter(|pending| !pending.is_zero());
+					});
+					Self::auction_or_queue_collateral(
+						currency_id,
+						amount,
+						target,
+						&refund_receiver,
+						true,
+						max_lots.saturating_sub(handled),
+					)
+				});
+
+				match result {
+					// the visit is counted even if no lot is created
+					Ok(created_lots) => handled = handled.saturating_add(created_lots.max(1)),
+					Err(_) => {
+						handled = handled.saturating_add(One::one());
+						break;
+					}
+				}
+			}
+		}
+
+		handled.saturated_into()
+	}
+
 	fn offset_surplus_and_debit() {
 		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
 
@@ -306,56 +745,19 @@ impl<T: Trait> CDPTreasuryExtended<T::AccountId> for Module<T> {
 		ensure!(
 			Self::total_collaterals(currency_id)
 				.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
+				.saturating_sub(Self::total_collateral_pending(currency_id))
 				>= amount,
 			Error::<T>::CollateralNotEnough,
 		);
 
-		let mut unhandled_collateral_amount = amount;
-		let mut unhandled_target = target;
-		let collateral_auction_maximum_size = Self::collateral_auction_maximum_size(currency_id);
-		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
-		let lots_count = if !splited
-			|| max_auctions_count.is_zero()
-			|| collateral_auction_maximum_size.is_zero()
-			|| amount <= collateral_auction_maximum_size
-		{
-			One::one()
-		} else {
-			let mut count = amount
-				.checked_div(collateral_auction_maximum_size)
-				.expect("collateral auction maximum size is not zero; qed");
-
-			let remainder = amount
-				.checked_rem(collateral_auction_maximum_size)
-				.expect("collateral auction maximum size is not zero; qed");
-			if !remainder.is_zero() {
-				count = count.saturating_add(One::one());
-			}
-			sp_std::cmp::min(count, max_auctions_count)
-		};
-		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
-		let average_target_per_lot = target.checked_div(lots_count).expect("lots count is at least 1; qed");
-		let mut created_lots: Balance = Zero::zero();
-
-		while !unhandled_collateral_amount.is_zero() {
-			created_lots = created_lots.saturating_add(One::one());
-			let (lot_collateral_amount, lot_target) = if created_lots == lots_count {
-				// the last lot may be have some remnant than average
-				(unhandled_collateral_amount, unhandled_target)
-			} else {
-				(average_amount_per_lot, average_target_per_lot)
-			};
-
-			T::AuctionManagerHandler::new_collateral_auction(
-				&refund_receiver,
-				currency_id,
-				lot_collateral_amount,
-				lot_target,
-			)?;
-
-			unhandled_collateral_amount = unhandled_collateral_amount.saturating_sub(lot_collateral_amount);
-			unhandled_target = unhandled_target.saturating_sub(lot_target);
-		}
+		Self::auction_or_queue_collateral(
+			currency_id,
+			amount,
+			target,
+			&refund_receiver,
+			splited,
+			Balance::max_value(),
+		)?;
 		Ok(())
 	}
 }
@@ -8,7 +8,7 @@ use frame_system::EnsureSignedBy;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
 use sp_std::cell::RefCell;
-use support::Rate;
+use support::{Price, Rate};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -142,10 +142,38 @@ pub type DEXModule = dex::Module<Runtime>;
 
 thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static CREATED_COLLATERAL_AUCTIONS: RefCell<Vec<(AccountId, CurrencyId, Balance, Balance)>> = RefCell::new(vec![]);
 	pub static TOTAL_DEBIT_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_SURPLUS_AUCTION: RefCell<u32> = RefCell::new(0);
 }
 
+thread_local! {
+	static RELATIVE_PRICE: RefCell<Option<Price>> = RefCell::new(None);
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_relative_price(price: Option<Price>) {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(base: CurrencyId, quote: CurrencyId) -> Option<Price> {
+		match (base, quote) {
+			(AUSD, BTC) => RELATIVE_PRICE.with(|v| *v.borrow_mut()),
+			_ => None,
+		}
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type CurrencyId = CurrencyId;
@@ -153,12 +181,13 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	type AuctionId = AuctionId;
 
 	fn new_collateral_auction(
-		_refund_recipient: &AccountId,
-		_currency_id: Self::CurrencyId,
-		_amount: Self::Balance,
-		_target: Self::Balance,
+		refund_recipient: &AccountId,
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
 	) -> DispatchResult {
 		TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut() += 1);
+		CREATED_COLLATERAL_AUCTIONS.with(|v| v.borrow_mut().push((*refund_recipient, currency_id, amount, target)));
 		Ok(())
 	}
 
@@ -180,6 +209,10 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut())
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Default::default()
 	}
@@ -206,6 +239,17 @@ thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+pub fn mock_shutdown() {
+	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
+}
+
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		IS_SHUTDOWN.with(|v| *v.borrow_mut())
+	}
+}
+
 impl Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
@@ -215,6 +259,8 @@ impl Trait for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = MockEmergencyShutdown;
+	type PriceSource = MockPriceSource;
 }
 pub type CDPTreasuryModule = Module<Runtime>;
 
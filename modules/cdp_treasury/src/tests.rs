@@ -3,9 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize},
+};
 use mock::*;
 use sp_runtime::traits::BadOrigin;
+use support::Price;
 
 #[test]
 fn surplus_pool_work() {
@@ -284,3 +288,284 @@ fn set_collateral_auction_maximum_size_work() {
 			.any(|record| record.event == update_collateral_auction_maximum_size_event));
 	});
 }
+
+#[test]
+fn set_debit_and_surplus_handle_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_debit_and_surplus_handle_params(
+				Origin::signed(5),
+				Change::NewValue(100),
+				Change::NewValue(1000),
+				Change::NewValue(200),
+				Change::NewValue(100),
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NewValue(100),
+			Change::NewValue(1000),
+			Change::NewValue(200),
+			Change::NoChange,
+		));
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 100);
+		assert_eq!(CDPTreasuryModule::initial_amount_per_debit_auction(), 1000);
+		assert_eq!(CDPTreasuryModule::debit_auction_fixed_size(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_auction_fixed_size(), 0);
+
+		let update_surplus_buffer_size_event = TestEvent::cdp_treasury(Event::SurplusBufferSizeUpdated(100));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_surplus_buffer_size_event));
+		let update_surplus_auction_fixed_size_event = TestEvent::cdp_treasury(Event::SurplusAuctionFixedSizeUpdated(0));
+		assert!(!System::events()
+			.iter()
+			.any(|record| record.event == update_surplus_auction_fixed_size_event));
+	});
+}
+
+#[test]
+fn trigger_surplus_auction_on_finalize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NewValue(500),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// surplus auction fixed size is zero, do not create surplus auction
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 0);
+		assert_eq!(CDPTreasuryModule::excessive_surplus(), 500);
+
+		// excessive surplus is 500, create 2 surplus auctions for 200
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(200),
+		));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 2);
+
+		let surplus_auctions_triggered_event = TestEvent::cdp_treasury(Event::SurplusAuctionsTriggered(2, 200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == surplus_auctions_triggered_event));
+	});
+}
+
+#[test]
+fn trigger_debit_auction_on_finalize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(2000));
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(300),
+			Change::NoChange,
+		));
+
+		// initial amount per debit auction is zero, do not create debit auction
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		// unhandled debit is 2000, create 6 debit auctions but limited by max auctions
+		// count
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NewValue(1000),
+			Change::NoChange,
+			Change::NoChange,
+		));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 5);
+
+		let debit_auctions_triggered_event = TestEvent::cdp_treasury(Event::DebitAuctionsTriggered(5, 300));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == debit_auctions_triggered_event));
+	});
+}
+
+#[test]
+fn do_not_trigger_auctions_after_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::set_debit_and_surplus_handle_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(200),
+		));
+		mock_shutdown();
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 0);
+	});
+}
+
+#[test]
+fn set_collateral_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_params(
+				Origin::signed(5),
+				BTC,
+				Change::NewValue(1000),
+				Change::NewValue(2)
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(1000),
+			Change::NewValue(2)
+		));
+		assert_eq!(CDPTreasuryModule::collateral_auction_maximum_value(BTC), 1000);
+		assert_eq!(CDPTreasuryModule::max_concurrent_collateral_auctions(BTC), 2);
+
+		let update_collateral_auction_maximum_value_event =
+			TestEvent::cdp_treasury(Event::CollateralAuctionMaximumValueUpdated(BTC, 1000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_collateral_auction_maximum_value_event));
+		let update_max_concurrent_collateral_auctions_event =
+			TestEvent::cdp_treasury(Event::MaxConcurrentCollateralAuctionsUpdated(BTC, 2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_max_concurrent_collateral_auctions_event));
+	});
+}
+
+#[test]
+fn collateral_auction_lot_size_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPTreasuryModule::collateral_auction_lot_size(BTC), 0);
+
+		// only maximum size
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_size(
+			Origin::signed(1),
+			BTC,
+			300
+		));
+		assert_eq!(CDPTreasuryModule::collateral_auction_lot_size(BTC), 300);
+
+		// price is unavailable, fallback to maximum size
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(1000),
+			Change::NoChange
+		));
+		assert_eq!(CDPTreasuryModule::collateral_auction_lot_size(BTC), 300);
+
+		// 1 BTC = 10 AUSD, maximum value 1000 AUSD is 100 BTC
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 10)));
+		assert_eq!(CDPTreasuryModule::collateral_auction_lot_size(BTC), 100);
+
+		// 1 BTC = 2 AUSD, maximum value 1000 AUSD is 500 BTC
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(CDPTreasuryModule::collateral_auction_lot_size(BTC), 300);
+	});
+}
+
+#[test]
+fn create_collateral_auctions_by_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Currencies::deposit(BTC, &CDPTreasuryModule::account_id(), 10000));
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 10)));
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(1000),
+			Change::NoChange
+		));
+
+		// lot size is 100 BTC, split 250 BTC to 3 lots, target is split in proportion
+		// to the collateral of lots
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 250, 1000, ALICE, true
+		));
+		assert_eq!(
+			CREATED_COLLATERAL_AUCTIONS.with(|v| v.borrow().clone()),
+			vec![(ALICE, BTC, 83, 332), (ALICE, BTC, 83, 332), (ALICE, BTC, 84, 336)]
+		);
+	});
+}
+
+#[test]
+fn create_collateral_auctions_limited_by_max_concurrent_auctions() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(BTC, &CDPTreasuryModule::account_id(), 10000));
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_size(
+			Origin::signed(1),
+			BTC,
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(3)
+		));
+
+		// 3 lots at most
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 1000, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 3);
+
+		// the lots exceeding the quota are queued
+		assert_eq!(
+			CDPTreasuryModule::pending_collateral_auctions(BTC, 0),
+			Some((ALICE, 400, 400))
+		);
+		assert_eq!(CDPTreasuryModule::total_collateral_pending(BTC), 400);
+
+		// quota is used up, the whole collateral is queued
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 1000, BOB, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 3);
+		assert_eq!(
+			CDPTreasuryModule::pending_collateral_auctions(BTC, 1),
+			Some((BOB, 1000, 1000))
+		);
+		assert_eq!(CDPTreasuryModule::total_collateral_pending(BTC), 1400);
+		let queued_event = TestEvent::cdp_treasury(Event::CollateralAuctionQueued(BTC, 1000, 1000));
+		assert!(System::events().iter().any(|record| record.event == queued_event));
+
+		// the queued collateral can't be auctioned again
+		assert_noop!(
+			CDPTreasuryModule::create_collateral_auctions(BTC, 8601, 1000, BOB, true),
+			Error::<Runtime>::CollateralNotEnough,
+		);
+
+		// no queued auction is created until the running auctions finish
+		CDPTreasuryModule::on_initialize(1);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 3);
+
+		// two auctions finished, two queued lots are created
+		TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut() = 1);
+		CREATED_COLLATERAL_AUCTIONS.with(|v| v.borrow_mut().clear());
+		CDPTreasuryModule::on_initialize(2);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 3);
+		let created_amount: Balance = CREATED_COLLATERAL_AUCTIONS.with(|v| v.borrow().iter().map(|lot| lot.2).sum());
+		assert_eq!(CREATED_COLLATERAL_AUCTIONS.with(|v| v.borrow().len()), 2);
+		assert_eq!(CDPTreasuryModule::total_collateral_pending(BTC), 1400 - created_amount);
+	});
+}
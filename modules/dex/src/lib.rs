@@ -300,40 +300,7 @@ decl_module! {
 		pub fn withdraw_liquidity(origin, currency_id: CurrencyId, #[compact] remove_share: T::Share) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				if remove_share.is_zero() { return Ok(()); }
-				ensure!(
-					T::EnabledCurrencyIds::get().contains(&currency_id),
-					Error::<T>::CurrencyIdNotAllowed,
-				);
-				let (other_currency_pool, base_currency_pool): (Balance, Balance) = Self::liquidity_pool(currency_id);
-				let proportion = Ratio::checked_from_rational(remove_share, Self::total_shares(currency_id)).unwrap_or_default();
-				let withdraw_other_currency_amount = proportion.saturating_mul_int(other_currency_pool);
-				let withdraw_base_currency_amount = proportion.saturating_mul_int(base_currency_pool);
-
-				T::Currency::transfer(currency_id, &Self::account_id(), &who, withdraw_other_currency_amount)?;
-				T::Currency::transfer(T::GetBaseCurrencyId::get(), &Self::account_id(), &who, withdraw_base_currency_amount)?;
-
-				<Shares<T>>::try_mutate(currency_id, &who, |share| -> DispatchResult {
-					*share = share.checked_sub(&remove_share).ok_or(Error::<T>::ShareNotEnough)?;
-					Ok(())
-				})?;
-				<TotalShares<T>>::mutate(currency_id, |share|
-					*share = share.checked_sub(&remove_share).expect("total share cannot underflow if share doesn't; qed")
-				);
-				LiquidityPool::mutate(currency_id, |(other, base)| {
-					*other = other.saturating_sub(withdraw_other_currency_amount);
-					*base = base.saturating_sub(withdraw_base_currency_amount);
-				});
-				T::OnRemoveLiquidity::happened(&(who.clone(), currency_id, remove_share));
-
-				Self::deposit_event(RawEvent::WithdrawLiquidity(
-					who,
-					currency_id,
-					withdraw_other_currency_amount,
-					withdraw_base_currency_amount,
-					remove_share,
-				));
-				Ok(())
+				Self::do_withdraw_liquidity(who, currency_id, remove_share)
 			})?;
 		}
 	}
@@ -344,6 +311,53 @@ impl<T: Trait> Module<T> {
 		T::ModuleId::get().into_account()
 	}
 
+	fn do_withdraw_liquidity(who: T::AccountId, currency_id: CurrencyId, remove_share: T::Share) -> DispatchResult {
+		if remove_share.is_zero() {
+			return Ok(());
+		}
+		ensure!(
+			T::EnabledCurrencyIds::get().contains(&currency_id),
+			Error::<T>::CurrencyIdNotAllowed,
+		);
+		let (other_currency_pool, base_currency_pool): (Balance, Balance) = Self::liquidity_pool(currency_id);
+		let proportion =
+			Ratio::checked_from_rational(remove_share, Self::total_shares(currency_id)).unwrap_or_default();
+		let withdraw_other_currency_amount = proportion.saturating_mul_int(other_currency_pool);
+		let withdraw_base_currency_amount = proportion.saturating_mul_int(base_currency_pool);
+
+		T::Currency::transfer(currency_id, &Self::account_id(), &who, withdraw_other_currency_amount)?;
+		T::Currency::transfer(
+			T::GetBaseCurrencyId::get(),
+			&Self::account_id(),
+			&who,
+			withdraw_base_currency_amount,
+		)?;
+
+		<Shares<T>>::try_mutate(currency_id, &who, |share| -> DispatchResult {
+			*share = share.checked_sub(&remove_share).ok_or(Error::<T>::ShareNotEnough)?;
+			Ok(())
+		})?;
+		<TotalShares<T>>::mutate(currency_id, |share| {
+			*share = share
+				.checked_sub(&remove_share)
+				.expect("total share cannot underflow if share doesn't; qed")
+		});
+		LiquidityPool::mutate(currency_id, |(other, base)| {
+			*other = other.saturating_sub(withdraw_other_currency_amount);
+			*base = base.saturating_sub(withdraw_base_currency_amount);
+		});
+		T::OnRemoveLiquidity::happened(&(who.clone(), currency_id, remove_share));
+
+		Self::deposit_event(RawEvent::WithdrawLiquidity(
+			who,
+			currency_id,
+			withdraw_other_currency_amount,
+			withdraw_base_currency_amount,
+			remove_share,
+		));
+		Ok(())
+	}
+
 	fn calculate_swap_target_amount(
 		supply_pool: Balance,
 		target_pool: Balance,
@@ -748,4 +762,12 @@ impl<T: Trait> DEXManager<T::AccountId, CurrencyId, Balance> for Module<T> {
 	fn get_liquidity_pool(currency_id: CurrencyId) -> (Balance, Balance) {
 		Self::liquidity_pool(currency_id)
 	}
+
+	fn get_shares(currency_id: CurrencyId, who: &T::AccountId) -> Balance {
+		Self::shares(currency_id, who).unique_saturated_into()
+	}
+
+	fn withdraw_liquidity(who: T::AccountId, currency_id: CurrencyId, share_amount: Balance) -> DispatchResult {
+		Self::do_withdraw_liquidity(who, currency_id, share_amount.unique_saturated_into())
+	}
 }
@@ -110,6 +110,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = ();
+	type PriceSource = ();
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -139,6 +141,9 @@ impl AuctionManager<AccountId> for MockAuctionManagerHandler {
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		unimplemented!()
 	}
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		unimplemented!()
+	}
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		unimplemented!()
 	}
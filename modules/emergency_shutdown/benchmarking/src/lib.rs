@@ -16,7 +16,7 @@ use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 
 use emergency_shutdown::Module as EmergencyShutdown;
 use emergency_shutdown::*;
-use orml_traits::{DataFeeder, MultiCurrencyExtended};
+use orml_traits::{Change, DataFeeder, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use support::{CDPTreasury, Price};
 
@@ -42,6 +42,25 @@ fn feed_price<T: Trait>(currency_id: CurrencyId, price: Price) -> Result<(), &'s
 	Ok(())
 }
 
+fn prepare_cash<T: Trait>(caller: &T::AccountId) -> Result<(), &'static str> {
+	let funder: T::AccountId = account("funder", 0, SEED);
+	let currency_ids = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get();
+	for currency_id in currency_ids.iter().copied() {
+		feed_price::<T>(currency_id, Price::one())?;
+		<T as loans::Trait>::Currency::update_balance(currency_id, &funder, dollar(100).unique_saturated_into())?;
+		<T as emergency_shutdown::Trait>::CDPTreasury::deposit_collateral(&funder, currency_id, dollar(100))?;
+	}
+	<T as emergency_shutdown::Trait>::CDPTreasury::issue_debit(caller, dollar(1000), true)?;
+	<T as emergency_shutdown::Trait>::CDPTreasury::issue_debit(&funder, dollar(9000), true)?;
+
+	EmergencyShutdown::<T>::emergency_shutdown(RawOrigin::Root.into())?;
+	for currency_id in currency_ids {
+		EmergencyShutdown::<T>::fix_cage_price(RawOrigin::Signed(funder.clone()).into(), currency_id)?;
+	}
+	EmergencyShutdown::<T>::open_collateral_refund(RawOrigin::Root.into())?;
+	Ok(())
+}
+
 benchmarks! {
 	_ { }
 
@@ -58,22 +77,47 @@ benchmarks! {
 		EmergencyShutdown::<T>::emergency_shutdown(RawOrigin::Root.into())?;
 	}: _(RawOrigin::Root)
 
-	refund_collaterals {
+	freeze_collateral {
 		let u in 0 .. 1000;
 
-		let funder: T::AccountId = account("funder", u, SEED);
-		let caller: T::AccountId = account("caller", u, SEED);
-		let currency_ids = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get();
-		for currency_id in currency_ids {
-			<T as loans::Trait>::Currency::update_balance(currency_id, &funder, dollar(100).unique_saturated_into())?;
-			<T as emergency_shutdown::Trait>::CDPTreasury::deposit_collateral(&funder, currency_id, dollar(100))?;
-		}
-		<T as emergency_shutdown::Trait>::CDPTreasury::issue_debit(&caller, dollar(1000), true)?;
-		<T as emergency_shutdown::Trait>::CDPTreasury::issue_debit(&funder, dollar(9000), true)?;
+		let currency_id = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get()[0];
+	}: _(RawOrigin::Root, currency_id)
+
+	unfreeze_collateral {
+		let u in 0 .. 1000;
 
+		let currency_id = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get()[0];
+		EmergencyShutdown::<T>::freeze_collateral(RawOrigin::Root.into(), currency_id)?;
+	}: _(RawOrigin::Root, currency_id)
+
+	set_settlement_delays {
+		let u in 0 .. 1000;
+	}: _(RawOrigin::Root, Change::NewValue(10.into()), Change::NewValue(20.into()), Change::NewValue(30.into()))
+
+	fix_cage_price {
+		let u in 0 .. 1000;
+
+		let caller: T::AccountId = account("caller", u, SEED);
+		let currency_id = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get()[0];
+		feed_price::<T>(currency_id, Price::one())?;
 		EmergencyShutdown::<T>::emergency_shutdown(RawOrigin::Root.into())?;
-		EmergencyShutdown::<T>::open_collateral_refund(RawOrigin::Root.into())?;
-	}: _(RawOrigin::Signed(caller),  dollar(1000))
+	}: _(RawOrigin::Signed(caller), currency_id)
+
+	pack {
+		let u in 0 .. 1000;
+
+		let caller: T::AccountId = account("caller", u, SEED);
+		prepare_cash::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), dollar(1000))
+
+	cash {
+		let u in 0 .. 1000;
+
+		let caller: T::AccountId = account("caller", u, SEED);
+		let currency_id = <T as emergency_shutdown::Trait>::CollateralCurrencyIds::get()[0];
+		prepare_cash::<T>(&caller)?;
+		EmergencyShutdown::<T>::pack(RawOrigin::Signed(caller.clone()).into(), dollar(1000))?;
+	}: _(RawOrigin::Signed(caller), currency_id, dollar(1000))
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -98,9 +142,44 @@ mod tests {
 	}
 
 	#[test]
-	fn refund_collaterals() {
+	fn freeze_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_freeze_collateral::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn unfreeze_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unfreeze_collateral::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn set_settlement_delays() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_settlement_delays::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn fix_cage_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_fix_cage_price::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn pack() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pack::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn cash() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(test_benchmark_refund_collaterals::<Runtime>());
+			assert_ok!(test_benchmark_cash::<Runtime>());
 		});
 	}
 }
@@ -10,6 +10,22 @@
 //! CDPs has debit, cancel all active auctions module, when debits and gaps are
 //! settled, the stable currency holder are allowed to refund a basket of
 //! remaining collateral assets.
+//!
+//! Besides the global shutdown, a single collateral can be frozen when its
+//! feed price breaks. CDPs of the frozen collateral cannot mint, withdraw or
+//! be liquidated, while the rest of the system keeps running.
+//!
+//! The final settlement works in several steps, each of them is gated by a
+//! block delay configured by governance:
+//! 1. After `CagePriceDelay` blocks since shutdown, the cage price of every
+//!    collateral is fixed from its locked price. Governance can set the cage
+//!    price directly if the locked price of a collateral is invalid.
+//! 2. After `SettlementDelay` blocks since shutdown, the final redemption is
+//!    opened, the total debt is fixed and the cash rate and debt share of
+//!    every collateral are computed.
+//! 3. After `CashDelay` blocks since the final redemption opened, the stable
+//!    currency holders pack stable currency into their bag, and cash out one
+//!    collateral at a time at the fixed cash rate of it.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -19,15 +35,21 @@ use frame_support::{
 	weights::{constants::WEIGHT_PER_MICROS, DispatchClass},
 };
 use frame_system::{self as system, ensure_signed};
+use orml_traits::{Change, MultiCurrency};
 use orml_utilities::with_transaction_result;
 use primitives::{Balance, CurrencyId};
-use sp_runtime::{traits::Zero, FixedPointNumber};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchResult, FixedPointNumber,
+};
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, EmergencyShutdown, PriceProvider, Ratio};
+use support::{AuctionManager, CDPTreasury, CollateralCircuitBreaker, EmergencyShutdown, Price, PriceProvider, Ratio};
 
 mod mock;
 mod tests;
 
+type ChangeBlockNumberOf<T> = Change<<T as system::Trait>::BlockNumber>;
+
 pub trait Trait: system::Trait + loans::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
@@ -37,6 +59,9 @@ pub trait Trait: system::Trait + loans::Trait {
 	/// Price source to freeze currencies' price
 	type PriceSource: PriceProvider<CurrencyId>;
 
+	/// The stable currency id, it should be AUSD in Acala.
+	type GetStableCurrencyId: Get<CurrencyId>;
+
 	/// CDP treasury to escrow collateral assets after settlement
 	type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
@@ -44,8 +69,8 @@ pub trait Trait: system::Trait + loans::Trait {
 	/// redemption
 	type AuctionManagerHandler: AuctionManager<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
-	/// The origin which may trigger emergency shutdown. Root can always do
-	/// this.
+	/// The origin which may trigger emergency shutdown or freeze collateral.
+	/// Root can always do this.
 	type ShutdownOrigin: EnsureOrigin<Self::Origin>;
 }
 
@@ -60,8 +85,24 @@ decl_event!(
 		Shutdown(BlockNumber),
 		/// The final redemption opened. \[block_number\]
 		OpenRefund(BlockNumber),
-		/// Refund info. \[caller, stable_coin_amount, refund_list\]
-		Refund(AccountId, Balance, Vec<(CurrencyId, Balance)>),
+		/// The cage price of collateral has been fixed. \[collateral_type, cage_price\]
+		CagePriceFixed(CurrencyId, Price),
+		/// The cash rate and debt share of collateral has been fixed. \[collateral_type, cash_rate, debt_share\]
+		CollateralRateFixed(CurrencyId, Ratio, Ratio),
+		/// Stable currency has been packed into bag. \[who, stable_coin_amount\]
+		Pack(AccountId, Balance),
+		/// Collateral has been cashed out. \[who, collateral_type, stable_coin_amount, collateral_amount\]
+		Cash(AccountId, CurrencyId, Balance, Balance),
+		/// The delay before fixing cage prices has been updated. \[new_delay\]
+		CagePriceDelayUpdated(BlockNumber),
+		/// The delay before opening final redemption has been updated. \[new_delay\]
+		SettlementDelayUpdated(BlockNumber),
+		/// The delay before cashing out has been updated. \[new_delay\]
+		CashDelayUpdated(BlockNumber),
+		/// Collateral has been frozen. \[collateral_type\]
+		CollateralFrozen(CurrencyId),
+		/// Collateral has been unfrozen. \[collateral_type\]
+		CollateralUnfrozen(CurrencyId),
 	}
 );
 
@@ -78,6 +119,24 @@ decl_error! {
 		ExistPotentialSurplus,
 		/// Exist unhandled debit, means settlement has not been completed
 		ExistUnhandledDebit,
+		/// The currency is not a valid collateral type
+		InvalidCollateralType,
+		/// Cage price of this collateral has already been fixed
+		CagePriceAlreadyFixed,
+		/// Cage price of collateral has not been fixed
+		CagePriceNotFixed,
+		/// Feed price is invalid
+		InvalidFeedPrice,
+		/// The delay of this step has not passed yet
+		DelayNotPassed,
+		/// The stable currency in bag is not enough to cash out
+		BagNotEnough,
+		/// Collateral has already been frozen
+		AlreadyFrozen,
+		/// Collateral is not frozen
+		NotFrozen,
+		/// Final redemption has already been opened
+		AlreadyOpenedRefund,
 	}
 }
 
@@ -87,6 +146,46 @@ decl_storage! {
 		pub IsShutdown get(fn is_shutdown): bool;
 		/// Open final redemption flag
 		pub CanRefund get(fn can_refund): bool;
+
+		/// Mapping from collateral type to its frozen flag
+		pub FrozenCollaterals get(fn frozen_collaterals): map hasher(twox_64_concat) CurrencyId => bool;
+
+		/// The block number when emergency shutdown occurs
+		pub ShutdownBlockNumber get(fn shutdown_block_number): Option<T::BlockNumber>;
+
+		/// The block number when final redemption opened
+		pub OpenRefundBlockNumber get(fn open_refund_block_number): Option<T::BlockNumber>;
+
+		/// The blocks to wait after shutdown before fixing cage prices
+		pub CagePriceDelay get(fn cage_price_delay) config(): T::BlockNumber;
+
+		/// The blocks to wait after shutdown before opening final redemption
+		pub SettlementDelay get(fn settlement_delay) config(): T::BlockNumber;
+
+		/// The blocks to wait after final redemption opened before cashing out
+		pub CashDelay get(fn cash_delay) config(): T::BlockNumber;
+
+		/// Mapping from collateral type to its cage price (the stable currency
+		/// value of a unit of collateral), fixed from the locked price
+		pub CagePrices get(fn cage_prices): map hasher(twox_64_concat) CurrencyId => Option<Price>;
+
+		/// The total debt of stable currency, fixed when final redemption opened
+		pub TotalDebt get(fn total_debt): Balance;
+
+		/// Mapping from collateral type to the collateral amount paid out per
+		/// unit of stable currency
+		pub CashRates get(fn cash_rates): map hasher(twox_64_concat) CurrencyId => Option<Ratio>;
+
+		/// Mapping from collateral type to the share of the total debt backed
+		/// by this collateral, valued at cage price
+		pub DebtShares get(fn debt_shares): map hasher(twox_64_concat) CurrencyId => Ratio;
+
+		/// Mapping from account to the stable currency amount packed in bag
+		pub Bags get(fn bags): map hasher(twox_64_concat) T::AccountId => Balance;
+
+		/// The stable currency amount in bag which has been used to cash out
+		/// specific collateral, CollateralType -> Owner -> Amount
+		pub Cashed get(fn cashed): double_map hasher(twox_64_concat) CurrencyId, hasher(twox_64_concat) T::AccountId => Balance;
 	}
 }
 
@@ -98,6 +197,9 @@ decl_module! {
 		/// The list of valid collateral currency types
 		const CollateralCurrencyIds: Vec<CurrencyId> = T::CollateralCurrencyIds::get();
 
+		/// Stable currency id
+		const GetStableCurrencyId: CurrencyId = T::GetStableCurrencyId::get();
+
 		/// Start emergency shutdown
 		///
 		/// The dispatch origin of this call must be `ShutdownOrigin`.
@@ -109,14 +211,14 @@ decl_module! {
 		/// 	- T::OnShutdown is (module_cdp_treasury, module_cdp_engine, module_honzon, module_dex)
 		/// - Complexity: `O(1)`
 		/// - Db reads: `IsShutdown`, (length of collateral_ids) items in modules related to module_emergency_shutdown
-		/// - Db writes: `IsShutdown`, (4 + length of collateral_ids) items in modules related to module_emergency_shutdown
+		/// - Db writes: `IsShutdown`, `ShutdownBlockNumber`, (4 + length of collateral_ids) items in modules related to module_emergency_shutdown
 		/// -------------------
 		/// Base Weight: 148.3 µs
 		/// # </weight>
 		#[weight = (
 			148 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(
 				1 + (T::CollateralCurrencyIds::get().len() as u64),
-				5 + (T::CollateralCurrencyIds::get().len() as u64),
+				6 + (T::CollateralCurrencyIds::get().len() as u64),
 			),
 			DispatchClass::Operational,
 		)]
@@ -133,13 +235,180 @@ decl_module! {
 					<T as Trait>::PriceSource::lock_price(currency_id);
 				}
 
+				let now = <system::Module<T>>::block_number();
 				<IsShutdown>::put(true);
-				Self::deposit_event(RawEvent::Shutdown(<system::Module<T>>::block_number()));
+				<ShutdownBlockNumber<T>>::put(now);
+				Self::deposit_event(RawEvent::Shutdown(now));
+				Ok(())
+			})?;
+		}
+
+		/// Freeze specific collateral, its CDPs cannot mint, withdraw or be
+		/// liquidated until it is unfrozen.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: `IsShutdown`, `FrozenCollaterals`
+		/// - Db writes: `FrozenCollaterals`
+		/// -------------------
+		/// Base Weight: 27.35 µs
+		/// # </weight>
+		#[weight = (27 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational)]
+		pub fn freeze_collateral(origin, currency_id: CurrencyId) {
+			with_transaction_result(|| {
+				T::ShutdownOrigin::ensure_origin(origin)?;
+				ensure!(!Self::is_shutdown(), Error::<T>::AlreadyShutdown);
+				ensure!(
+					T::CollateralCurrencyIds::get().contains(&currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
+				ensure!(!Self::frozen_collaterals(currency_id), Error::<T>::AlreadyFrozen);
+
+				<FrozenCollaterals>::insert(currency_id, true);
+				Self::deposit_event(RawEvent::CollateralFrozen(currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Unfreeze specific collateral.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: `FrozenCollaterals`
+		/// - Db writes: `FrozenCollaterals`
+		/// -------------------
+		/// Base Weight: 25.12 µs
+		/// # </weight>
+		#[weight = (25 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational)]
+		pub fn unfreeze_collateral(origin, currency_id: CurrencyId) {
+			with_transaction_result(|| {
+				T::ShutdownOrigin::ensure_origin(origin)?;
+				ensure!(Self::frozen_collaterals(currency_id), Error::<T>::NotFrozen);
+
+				<FrozenCollaterals>::remove(currency_id);
+				Self::deposit_event(RawEvent::CollateralUnfrozen(currency_id));
 				Ok(())
 			})?;
 		}
 
-		/// Open final redemption if settlement is completed.
+		/// Update the block delays of final settlement steps
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `cage_price_delay`: blocks to wait after shutdown before fixing cage prices,
+		///   `None` means do not update.
+		/// - `settlement_delay`: blocks to wait after shutdown before opening final redemption,
+		///   `None` means do not update.
+		/// - `cash_delay`: blocks to wait after final redemption opened before cashing out,
+		///   `None` means do not update.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 3
+		/// -------------------
+		/// Base Weight: 29.51 µs
+		/// # </weight>
+		#[weight = (29 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(0, 3), DispatchClass::Operational)]
+		pub fn set_settlement_delays(
+			origin,
+			cage_price_delay: ChangeBlockNumberOf<T>,
+			settlement_delay: ChangeBlockNumberOf<T>,
+			cash_delay: ChangeBlockNumberOf<T>,
+		) {
+			with_transaction_result(|| {
+				T::ShutdownOrigin::ensure_origin(origin)?;
+				if let Change::NewValue(delay) = cage_price_delay {
+					<CagePriceDelay<T>>::put(delay);
+					Self::deposit_event(RawEvent::CagePriceDelayUpdated(delay));
+				}
+				if let Change::NewValue(delay) = settlement_delay {
+					<SettlementDelay<T>>::put(delay);
+					Self::deposit_event(RawEvent::SettlementDelayUpdated(delay));
+				}
+				if let Change::NewValue(delay) = cash_delay {
+					<CashDelay<T>>::put(delay);
+					Self::deposit_event(RawEvent::CashDelayUpdated(delay));
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Fix the cage price of specific collateral from its locked price.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: collateral type.
+		///
+		/// # <weight>
+		/// - Preconditions:
+		/// 	- T::PriceSource is module_prices
+		/// - Complexity: `O(1)`
+		/// - Db reads: `ShutdownBlockNumber`, `CagePriceDelay`, `CagePrices`, 2 items of module_prices
+		/// - Db writes: `CagePrices`
+		/// -------------------
+		/// Base Weight: 41.26 µs
+		/// # </weight>
+		#[weight = 41 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(5, 1)]
+		pub fn fix_cage_price(origin, currency_id: CurrencyId) {
+			with_transaction_result(|| {
+				ensure_signed(origin)?;
+				ensure!(
+					T::CollateralCurrencyIds::get().contains(&currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
+				Self::ensure_delay_passed(Self::shutdown_block_number(), Self::cage_price_delay())?;
+				ensure!(
+					!<CagePrices>::contains_key(currency_id),
+					Error::<T>::CagePriceAlreadyFixed,
+				);
+
+				// the price of collateral is locked after shutdown, so the relative
+				// price to stable currency is fixed.
+				let cage_price = <T as Trait>::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+
+				<CagePrices>::insert(currency_id, cage_price);
+				Self::deposit_event(RawEvent::CagePriceFixed(currency_id, cage_price));
+				Ok(())
+			})?;
+		}
+
+		/// Set the cage price of specific collateral, it overrides the price
+		/// fixed from the locked price. Used when the locked price of the
+		/// collateral is invalid and the final redemption cannot be opened.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `cage_price`: the stable currency value of a unit of collateral.
+		#[weight = (10_000 + T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational)]
+		pub fn set_cage_price(origin, currency_id: CurrencyId, cage_price: Price) {
+			with_transaction_result(|| {
+				T::ShutdownOrigin::ensure_origin(origin)?;
+				ensure!(Self::is_shutdown(), Error::<T>::MustAfterShutdown);
+				ensure!(!Self::can_refund(), Error::<T>::AlreadyOpenedRefund);
+				ensure!(
+					T::CollateralCurrencyIds::get().contains(&currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
+
+				<CagePrices>::insert(currency_id, cage_price);
+				Self::deposit_event(RawEvent::CagePriceFixed(currency_id, cage_price));
+				Ok(())
+			})?;
+		}
+
+		/// Open final redemption if settlement is completed, fix the total
+		/// debt and the cash rate of every collateral.
 		///
 		/// The dispatch origin of this call must be `ShutdownOrigin`.
 		///
@@ -148,16 +417,16 @@ decl_module! {
 		/// 	- T::CDPTreasury is module_cdp_treasury
 		/// 	- T::AuctionManagerHandler is module_auction_manager
 		/// 	- T::OnShutdown is (module_cdp_treasury, module_cdp_engine, module_honzon, module_dex)
-		/// - Complexity: `O(1)`
-		/// - Db reads: `IsShutdown`, (2 + 2 * length of collateral_ids) items in modules related to module_emergency_shutdown
-		/// - Db writes: `CanRefund`
+		/// - Complexity: `O(N)` where `N` is the length of collateral_ids
+		/// - Db reads: `IsShutdown`, `ShutdownBlockNumber`, `SettlementDelay`, (3 + 4 * length of collateral_ids) items in modules related to module_emergency_shutdown
+		/// - Db writes: `CanRefund`, `OpenRefundBlockNumber`, `TotalDebt`, (2 * length of collateral_ids) items
 		/// -------------------
 		/// Base Weight: 71.8 µs
 		/// # </weight>
 		#[weight = (
 			72 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(
-				2 + 2 * (T::CollateralCurrencyIds::get().len() as u64),
-				1,
+				6 + 4 * (T::CollateralCurrencyIds::get().len() as u64),
+				3 + 2 * (T::CollateralCurrencyIds::get().len() as u64),
 			),
 			DispatchClass::Operational,
 		)]
@@ -165,6 +434,7 @@ decl_module! {
 			with_transaction_result(|| {
 				T::ShutdownOrigin::ensure_origin(origin)?;
 				ensure!(Self::is_shutdown(), Error::<T>::MustAfterShutdown);	// must after shutdown
+				Self::ensure_delay_passed(Self::shutdown_block_number(), Self::settlement_delay())?;
 
 				// Ensure there's no debit and surplus auction now, they may bring uncertain surplus to system.
 				// Cancel all surplus auctions and debit auctions to pass the check!
@@ -178,7 +448,7 @@ decl_module! {
 				// Settle all collaterals type CDPs which have debit, cancel all collateral auctions in forward stage and
 				// wait for all collateral auctions in reverse stage to be ended.
 				let collateral_currency_ids = T::CollateralCurrencyIds::get();
-				for currency_id in collateral_currency_ids {
+				for currency_id in collateral_currency_ids.iter().copied() {
 					// there's no collateral auction
 					ensure!(
 						<T as Trait>::AuctionManagerHandler::get_total_collateral_in_auction(currency_id).is_zero(),
@@ -189,66 +459,141 @@ decl_module! {
 						<loans::Module<T>>::total_positions(currency_id).debit.is_zero(),
 						Error::<T>::ExistUnhandledDebit,
 					);
+					// the cage price must be fixed if there's collateral to refund
+					ensure!(
+						<CagePrices>::contains_key(currency_id)
+						|| <T as Trait>::CDPTreasury::get_total_collaterals(currency_id).is_zero(),
+						Error::<T>::CagePriceNotFixed,
+					);
+				}
+
+				// fix the total debt, it will not change even if stable currency is packed into bag
+				let total_debt = <T as loans::Trait>::Currency::total_issuance(T::GetStableCurrencyId::get());
+				<TotalDebt>::put(total_debt);
+
+				// fix the cash rate and debt share of every collateral
+				for currency_id in collateral_currency_ids {
+					let total_collateral = <T as Trait>::CDPTreasury::get_total_collaterals(currency_id);
+					let cash_rate = Ratio::checked_from_rational(total_collateral, total_debt).unwrap_or_default();
+					let debt_share = Self::cage_prices(currency_id)
+						.and_then(|cage_price| Ratio::checked_from_rational(
+							cage_price.saturating_mul_int(total_collateral),
+							total_debt,
+						))
+						.unwrap_or_default();
+
+					<CashRates>::insert(currency_id, cash_rate);
+					<DebtShares>::insert(currency_id, debt_share);
+					Self::deposit_event(RawEvent::CollateralRateFixed(currency_id, cash_rate, debt_share));
 				}
 
 				// Open refund stage
+				let now = <system::Module<T>>::block_number();
 				<CanRefund>::put(true);
-				Self::deposit_event(RawEvent::OpenRefund(<system::Module<T>>::block_number()));
+				<OpenRefundBlockNumber<T>>::put(now);
+				Self::deposit_event(RawEvent::OpenRefund(now));
 				Ok(())
 			})?;
 		}
 
-		/// Refund a basket of remaining collateral assets to caller
+		/// Pack stable currency into caller's bag, the stable currency will be
+		/// burned and the amount in bag can be used to cash out every collateral.
 		///
-		/// - `amount`: stable currency amount used to refund.
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `amount`: stable currency amount to pack.
 		///
 		/// # <weight>
 		/// - Preconditions:
 		/// 	- T::CDPTreasury is module_cdp_treasury
-		/// 	- T::AuctionManagerHandler is module_auction_manager
-		/// 	- T::OnShutdown is (module_cdp_treasury, module_cdp_engine, module_honzon, module_dex)
 		/// - Complexity: `O(1)`
-		/// - Db reads: `CanRefund`, (2 + 3 * length of collateral_ids) items in modules related to module_emergency_shutdown
-		/// - Db writes: (3 * length of collateral_ids) items in modules related to module_emergency_shutdown
+		/// - Db reads: `CanRefund`, `OpenRefundBlockNumber`, `CashDelay`, `Bags`, 2 items in module_cdp_treasury
+		/// - Db writes: `Bags`, 2 items in module_cdp_treasury
 		/// -------------------
-		/// Base Weight: 455.1 µs
+		/// Base Weight: 98.7 µs
 		/// # </weight>
-		#[weight = 455 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(
-			3 + 3 * (T::CollateralCurrencyIds::get().len() as u64),
-			3 * (T::CollateralCurrencyIds::get().len() as u64)
-		)]
-		pub fn refund_collaterals(origin, #[compact] amount: Balance) {
+		#[weight = 99 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(6, 3)]
+		pub fn pack(origin, #[compact] amount: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				ensure!(Self::can_refund(), Error::<T>::CanNotRefund);
-
-				let refund_ratio: Ratio = <T as Trait>::CDPTreasury::get_debit_proportion(amount);
-				let collateral_currency_ids = T::CollateralCurrencyIds::get();
+				Self::ensure_can_cash()?;
 
 				// burn caller's stable currency by CDP treasury
 				<T as Trait>::CDPTreasury::burn_debit(&who, amount)?;
+				<Bags<T>>::mutate(&who, |bag| *bag = bag.saturating_add(amount));
 
-				let mut refund_assets: Vec<(CurrencyId, Balance)> = vec![];
-				// refund collaterals to caller by CDP treasury
-				for currency_id in collateral_currency_ids {
-					let refund_amount = refund_ratio
-						.saturating_mul_int(<T as Trait>::CDPTreasury::get_total_collaterals(currency_id));
+				Self::deposit_event(RawEvent::Pack(who, amount));
+				Ok(())
+			})?;
+		}
+
+		/// Cash out specific collateral at its fixed cash rate by the stable
+		/// currency in caller's bag. Every collateral can be cashed out by
+		/// the stable currency in bag once.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: collateral type to cash out.
+		/// - `amount`: stable currency amount in bag used to cash out.
+		///
+		/// # <weight>
+		/// - Preconditions:
+		/// 	- T::CDPTreasury is module_cdp_treasury
+		/// - Complexity: `O(1)`
+		/// - Db reads: `CanRefund`, `OpenRefundBlockNumber`, `CashDelay`, `CashRates`, `Bags`, `Cashed`, 2 items in module_cdp_treasury
+		/// - Db writes: `Cashed`, 2 items in module_cdp_treasury
+		/// -------------------
+		/// Base Weight: 112.4 µs
+		/// # </weight>
+		#[weight = 112 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(8, 3)]
+		pub fn cash(origin, currency_id: CurrencyId, #[compact] amount: Balance) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::ensure_can_cash()?;
+				let cash_rate = Self::cash_rates(currency_id).ok_or(Error::<T>::InvalidCollateralType)?;
+
+				let cashed = Self::cashed(currency_id, &who).saturating_add(amount);
+				ensure!(cashed <= Self::bags(&who), Error::<T>::BagNotEnough);
+				<Cashed<T>>::insert(currency_id, &who, cashed);
 
-					if !refund_amount.is_zero() {
-						<T as Trait>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_amount)?;
-						refund_assets.push((currency_id, refund_amount));
-					}
+				let collateral_amount = cash_rate.saturating_mul_int(amount);
+				if !collateral_amount.is_zero() {
+					<T as Trait>::CDPTreasury::withdraw_collateral(&who, currency_id, collateral_amount)?;
 				}
 
-				Self::deposit_event(RawEvent::Refund(who, amount, refund_assets));
+				Self::deposit_event(RawEvent::Cash(who, currency_id, amount, collateral_amount));
 				Ok(())
 			})?;
 		}
 	}
 }
 
+impl<T: Trait> Module<T> {
+	/// Ensure `delay` blocks have passed since `since`, fail if `since` is
+	/// not set.
+	fn ensure_delay_passed(since: Option<T::BlockNumber>, delay: T::BlockNumber) -> DispatchResult {
+		let since = since.ok_or(Error::<T>::MustAfterShutdown)?;
+		ensure!(
+			<system::Module<T>>::block_number() >= since.saturating_add(delay),
+			Error::<T>::DelayNotPassed,
+		);
+		Ok(())
+	}
+
+	fn ensure_can_cash() -> DispatchResult {
+		ensure!(Self::can_refund(), Error::<T>::CanNotRefund);
+		Self::ensure_delay_passed(Self::open_refund_block_number(), Self::cash_delay())
+	}
+}
+
 impl<T: Trait> EmergencyShutdown for Module<T> {
 	fn is_shutdown() -> bool {
 		Self::is_shutdown()
 	}
 }
+
+impl<T: Trait> CollateralCircuitBreaker<CurrencyId> for Module<T> {
+	fn is_frozen(currency_id: CurrencyId) -> bool {
+		Self::frozen_collaterals(currency_id)
+	}
+}
@@ -199,6 +199,10 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		Default::default()
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Default::default()
 	}
@@ -223,6 +227,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = EmergencyShutdownModule;
+	type PriceSource = MockPriceSource;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -234,6 +240,7 @@ impl Trait for Runtime {
 	type Event = TestEvent;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = MockPriceSource;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
 	type ShutdownOrigin = EnsureSignedBy<One, AccountId>;
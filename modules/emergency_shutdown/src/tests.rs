@@ -60,11 +60,302 @@ fn open_collateral_refund_work() {
 }
 
 #[test]
-fn refund_collaterals_fail() {
+fn set_settlement_delays_work() {
 	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::set_settlement_delays(
+				Origin::signed(5),
+				Change::NewValue(10),
+				Change::NoChange,
+				Change::NoChange,
+			),
+			BadOrigin,
+		);
+		assert_ok!(EmergencyShutdownModule::set_settlement_delays(
+			Origin::signed(1),
+			Change::NewValue(10),
+			Change::NewValue(20),
+			Change::NoChange,
+		));
+
+		let cage_price_delay_updated_event = TestEvent::emergency_shutdown(RawEvent::CagePriceDelayUpdated(10));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cage_price_delay_updated_event));
+		let settlement_delay_updated_event = TestEvent::emergency_shutdown(RawEvent::SettlementDelayUpdated(20));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == settlement_delay_updated_event));
+
+		assert_eq!(EmergencyShutdownModule::cage_price_delay(), 10);
+		assert_eq!(EmergencyShutdownModule::settlement_delay(), 20);
+		assert_eq!(EmergencyShutdownModule::cash_delay(), 0);
+	});
+}
+
+#[test]
+fn fix_cage_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+		assert_ok!(EmergencyShutdownModule::set_settlement_delays(
+			Origin::signed(1),
+			Change::NewValue(10),
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_eq!(EmergencyShutdownModule::shutdown_block_number(), Some(1));
+		assert_noop!(
+			EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), ACA),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC),
+			Error::<Runtime>::DelayNotPassed,
+		);
+
+		System::set_block_number(11);
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC));
+
+		let cage_price_fixed_event = TestEvent::emergency_shutdown(RawEvent::CagePriceFixed(BTC, Price::one()));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cage_price_fixed_event));
+
+		assert_eq!(EmergencyShutdownModule::cage_prices(BTC), Some(Price::one()));
+		assert_eq!(EmergencyShutdownModule::cage_prices(DOT), None);
+		assert_noop!(
+			EmergencyShutdownModule::fix_cage_price(Origin::signed(BOB), BTC),
+			Error::<Runtime>::CagePriceAlreadyFixed,
+		);
+	});
+}
+
+#[test]
+fn set_cage_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::set_cage_price(Origin::signed(1), BTC, Price::one()),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::set_cage_price(Origin::signed(ALICE), BTC, Price::one()),
+			BadOrigin,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::set_cage_price(Origin::signed(1), ACA, Price::one()),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC));
+		assert_ok!(EmergencyShutdownModule::set_cage_price(
+			Origin::signed(1),
+			BTC,
+			Price::saturating_from_integer(2)
+		));
+		let cage_price_fixed_event =
+			TestEvent::emergency_shutdown(RawEvent::CagePriceFixed(BTC, Price::saturating_from_integer(2)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cage_price_fixed_event));
+		assert_eq!(
+			EmergencyShutdownModule::cage_prices(BTC),
+			Some(Price::saturating_from_integer(2))
+		);
+
+		assert_ok!(EmergencyShutdownModule::set_cage_price(
+			Origin::signed(1),
+			DOT,
+			Price::one()
+		));
+		assert_ok!(EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::set_cage_price(Origin::signed(1), DOT, Price::one()),
+			Error::<Runtime>::AlreadyOpenedRefund,
+		);
+	});
+}
+
+#[test]
+fn open_collateral_refund_fix_cash_rates() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, DOT, 300));
+		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 1000, true));
+		assert_ok!(EmergencyShutdownModule::set_settlement_delays(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NewValue(5),
+			Change::NoChange,
+		));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)),
+			Error::<Runtime>::DelayNotPassed,
+		);
+
+		System::set_block_number(6);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)),
+			Error::<Runtime>::CagePriceNotFixed,
+		);
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC));
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), DOT));
+		assert_ok!(EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)));
+
+		let collateral_rate_fixed_event = TestEvent::emergency_shutdown(RawEvent::CollateralRateFixed(
+			DOT,
+			Ratio::saturating_from_rational(3, 10),
+			Ratio::saturating_from_rational(3, 10),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == collateral_rate_fixed_event));
+
+		assert_eq!(EmergencyShutdownModule::total_debt(), 1000);
+		assert_eq!(EmergencyShutdownModule::open_refund_block_number(), Some(6));
+		assert_eq!(
+			EmergencyShutdownModule::cash_rates(BTC),
+			Some(Ratio::saturating_from_rational(1, 10))
+		);
+		assert_eq!(
+			EmergencyShutdownModule::debt_shares(BTC),
+			Ratio::saturating_from_rational(1, 10)
+		);
+		assert_eq!(
+			EmergencyShutdownModule::cash_rates(DOT),
+			Some(Ratio::saturating_from_rational(3, 10))
+		);
+	});
+}
+
+#[test]
+fn pack_and_cash_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EmergencyShutdownModule::pack(Origin::signed(ALICE), 10),
+			Error::<Runtime>::CanNotRefund,
+		);
 		assert_noop!(
-			EmergencyShutdownModule::refund_collaterals(Origin::signed(ALICE), 10),
+			EmergencyShutdownModule::cash(Origin::signed(ALICE), BTC, 10),
 			Error::<Runtime>::CanNotRefund,
 		);
 	});
 }
+
+#[test]
+fn pack_and_cash_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, DOT, 300));
+		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 1000, true));
+		assert_ok!(EmergencyShutdownModule::set_settlement_delays(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10),
+		));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), BTC));
+		assert_ok!(EmergencyShutdownModule::fix_cage_price(Origin::signed(ALICE), DOT));
+		assert_ok!(EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::pack(Origin::signed(ALICE), 500),
+			Error::<Runtime>::DelayNotPassed,
+		);
+
+		System::set_block_number(11);
+		assert_ok!(EmergencyShutdownModule::pack(Origin::signed(ALICE), 500));
+		let pack_event = TestEvent::emergency_shutdown(RawEvent::Pack(ALICE, 500));
+		assert!(System::events().iter().any(|record| record.event == pack_event));
+		assert_eq!(EmergencyShutdownModule::bags(ALICE), 500);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 500);
+		assert_eq!(EmergencyShutdownModule::total_debt(), 1000);
+
+		assert_noop!(
+			EmergencyShutdownModule::cash(Origin::signed(ALICE), ACA, 100),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_ok!(EmergencyShutdownModule::cash(Origin::signed(ALICE), BTC, 500));
+		let cash_event = TestEvent::emergency_shutdown(RawEvent::Cash(ALICE, BTC, 500, 50));
+		assert!(System::events().iter().any(|record| record.event == cash_event));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1050);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+		assert_eq!(EmergencyShutdownModule::cashed(BTC, ALICE), 500);
+		assert_noop!(
+			EmergencyShutdownModule::cash(Origin::signed(ALICE), BTC, 1),
+			Error::<Runtime>::BagNotEnough,
+		);
+
+		assert_ok!(EmergencyShutdownModule::cash(Origin::signed(ALICE), DOT, 200));
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 1060);
+		assert_ok!(EmergencyShutdownModule::cash(Origin::signed(ALICE), DOT, 300));
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 1150);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 150);
+		assert_noop!(
+			EmergencyShutdownModule::cash(Origin::signed(ALICE), DOT, 1),
+			Error::<Runtime>::BagNotEnough,
+		);
+	});
+}
+
+#[test]
+fn freeze_and_unfreeze_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::freeze_collateral(Origin::signed(5), BTC),
+			BadOrigin,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::freeze_collateral(Origin::signed(1), ACA),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::unfreeze_collateral(Origin::signed(1), BTC),
+			Error::<Runtime>::NotFrozen,
+		);
+		assert_ok!(EmergencyShutdownModule::freeze_collateral(Origin::signed(1), BTC));
+
+		let collateral_frozen_event = TestEvent::emergency_shutdown(RawEvent::CollateralFrozen(BTC));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == collateral_frozen_event));
+
+		assert_eq!(
+			<EmergencyShutdownModule as CollateralCircuitBreaker<_>>::is_frozen(BTC),
+			true
+		);
+		assert_eq!(
+			<EmergencyShutdownModule as CollateralCircuitBreaker<_>>::is_frozen(DOT),
+			false
+		);
+		assert_eq!(<EmergencyShutdownModule as EmergencyShutdown>::is_shutdown(), false);
+		assert_noop!(
+			EmergencyShutdownModule::freeze_collateral(Origin::signed(1), BTC),
+			Error::<Runtime>::AlreadyFrozen,
+		);
+
+		assert_ok!(EmergencyShutdownModule::unfreeze_collateral(Origin::signed(1), BTC));
+		let collateral_unfrozen_event = TestEvent::emergency_shutdown(RawEvent::CollateralUnfrozen(BTC));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == collateral_unfrozen_event));
+		assert_eq!(EmergencyShutdownModule::frozen_collaterals(BTC), false);
+
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::freeze_collateral(Origin::signed(1), BTC),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
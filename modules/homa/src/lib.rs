@@ -6,7 +6,7 @@ use frame_system::{self as system, ensure_signed};
 use orml_utilities::with_transaction_result;
 use primitives::{Balance, EraIndex};
 use sp_runtime::RuntimeDebug;
-use support::HomaProtocol;
+use support::{HomaProtocol, Rate, RedeemRequestId};
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
 pub enum RedeemStrategy {
@@ -21,7 +21,7 @@ pub trait Trait: system::Trait {
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		#[weight = 10_000]
+		#[weight = 10_000 + T::Homa::mint_weight()]
 		pub fn mint(origin, #[compact] amount: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
@@ -57,6 +57,24 @@ decl_module! {
 				Ok(())
 			})?;
 		}
+
+		#[weight = 10_000 + T::Homa::request_redeem_weight()]
+		pub fn request_redeem(origin, #[compact] amount: Balance, max_fee_rate: Rate) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				T::Homa::request_redeem(&who, amount, max_fee_rate)?;
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000 + T::Homa::cancel_redeem_request_weight()]
+		pub fn cancel_redeem_request(origin, request_id: RedeemRequestId) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				T::Homa::cancel_redeem_request(&who, request_id)?;
+				Ok(())
+			})?;
+		}
 	}
 }
 
@@ -19,7 +19,7 @@ use frame_system::{self as system, ensure_signed};
 use orml_utilities::with_transaction_result;
 use primitives::{Amount, CurrencyId};
 use sp_runtime::{traits::Zero, DispatchResult};
-use support::EmergencyShutdown;
+use support::{CollateralCircuitBreaker, EmergencyShutdown};
 
 mod mock;
 mod tests;
@@ -57,6 +57,8 @@ decl_error! {
 		NoAuthorization,
 		// The system has been shutdown
 		AlreadyShutdown,
+		// The collateral has been frozen
+		CollateralFrozen,
 	}
 }
 
@@ -94,6 +96,10 @@ decl_module! {
 				if !debit_adjustment.is_zero() {
 					ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
 				}
+				// not allowed to mint or withdraw collateral when the collateral is frozen
+				if debit_adjustment.is_positive() || collateral_adjustment.is_negative() {
+					ensure!(!T::EmergencyShutdown::is_frozen(currency_id), Error::<T>::CollateralFrozen);
+				}
 				<cdp_engine::Module<T>>::adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment)?;
 				Ok(())
 			})?;
@@ -13,7 +13,7 @@ use sp_runtime::{
 	FixedPointNumber, ModuleId, Perbill,
 };
 use sp_std::cell::RefCell;
-use support::{AuctionManager, ExchangeRate, Price, PriceProvider, Rate, Ratio};
+use support::{AuctionManager, CollateralCircuitBreaker, ExchangeRate, Price, PriceProvider, Rate, Ratio};
 
 mod honzon {
 	pub use super::super::*;
@@ -201,6 +201,10 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_collateral_auctions_count(_id: Self::CurrencyId) -> u32 {
+		Default::default()
+	}
+
 	fn get_total_surplus_in_auction() -> Self::Balance {
 		Default::default()
 	}
@@ -208,12 +212,17 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static FROZEN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_freeze(currency_id: CurrencyId) {
+	FROZEN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
@@ -221,6 +230,12 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+impl CollateralCircuitBreaker<CurrencyId> for MockEmergencyShutdown {
+	fn is_frozen(currency_id: CurrencyId) -> bool {
+		FROZEN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -240,6 +255,8 @@ impl cdp_treasury::Trait for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = MockEmergencyShutdown;
+	type PriceSource = MockPriceSource;
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
@@ -116,6 +116,36 @@ fn adjust_loan_should_work() {
 	});
 }
 
+#[test]
+fn adjust_loan_on_frozen_collateral() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		mock_freeze(BTC);
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 0, 10),
+			Error::<Runtime>::CollateralFrozen,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, -10, 0),
+			Error::<Runtime>::CollateralFrozen,
+		);
+
+		// deposit collateral and repay debit are still allowed
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 10, -10));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 110);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+	});
+}
+
 #[test]
 fn on_emergency_shutdown_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -12,7 +12,7 @@ use orml_utilities::with_transaction_result;
 use primitives::{Amount, Balance, CurrencyId, Share};
 use sp_runtime::{traits::Zero, FixedPointNumber, RuntimeDebug};
 use sp_std::prelude::*;
-use support::{CDPTreasury, DEXManager, EmergencyShutdown, Rate};
+use support::{CDPTreasury, CollateralCircuitBreaker, DEXManager, EmergencyShutdown, Rate};
 
 mod mock;
 mod tests;
@@ -29,6 +29,9 @@ pub enum PoolId {
 	DexSaving(CurrencyId),
 	/// Rewards(ACA) pool for users who staking by Homa protocol
 	Homa,
+	/// Rewards(LDOT) pool for voters of nominees election, accumulated from
+	/// Homa commission
+	NomineesElection,
 }
 
 pub trait Trait: frame_system::Trait + orml_rewards::Trait<Share = Share, Balance = Balance, PoolId = PoolId> {
@@ -42,6 +45,9 @@ pub trait Trait: frame_system::Trait + orml_rewards::Trait<Share = Share, Balanc
 	/// The vault account to keep rewards for type HomaIncentive PoolId
 	type HomaIncentivePool: Get<Self::AccountId>;
 
+	/// The vault account to keep rewards for type NomineesElection PoolId
+	type NomineesElectionPool: Get<Self::AccountId>;
+
 	/// The period to accumulate rewards
 	type AccumulatePeriod: Get<Self::BlockNumber>;
 
@@ -51,6 +57,9 @@ pub trait Trait: frame_system::Trait + orml_rewards::Trait<Share = Share, Balanc
 	/// The saving reward type (should be AUSD)
 	type SavingCurrencyId: Get<CurrencyId>;
 
+	/// The nominees election reward type (should be LDOT)
+	type LiquidCurrencyId: Get<CurrencyId>;
+
 	/// The origin which may update incentive related params
 	type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
@@ -63,8 +72,9 @@ pub trait Trait: frame_system::Trait + orml_rewards::Trait<Share = Share, Balanc
 	/// DEX to supply liquidity info
 	type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
-	/// Emergency shutdown.
-	type EmergencyShutdown: EmergencyShutdown;
+	/// Emergency shutdown, and the circuit breaker to freeze specific
+	/// collateral.
+	type EmergencyShutdown: EmergencyShutdown + CollateralCircuitBreaker<CurrencyId>;
 }
 
 decl_storage! {
@@ -94,6 +104,9 @@ decl_module! {
 		/// The vault account to keep rewards for type HomaIncentive PoolId
 		const HomaIncentivePool: T::AccountId = T::HomaIncentivePool::get();
 
+		/// The vault account to keep rewards for type NomineesElection PoolId
+		const NomineesElectionPool: T::AccountId = T::NomineesElectionPool::get();
+
 		/// The period to accumulate rewards
 		const AccumulatePeriod: T::BlockNumber = T::AccumulatePeriod::get();
 
@@ -103,6 +116,9 @@ decl_module! {
 		/// The saving reward type (should be AUSD)
 		const SavingCurrencyId: CurrencyId = T::SavingCurrencyId::get();
 
+		/// The nominees election reward type (should be LDOT)
+		const LiquidCurrencyId: CurrencyId = T::LiquidCurrencyId::get();
+
 		#[weight = 10_000]
 		pub fn claim_rewards(origin, pool_id: T::PoolId) {
 			with_transaction_result(|| {
@@ -226,6 +242,10 @@ impl<T: Trait> RewardHandler<T::AccountId, T::BlockNumber> for Module<T> {
 				if !pool_info.total_shares.is_zero() {
 					match pool_id {
 						PoolId::Loans(currency_id) => {
+							// CDPs of frozen collateral do not accumulate incentive rewards
+							if T::EmergencyShutdown::is_frozen(currency_id) {
+								continue;
+							}
 							let incentive_reward = Self::loans_incentive_rewards(currency_id);
 
 							// TODO: transfer from RESERVED TREASURY instead of issuing
@@ -286,6 +306,8 @@ impl<T: Trait> RewardHandler<T::AccountId, T::BlockNumber> for Module<T> {
 								accumulated_incentive = accumulated_incentive.saturating_add(incentive_reward);
 							}
 						}
+						// rewards are accumulated from Homa commission by nominees election
+						PoolId::NomineesElection => {}
 					}
 				}
 			}
@@ -307,6 +329,7 @@ impl<T: Trait> RewardHandler<T::AccountId, T::BlockNumber> for Module<T> {
 			PoolId::DexIncentive(_) => (T::DexIncentivePool::get(), T::IncentiveCurrencyId::get()),
 			PoolId::DexSaving(_) => (T::DexIncentivePool::get(), T::SavingCurrencyId::get()),
 			PoolId::Homa => (T::HomaIncentivePool::get(), T::IncentiveCurrencyId::get()),
+			PoolId::NomineesElection => (T::NomineesElectionPool::get(), T::LiquidCurrencyId::get()),
 		};
 
 		// payout the reward to user from the pool. it should not affect the
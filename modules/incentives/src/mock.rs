@@ -22,6 +22,7 @@ pub const ACA: CurrencyId = CurrencyId::ACA;
 pub const AUSD: CurrencyId = CurrencyId::AUSD;
 pub const BTC: CurrencyId = CurrencyId::XBTC;
 pub const DOT: CurrencyId = CurrencyId::DOT;
+pub const LDOT: CurrencyId = CurrencyId::LDOT;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
@@ -160,16 +161,29 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 			_ => (0, 0),
 		}
 	}
+
+	fn get_shares(_: CurrencyId, _: &AccountId) -> Balance {
+		unimplemented!()
+	}
+
+	fn withdraw_liquidity(_: AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
 }
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static FROZEN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_freeze(currency_id: CurrencyId) {
+	FROZEN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
@@ -177,6 +191,12 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+impl CollateralCircuitBreaker<CurrencyId> for MockEmergencyShutdown {
+	fn is_frozen(currency_id: CurrencyId) -> bool {
+		FROZEN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
+}
+
 impl orml_rewards::Trait for Runtime {
 	type Share = Share;
 	type Balance = Balance;
@@ -190,9 +210,11 @@ parameter_types! {
 	pub const LoansIncentivePool: AccountId = 10;
 	pub const DexIncentivePool: AccountId = 11;
 	pub const HomaIncentivePool: AccountId = 12;
+	pub const NomineesElectionPool: AccountId = 13;
 	pub const AccumulatePeriod: BlockNumber = 10;
 	pub const IncentiveCurrencyId: CurrencyId = ACA;
 	pub const SavingCurrencyId: CurrencyId = AUSD;
+	pub const LiquidCurrencyId: CurrencyId = LDOT;
 }
 
 ord_parameter_types! {
@@ -203,9 +225,11 @@ impl Trait for Runtime {
 	type LoansIncentivePool = LoansIncentivePool;
 	type DexIncentivePool = DexIncentivePool;
 	type HomaIncentivePool = HomaIncentivePool;
+	type NomineesElectionPool = NomineesElectionPool;
 	type AccumulatePeriod = AccumulatePeriod;
 	type IncentiveCurrencyId = IncentiveCurrencyId;
 	type SavingCurrencyId = SavingCurrencyId;
+	type LiquidCurrencyId = LiquidCurrencyId;
 	type UpdateOrigin = EnsureSignedBy<Four, AccountId>;
 	type CDPTreasury = MockCDPTreasury;
 	type Currency = TokensModule;
@@ -380,6 +380,11 @@ fn pay_out_works_works() {
 		IncentivesModule::payout(&BOB, PoolId::Homa, 3000);
 		assert_eq!(TokensModule::free_balance(ACA, &HomaIncentivePool::get()), 7000);
 		assert_eq!(TokensModule::free_balance(ACA, &BOB), 4000);
+
+		assert_ok!(TokensModule::deposit(LDOT, &NomineesElectionPool::get(), 10000));
+		IncentivesModule::payout(&ALICE, PoolId::NomineesElection, 2000);
+		assert_eq!(TokensModule::free_balance(LDOT, &NomineesElectionPool::get()), 8000);
+		assert_eq!(TokensModule::free_balance(LDOT, &ALICE), 2000);
 	});
 }
 
@@ -437,3 +442,19 @@ fn accumulate_reward_works() {
 		assert_eq!(IncentivesModule::accumulate_reward(60, |_, _| {}), vec![]);
 	});
 }
+
+#[test]
+fn accumulate_reward_skip_frozen_collateral() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 1000), (DOT, 2000),],
+		));
+		RewardsModule::add_share(&ALICE, PoolId::Loans(BTC), 1);
+		RewardsModule::add_share(&ALICE, PoolId::Loans(DOT), 1);
+		assert_eq!(IncentivesModule::accumulate_reward(10, |_, _| {}), vec![(ACA, 3000)]);
+
+		mock_freeze(BTC);
+		assert_eq!(IncentivesModule::accumulate_reward(20, |_, _| {}), vec![(ACA, 2000)]);
+	});
+}
@@ -2,20 +2,21 @@
 
 use codec::{Decode, Encode};
 use frame_support::{
-	decl_error, decl_module, decl_storage, ensure,
-	traits::{Get, LockIdentifier},
-	IterableStorageMap, Parameter,
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get, LockIdentifier},
+	weights::Weight,
+	IterableStorageDoubleMap, IterableStorageMap, Parameter,
 };
 use frame_system::{self as system, ensure_signed};
 use orml_traits::{BasicCurrency, BasicLockableCurrency};
 use orml_utilities::with_transaction_result;
-use primitives::{Balance, EraIndex};
+use primitives::{Balance, CurrencyId, EraIndex, Share};
 use sp_runtime::{
-	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
-	RuntimeDebug,
+	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, One, Saturating, Zero},
+	FixedPointNumber, PerThing, Perbill, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, prelude::*};
-use support::{NomineesProvider, OnNewEra};
+use support::{NomineesProvider, OnCommission, OnNewEra, OnValidatorPerformance, Ratio, ValidatorPerformance};
 
 mod mock;
 mod tests;
@@ -46,6 +47,59 @@ pub struct BondingLedger {
 	pub unlocking: Vec<UnlockChunk>,
 }
 
+/// The conviction of the bonded funds. A higher conviction gives more votes
+/// for the bonded balance, and locks the unbonded funds for more bonding
+/// durations.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Encode, Decode, RuntimeDebug)]
+pub enum Conviction {
+	/// 1x votes, unlocked after one bonding duration.
+	None,
+	/// 2x votes, unlocked after 2x bonding duration.
+	Locked2x,
+	/// 3x votes, unlocked after 4x bonding duration.
+	Locked3x,
+	/// 4x votes, unlocked after 8x bonding duration.
+	Locked4x,
+	/// 5x votes, unlocked after 16x bonding duration.
+	Locked5x,
+	/// 6x votes, unlocked after 32x bonding duration.
+	Locked6x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// The multiplier of bonding duration for which the unbonded funds are
+	/// locked.
+	pub fn lock_periods(self) -> EraIndex {
+		match self {
+			Conviction::None => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+
+	/// The votes of `value` bonded with this conviction.
+	pub fn votes(self, value: Balance) -> Balance {
+		let multiplier: Balance = match self {
+			Conviction::None => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 5,
+			Conviction::Locked6x => 6,
+		};
+		value.saturating_mul(multiplier)
+	}
+}
+
 impl BondingLedger {
 	/// Remove entries from `unlocking` that are sufficiently old and reduce the
 	/// total by the sum of their balances.
@@ -97,15 +151,73 @@ impl BondingLedger {
 	}
 }
 
-pub trait Trait: system::Trait {
+pub trait Trait: system::Trait + orml_rewards::Trait<Share = Share, Balance = Balance> {
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 	type Currency: BasicLockableCurrency<Self::AccountId, Moment = Self::BlockNumber, Balance = Balance>;
 	type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
 	type MinBondThreshold: Get<Balance>;
 	type BondingDuration: Get<EraIndex>;
 	type NominateesCount: Get<usize>;
 	type MaxUnlockingChunks: Get<usize>;
+
+	/// The max votes of a validator counted in election.
+	type MaxVotesPerValidator: Get<Balance>;
+
+	/// The number of eras a slashed validator is excluded from election.
+	type SlashExclusionDuration: Get<EraIndex>;
+
+	/// The origin which may update the blacklist of validators.
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The max length of a delegation chain.
+	type MaxDelegationDepth: Get<u32>;
+
+	/// The currency id of `Currency`, the liquid currency in which Homa
+	/// commission arrives.
+	type LiquidCurrencyId: Get<CurrencyId>;
+
+	/// The rewards pool of voters.
+	type RewardPoolId: Get<Self::PoolId>;
+
+	/// The vault account to keep rewards of voters.
+	type RewardPool: Get<Self::AccountId>;
+
+	/// The ratio of Homa commission rewarded to voters.
+	type RewardRatio: Get<Ratio>;
+
+	/// The max number of voters whose reward shares are refreshed per block
+	/// after new era.
+	type MaxRewardShareUpdatesPerBlock: Get<u32>;
+
+	/// The handler of the rest Homa commission.
+	type OnCommission: OnCommission<Balance, CurrencyId>;
 }
 
+decl_event!(
+	pub enum Event<T>
+	where
+		<T as system::Trait>::AccountId,
+		PolkadotAccountId = <T as Trait>::PolkadotAccountId,
+	{
+		/// \[era, nominees\]
+		NomineesElected(EraIndex, Vec<PolkadotAccountId>),
+		/// \[validator\]
+		ValidatorBlacklisted(PolkadotAccountId),
+		/// \[validator\]
+		ValidatorUnblacklisted(PolkadotAccountId),
+		/// \[validator, era\]
+		SlashedValidatorRemoved(PolkadotAccountId, EraIndex),
+		/// \[who, conviction\]
+		ConvictionUpdated(AccountId, Conviction),
+		/// \[delegator, target\]
+		Delegated(AccountId, AccountId),
+		/// \[delegator\]
+		Undelegated(AccountId),
+		/// \[amount\]
+		VoterRewardsAccumulated(Balance),
+	}
+);
+
 decl_error! {
 	/// Error for nominees election module.
 	pub enum Error for Module<T: Trait> {
@@ -114,6 +226,20 @@ decl_error! {
 		TooManyChunks,
 		NoBonded,
 		NoUnlockChunk,
+		/// The target is in the blacklist
+		BlacklistedTarget,
+		/// The conviction cannot be decreased while funds are bonded or unlocking
+		ConvictionLocked,
+		/// The account cannot delegate to itself
+		SelfDelegation,
+		/// The delegation chain would exceed the max depth
+		DelegationTooDeep,
+		/// The account has delegators and cannot delegate
+		HasDelegators,
+		/// The account has delegated its votes
+		AlreadyDelegated,
+		/// The account has not delegated its votes
+		NotDelegated,
 	}
 }
 
@@ -124,16 +250,99 @@ decl_storage! {
 		pub Votes get(fn votes): map hasher(twox_64_concat) T::PolkadotAccountId => Balance;
 		pub Nominees get(fn nominees): Vec<T::PolkadotAccountId>;
 		pub CurrentEra get(fn current_era): EraIndex;
+
+		/// The latest performance of validators reported from relay chain.
+		pub Performances get(fn performances): map hasher(twox_64_concat) T::PolkadotAccountId => Option<ValidatorPerformance>;
+
+		/// The slash count and the last slashed era of validators.
+		pub Slashes get(fn slashes): map hasher(twox_64_concat) T::PolkadotAccountId => (u32, EraIndex);
+
+		/// The validators excluded from election by governance.
+		pub Blacklist get(fn blacklist): map hasher(twox_64_concat) T::PolkadotAccountId => bool;
+
+		/// The conviction of bonded funds of accounts.
+		pub Convictions get(fn convictions): map hasher(twox_64_concat) T::AccountId => Conviction;
+
+		/// The account to which the votes are delegated.
+		pub Delegations get(fn delegations): map hasher(twox_64_concat) T::AccountId => Option<T::AccountId>;
+
+		/// The votes delegated to the account, directly or through a delegation
+		/// chain.
+		pub DelegatedVotes get(fn delegated_votes): map hasher(twox_64_concat) T::AccountId => Balance;
+
+		/// The number of accounts delegating directly to the account.
+		pub DelegatorCount get(fn delegator_count): map hasher(twox_64_concat) T::AccountId => u32;
+
+		/// The average era points of the reported validators.
+		pub AverageEraPoints get(fn average_era_points): u32;
+
+		/// The average performance ratio of the reported validators, which
+		/// the validators without reported performance are rated.
+		pub AveragePerformanceRatio get(fn average_performance_ratio): Option<Ratio>;
+
+		/// The voters whose reward shares are refreshed with the validator
+		/// performance at the era, they are refreshed in `on_initialize`.
+		pub RewardShareUpdates get(fn reward_share_updates): double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId => ();
+
+		/// The earliest era whose reward share updates are not finished.
+		pub NextRewardShareUpdateEra get(fn next_reward_share_update_era): EraIndex;
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
+		fn deposit_event() = default;
 
 		const MinBondThreshold: Balance = T::MinBondThreshold::get();
 		const NominateesCount: u32 = T::NominateesCount::get() as u32;
 		const MaxUnlockingChunks: u32 = T::MaxUnlockingChunks::get() as u32;
+		const MaxVotesPerValidator: Balance = T::MaxVotesPerValidator::get();
+		const SlashExclusionDuration: EraIndex = T::SlashExclusionDuration::get();
+		const MaxDelegationDepth: u32 = T::MaxDelegationDepth::get();
+		const LiquidCurrencyId: CurrencyId = T::LiquidCurrencyId::get();
+		const RewardPool: T::AccountId = T::RewardPool::get();
+		const RewardRatio: Ratio = T::RewardRatio::get();
+		const MaxRewardShareUpdatesPerBlock: u32 = T::MaxRewardShareUpdatesPerBlock::get();
+
+		/// Refresh the reward shares of at most `MaxRewardShareUpdatesPerBlock`
+		/// voters scheduled up to current era.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let current_era = Self::current_era();
+			let max_updates = T::MaxRewardShareUpdatesPerBlock::get();
+			let mut era = Self::next_reward_share_update_era();
+			let mut updates: u32 = 0;
+
+			while era <= current_era && updates < max_updates {
+				let voters = <RewardShareUpdates<T>>::iter_prefix(era)
+					.take(max_updates.saturating_sub(updates) as usize)
+					.map(|(who, _)| who)
+					.collect::<Vec<_>>();
+				// an era without voters is counted as one update to bound the eras visited
+				updates = updates.saturating_add((voters.len() as u32).max(1));
+
+				for who in voters {
+					<RewardShareUpdates<T>>::remove(era, &who);
+					if <Ledger<T>>::contains_key(&who) {
+						Self::update_reward_share(&who);
+					}
+				}
+				if <RewardShareUpdates<T>>::iter_prefix(era).next().is_some() {
+					break;
+				}
+				era = era.saturating_add(One::one());
+			}
+			NextRewardShareUpdateEra::put(era);
+
+			// the ledger, nominations, performances and delegation chain are read to
+			// calculate each share
+			let reads_per_update = 6 + T::NominateesCount::get() as Weight + T::MaxDelegationDepth::get() as Weight;
+			T::DbWeight::get().reads_writes(2, 1).saturating_add(
+				T::DbWeight::get()
+					.reads_writes(reads_per_update, 5)
+					.saturating_mul(updates as Weight),
+			)
+		}
 
 		#[weight = 10_000]
 		pub fn bond(origin, #[compact] amount: Balance) {
@@ -144,14 +353,14 @@ decl_module! {
 				let free_balance = T::Currency::free_balance(&who);
 				if let Some(extra) = free_balance.checked_sub(ledger.total) {
 					let extra = extra.min(amount);
-					let old_active = ledger.active;
+					let old_power = Self::voting_power(&who);
 					ledger.active += extra;
 					ensure!(ledger.active >= T::MinBondThreshold::get(), Error::<T>::BelowMinBondThreshold);
 					ledger.total += extra;
-					let old_nominations = Self::nominations(&who);
 
-					Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
 					Self::update_ledger(&who, &ledger);
+					Self::update_power(&who, old_power, Self::voting_power(&who));
+					Self::update_reward_share(&who);
 				}
 				Ok(())
 			})?;
@@ -171,7 +380,7 @@ decl_module! {
 				let amount = amount.min(ledger.active);
 
 				if !amount.is_zero() {
-					let old_active = ledger.active;
+					let old_power = Self::voting_power(&who);
 					ledger.active -= amount;
 
 					ensure!(
@@ -180,15 +389,17 @@ decl_module! {
 					);
 
 					// Note: in case there is no current era it is fine to bond one era more.
-					let era = Self::current_era() + T::BondingDuration::get();
+					let lock_periods = Self::convictions(&who).lock_periods();
+					let lock_duration = T::BondingDuration::get().saturating_mul(lock_periods);
+					let era = Self::current_era().saturating_add(lock_duration);
 					ledger.unlocking.push(UnlockChunk{
 						value: amount,
 						era,
 					});
-					let old_nominations = Self::nominations(&who);
 
-					Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
 					Self::update_ledger(&who, &ledger);
+					Self::update_power(&who, old_power, Self::voting_power(&who));
+					Self::update_reward_share(&who);
 				}
 				Ok(())
 			})?;
@@ -203,12 +414,12 @@ decl_module! {
 					!ledger.unlocking.is_empty(),
 					Error::<T>::NoUnlockChunk,
 				);
-				let old_active = ledger.active;
-				let old_nominations = Self::nominations(&who);
+				let old_power = Self::voting_power(&who);
 				let ledger = ledger.rebond(amount);
 
-				Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
 				Self::update_ledger(&who, &ledger);
+				Self::update_power(&who, old_power, Self::voting_power(&who));
+				Self::update_reward_share(&who);
 				Ok(())
 			})?;
 		}
@@ -221,6 +432,7 @@ decl_module! {
 
 				if ledger.unlocking.is_empty() && ledger.active.is_zero() {
 					Self::remove_ledger(&who);
+					Self::update_reward_share(&who);
 				} else {
 					// This was the consequence of a partial unbond. just update the ledger and move on.
 					Self::update_ledger(&who, &ledger);
@@ -239,18 +451,28 @@ decl_module! {
 					Error::<T>::InvalidTargetsLength,
 				);
 
+				ensure!(
+					!targets.iter().any(Self::blacklist),
+					Error::<T>::BlacklistedTarget,
+				);
+				ensure!(Self::delegations(&who).is_none(), Error::<T>::AlreadyDelegated);
+
 				let ledger = Self::ledger(&who);
-				ensure!(!ledger.total.is_zero(), Error::<T>::NoBonded);
+				ensure!(
+					!ledger.total.is_zero() || !Self::delegator_count(&who).is_zero(),
+					Error::<T>::NoBonded,
+				);
 
 				let mut targets = targets;
 				targets.sort();
 				targets.dedup();
 
 				let old_nominations = Self::nominations(&who);
-				let old_active = Self::ledger(&who).active;
+				let power = Self::voting_power(&who);
 
-				Self::update_votes(old_active, &old_nominations, old_active, &targets);
+				Self::update_votes(power, &old_nominations, power, &targets);
 				<Nominations<T>>::insert(&who, &targets);
+				Self::update_reward_share(&who);
 				Ok(())
 			})?;
 		}
@@ -260,11 +482,100 @@ decl_module! {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
 
-				let old_nominations = Self::nominations(&who);
-				let old_active = Self::ledger(&who).active;
+				Self::remove_nominations(&who);
+				Self::update_reward_share(&who);
+				Ok(())
+			})?;
+		}
 
-				Self::update_votes(old_active, &old_nominations, Zero::zero(), &[]);
-				<Nominations<T>>::remove(&who);
+		#[weight = 10_000]
+		pub fn set_conviction(origin, conviction: Conviction) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				// the unlocking funds are rebondable, so they keep the conviction as well
+				ensure!(
+					conviction >= Self::convictions(&who) || Self::ledger(&who).total.is_zero(),
+					Error::<T>::ConvictionLocked,
+				);
+
+				let old_power = Self::voting_power(&who);
+				<Convictions<T>>::insert(&who, conviction);
+				Self::update_power(&who, old_power, Self::voting_power(&who));
+				Self::deposit_event(RawEvent::ConvictionUpdated(who, conviction));
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000]
+		pub fn delegate(origin, target: T::AccountId) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(who != target, Error::<T>::SelfDelegation);
+				// the chains of accounts having delegators are fixed, so the depth checked here
+				// won't grow later.
+				ensure!(Self::delegator_count(&who).is_zero(), Error::<T>::HasDelegators);
+
+				let max_depth = T::MaxDelegationDepth::get();
+				let mut depth: u32 = 1;
+				let mut account = target.clone();
+				ensure!(depth <= max_depth, Error::<T>::DelegationTooDeep);
+				while let Some(next) = Self::delegations(&account) {
+					depth += 1;
+					ensure!(depth <= max_depth, Error::<T>::DelegationTooDeep);
+					account = next;
+				}
+
+				Self::remove_delegation(&who);
+				Self::remove_nominations(&who);
+
+				<Delegations<T>>::insert(&who, &target);
+				<DelegatorCount<T>>::mutate(&target, |count| *count = count.saturating_add(1));
+				Self::update_power(&who, Zero::zero(), Self::voting_power(&who));
+				Self::update_reward_share(&who);
+				Self::deposit_event(RawEvent::Delegated(who, target));
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000]
+		pub fn undelegate(origin) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::delegations(&who).is_some(), Error::<T>::NotDelegated);
+
+				Self::remove_delegation(&who);
+				Self::update_reward_share(&who);
+				Self::deposit_event(RawEvent::Undelegated(who));
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000]
+		pub fn claim_rewards(origin) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				<orml_rewards::Module<T>>::claim_rewards(&who, T::RewardPoolId::get());
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000]
+		pub fn add_blacklist(origin, validator: T::PolkadotAccountId) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				<Blacklist<T>>::insert(&validator, true);
+				<Nominees<T>>::mutate(|nominees| nominees.retain(|nominee| *nominee != validator));
+				Self::deposit_event(RawEvent::ValidatorBlacklisted(validator));
+				Ok(())
+			})?;
+		}
+
+		#[weight = 10_000]
+		pub fn remove_blacklist(origin, validator: T::PolkadotAccountId) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				<Blacklist<T>>::remove(&validator);
+				Self::deposit_event(RawEvent::ValidatorUnblacklisted(validator));
 				Ok(())
 			})?;
 		}
@@ -280,9 +591,57 @@ impl<T: Trait> Module<T> {
 	fn remove_ledger(who: &T::AccountId) {
 		T::Currency::remove_lock(NOMINEES_ELECTION_ID, who);
 		<Ledger<T>>::remove(who);
+		<Convictions<T>>::remove(who);
+		// the nominations still carry the delegated votes
+		if Self::delegator_count(who).is_zero() {
+			<Nominations<T>>::remove(who);
+		}
+	}
+
+	/// The votes of the account, that's the conviction weighted active
+	/// balance plus the votes delegated to it.
+	pub fn voting_power(who: &T::AccountId) -> Balance {
+		Self::convictions(who)
+			.votes(Self::ledger(who).active)
+			.saturating_add(Self::delegated_votes(who))
+	}
+
+	/// Apply the change of voting power of the account to the end of its
+	/// delegation chain, and update the votes of the nominations there.
+	fn update_power(who: &T::AccountId, old_power: Balance, new_power: Balance) {
+		let mut account = who.clone();
+		let mut old_power = old_power;
+		let mut new_power = new_power;
+
+		// the length of delegation chain is limited by `MaxDelegationDepth`.
+		while let Some(target) = Self::delegations(&account) {
+			let target_old_power = Self::voting_power(&target);
+			<DelegatedVotes<T>>::mutate(&target, |votes| {
+				*votes = votes.saturating_sub(old_power).saturating_add(new_power)
+			});
+			old_power = target_old_power;
+			new_power = Self::voting_power(&target);
+			account = target;
+		}
+
+		let nominations = Self::nominations(&account);
+		Self::update_votes(old_power, &nominations, new_power, &nominations);
+	}
+
+	fn remove_nominations(who: &T::AccountId) {
+		let old_nominations = Self::nominations(who);
+		Self::update_votes(Self::voting_power(who), &old_nominations, Zero::zero(), &[]);
 		<Nominations<T>>::remove(who);
 	}
 
+	fn remove_delegation(who: &T::AccountId) {
+		if let Some(target) = Self::delegations(who) {
+			Self::update_power(who, Self::voting_power(who), Zero::zero());
+			<Delegations<T>>::remove(who);
+			<DelegatorCount<T>>::mutate(&target, |count| *count = count.saturating_sub(1));
+		}
+	}
+
 	fn update_votes(
 		old_active: Balance,
 		old_nominations: &[T::PolkadotAccountId],
@@ -302,31 +661,201 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
-	fn rebalance() {
-		let mut voters = <Votes<T>>::iter().collect::<Vec<(T::PolkadotAccountId, Balance)>>();
+	/// Whether the validator can be elected at the era, that's not in the
+	/// blacklist and not slashed recently.
+	pub fn is_eligible(validator: &T::PolkadotAccountId, era: EraIndex) -> bool {
+		if Self::blacklist(validator) {
+			return false;
+		}
 
-		voters.sort_by(|a, b| b.1.cmp(&a.1));
+		let (slash_count, last_slashed_era) = Self::slashes(validator);
+		slash_count.is_zero() || last_slashed_era.saturating_add(T::SlashExclusionDuration::get()) <= era
+	}
 
-		let new_nominees = voters
+	/// The performance of the validator as a ratio no more than one, reduced
+	/// by the commission, by the era points below the average and by the past
+	/// slashes. The validator without reported performance is rated the
+	/// average of the reported ones, or one if none is reported.
+	pub fn performance_ratio(validator: &T::PolkadotAccountId, average_era_points: u32) -> Ratio {
+		match Self::performances(validator) {
+			Some(performance) => Self::rate_performance(validator, &performance, average_era_points),
+			None => Self::average_performance_ratio().unwrap_or_else(Ratio::one),
+		}
+	}
+
+	fn rate_performance(
+		validator: &T::PolkadotAccountId,
+		performance: &ValidatorPerformance,
+		average_era_points: u32,
+	) -> Ratio {
+		let commission_ratio = Ratio::one().saturating_sub(
+			Ratio::checked_from_rational(performance.commission.deconstruct(), Perbill::ACCURACY).unwrap_or_default(),
+		);
+		let points_ratio = if average_era_points.is_zero() {
+			Ratio::one()
+		} else {
+			Ratio::checked_from_rational(performance.era_points, average_era_points)
+				.unwrap_or_default()
+				.min(Ratio::one())
+		};
+		let (slash_count, _) = Self::slashes(validator);
+		let slash_ratio = Ratio::checked_from_rational(1, slash_count.saturating_add(1)).unwrap_or_default();
+
+		commission_ratio
+			.saturating_mul(points_ratio)
+			.saturating_mul(slash_ratio)
+	}
+
+	/// The votes of the validator adjusted by its quality, that's the capped
+	/// votes multiplied by its performance ratio.
+	pub fn score(validator: &T::PolkadotAccountId, votes: Balance, average_era_points: u32) -> Balance {
+		let votes = votes.min(T::MaxVotesPerValidator::get());
+		Self::performance_ratio(validator, average_era_points).saturating_mul_int(votes)
+	}
+
+	/// The share of the voter in the rewards pool, that's the active bonded
+	/// balance multiplied by the average performance ratio of the validators
+	/// it votes for. A delegator votes for the nominations at the end of its
+	/// delegation chain.
+	pub fn reward_share(who: &T::AccountId, average_era_points: u32) -> Share {
+		let mut account = who.clone();
+		while let Some(target) = Self::delegations(&account) {
+			account = target;
+		}
+
+		let nominations = Self::nominations(&account);
+		if nominations.is_empty() {
+			return Zero::zero();
+		}
+
+		let active = Self::ledger(who).active;
+		let total_ratio = nominations.iter().fold(Ratio::zero(), |total, validator| {
+			total.saturating_add(Self::performance_ratio(validator, average_era_points))
+		});
+		total_ratio.saturating_mul_int(active) / nominations.len() as Balance
+	}
+
+	/// Update the share of the voter in the rewards pool, and schedule it to
+	/// be refreshed with the validator performance at next era. The shares
+	/// of delegators are refreshed at next era as well.
+	fn update_reward_share(who: &T::AccountId) {
+		let share = Self::reward_share(who, Self::average_era_points());
+		<orml_rewards::Module<T>>::set_share(who, T::RewardPoolId::get(), share);
+
+		let current_era = Self::current_era();
+		<RewardShareUpdates<T>>::remove(current_era, who);
+		if <Ledger<T>>::contains_key(who) {
+			<RewardShareUpdates<T>>::insert(current_era.saturating_add(One::one()), who, ());
+		}
+	}
+
+	/// Elect the nominees, return the number of validators visited.
+	fn rebalance() -> u32 {
+		let current_era = Self::current_era();
+		let average_era_points = Self::average_era_points();
+
+		let mut visited: u32 = 0;
+		let mut candidates = <Votes<T>>::iter()
+			.inspect(|_| visited = visited.saturating_add(1))
+			.filter(|(validator, _)| Self::is_eligible(validator, current_era))
+			.map(|(validator, votes)| {
+				let score = Self::score(&validator, votes, average_era_points);
+				(validator, score)
+			})
+			.filter(|(_, score)| !score.is_zero())
+			.collect::<Vec<(T::PolkadotAccountId, Balance)>>();
+
+		candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+		let new_nominees = candidates
 			.into_iter()
 			.take(T::NominateesCount::get())
 			.map(|(nominee, _)| nominee)
 			.collect::<Vec<_>>();
 
 		<Nominees<T>>::put(new_nominees);
+		visited
 	}
 }
 
 impl<T: Trait> NomineesProvider<T::PolkadotAccountId> for Module<T> {
 	fn nominees() -> Vec<T::PolkadotAccountId> {
-		Self::rebalance();
 		<Nominees<T>>::get()
 	}
 }
 
 impl<T: Trait> OnNewEra<EraIndex> for Module<T> {
-	fn on_new_era(era: EraIndex) {
+	fn on_new_era(era: EraIndex) -> Weight {
 		CurrentEra::put(era);
-		Self::rebalance();
+		let visited = Self::rebalance();
+		Self::deposit_event(RawEvent::NomineesElected(era, Self::nominees()));
+
+		// every visited validator reads its votes, blacklist, slashes and performance
+		T::DbWeight::get()
+			.reads_writes(3, 2)
+			.saturating_add(T::DbWeight::get().reads(4).saturating_mul(visited as Weight))
+	}
+}
+
+impl<T: Trait> OnValidatorPerformance<T::PolkadotAccountId, EraIndex> for Module<T> {
+	fn on_validator_performance(era: EraIndex, performances: Vec<(T::PolkadotAccountId, ValidatorPerformance)>) {
+		for (validator, performance) in performances {
+			if performance.slashed {
+				<Slashes<T>>::mutate(&validator, |(slash_count, last_slashed_era)| {
+					*slash_count = slash_count.saturating_add(1);
+					*last_slashed_era = era;
+				});
+
+				// remove the slashed validator from nominees at once
+				let mut nominees = Self::nominees();
+				if nominees.contains(&validator) {
+					nominees.retain(|nominee| *nominee != validator);
+					<Nominees<T>>::put(nominees);
+					Self::deposit_event(RawEvent::SlashedValidatorRemoved(validator.clone(), era));
+				}
+			}
+			<Performances<T>>::insert(validator, performance);
+		}
+
+		let (total_era_points, count) =
+			<Performances<T>>::iter().fold((0u64, 0u64), |(total, count), (_, performance)| {
+				(
+					total.saturating_add(performance.era_points.into()),
+					count.saturating_add(1),
+				)
+			});
+		let average_era_points = total_era_points.checked_div(count).unwrap_or_default() as u32;
+		AverageEraPoints::put(average_era_points);
+
+		let total_ratio = <Performances<T>>::iter().fold(Ratio::zero(), |total, (validator, performance)| {
+			total.saturating_add(Self::rate_performance(&validator, &performance, average_era_points))
+		});
+		if let Some(average_ratio) = total_ratio.into_inner().checked_div(count.into()) {
+			AveragePerformanceRatio::put(Ratio::from_inner(average_ratio));
+		} else {
+			AveragePerformanceRatio::kill();
+		}
+	}
+}
+
+impl<T: Trait> OnCommission<Balance, CurrencyId> for Module<T> {
+	fn on_commission(currency_id: CurrencyId, amount: Balance) {
+		let pool_id = T::RewardPoolId::get();
+		let mut rewards: Balance = Zero::zero();
+
+		// take the part of liquid currency commission to voters, only if there're
+		// voters to share it.
+		if currency_id == T::LiquidCurrencyId::get()
+			&& !<orml_rewards::Module<T>>::pools(pool_id).total_shares.is_zero()
+		{
+			let amount_to_voters = T::RewardRatio::get().saturating_mul_int(amount);
+			if !amount_to_voters.is_zero() && T::Currency::deposit(&T::RewardPool::get(), amount_to_voters).is_ok() {
+				<orml_rewards::Module<T>>::accumulate_reward(&pool_id, amount_to_voters);
+				rewards = amount_to_voters;
+				Self::deposit_event(RawEvent::VoterRewardsAccumulated(rewards));
+			}
+		}
+
+		T::OnCommission::on_commission(currency_id, amount.saturating_sub(rewards));
 	}
 }
@@ -3,26 +3,42 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{impl_outer_origin, parameter_types};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types};
+use frame_system::EnsureRoot;
+use orml_traits::RewardHandler;
 use primitives::{Amount, CurrencyId};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
+pub type PoolId = u32;
 
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
+pub const CHARLIE: AccountId = 2;
+pub const DAVE: AccountId = 3;
 pub const ACA: CurrencyId = CurrencyId::ACA;
 pub const LDOT: CurrencyId = CurrencyId::LDOT;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
 
+mod nominees_election {
+	pub use super::super::*;
+}
+
 impl_outer_origin! {
 	pub enum Origin for Runtime {}
 }
 
+impl_outer_event! {
+	pub enum TestEvent for Runtime {
+		frame_system<T>,
+		nominees_election<T>,
+	}
+}
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const MaximumBlockWeight: u32 = 1024;
@@ -40,7 +56,7 @@ impl frame_system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type MaximumBlockLength = MaximumBlockLength;
@@ -105,15 +121,58 @@ parameter_types! {
 	pub const BondingDuration: EraIndex = 4;
 	pub const NominateesCount: usize = 5;
 	pub const MaxUnlockingChunks: usize = 3;
+	pub const MaxVotesPerValidator: Balance = 800;
+	pub const SlashExclusionDuration: EraIndex = 2;
+	pub const MaxDelegationDepth: u32 = 2;
+	pub const RewardPoolId: PoolId = 0;
+	pub const RewardPool: AccountId = 10;
+	pub RewardRatio: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub const MaxRewardShareUpdatesPerBlock: u32 = 2;
+}
+
+pub struct MockRewardHandler;
+impl RewardHandler<AccountId, BlockNumber> for MockRewardHandler {
+	type Share = Share;
+	type Balance = Balance;
+	type PoolId = PoolId;
+	type CurrencyId = CurrencyId;
+
+	fn accumulate_reward(_now: BlockNumber, _callback: impl Fn(PoolId, Balance)) -> Vec<(CurrencyId, Balance)> {
+		vec![]
+	}
+
+	fn payout(who: &AccountId, _pool_id: PoolId, amount: Balance) {
+		let _ = LDOTCurrency::transfer(&RewardPool::get(), who, amount);
+	}
+}
+
+impl orml_rewards::Trait for Runtime {
+	type Share = Share;
+	type Balance = Balance;
+	type PoolId = PoolId;
+	type Handler = MockRewardHandler;
+	type WeightInfo = ();
 }
+pub type RewardsModule = orml_rewards::Module<Runtime>;
 
 impl Trait for Runtime {
+	type Event = TestEvent;
 	type Currency = LDOTCurrency;
 	type PolkadotAccountId = AccountId;
 	type MinBondThreshold = MinBondThreshold;
 	type BondingDuration = BondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxVotesPerValidator = MaxVotesPerValidator;
+	type SlashExclusionDuration = SlashExclusionDuration;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type LiquidCurrencyId = GetLDOTCurrencyId;
+	type RewardPoolId = RewardPoolId;
+	type RewardPool = RewardPool;
+	type RewardRatio = RewardRatio;
+	type MaxRewardShareUpdatesPerBlock = MaxRewardShareUpdatesPerBlock;
+	type OnCommission = ();
 }
 pub type NomineesElectionModule = Module<Runtime>;
 
@@ -124,7 +183,12 @@ pub struct ExtBuilder {
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
-			endowed_accounts: vec![(ALICE, LDOT, 1000), (BOB, LDOT, 1000)],
+			endowed_accounts: vec![
+				(ALICE, LDOT, 1000),
+				(BOB, LDOT, 1000),
+				(CHARLIE, LDOT, 1000),
+				(DAVE, LDOT, 1000),
+			],
 		}
 	}
 }
@@ -3,8 +3,12 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{ExtBuilder, LDOTCurrency, NomineesElectionModule, Origin, Runtime, ALICE, BOB};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{
+	AccountId, ExtBuilder, LDOTCurrency, NomineesElectionModule, Origin, RewardPool, RewardPoolId, RewardsModule,
+	Runtime, System, TestEvent, ALICE, BOB, CHARLIE, DAVE, LDOT,
+};
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn bond_below_min_bond_threshold() {
@@ -236,3 +240,402 @@ fn update_votes_work() {
 		assert_eq!(NomineesElectionModule::votes(4), 10);
 	});
 }
+
+#[test]
+fn elect_nominees_at_new_era() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_eq!(
+			<NomineesElectionModule as NomineesProvider<AccountId>>::nominees(),
+			vec![]
+		);
+
+		NomineesElectionModule::on_new_era(1);
+		assert_eq!(NomineesElectionModule::current_era(), 1);
+		assert_eq!(
+			<NomineesElectionModule as NomineesProvider<AccountId>>::nominees(),
+			vec![1]
+		);
+		let elected_event = TestEvent::nominees_election(RawEvent::NomineesElected(1, vec![1]));
+		assert!(System::events().iter().any(|record| record.event == elected_event));
+	});
+}
+
+#[test]
+fn score_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(NomineesElectionModule::score(&1, 500, 100), 500);
+		assert_eq!(NomineesElectionModule::score(&1, 1000, 100), 800);
+
+		<Performances<Runtime>>::insert(
+			1,
+			ValidatorPerformance {
+				commission: Perbill::from_percent(10),
+				era_points: 50,
+				slashed: false,
+			},
+		);
+		assert_eq!(NomineesElectionModule::score(&1, 1000, 0), 720);
+		assert_eq!(NomineesElectionModule::score(&1, 1000, 50), 720);
+		assert_eq!(NomineesElectionModule::score(&1, 1000, 100), 360);
+
+		<Slashes<Runtime>>::insert(1, (1, 0));
+		assert_eq!(NomineesElectionModule::score(&1, 1000, 100), 180);
+	});
+}
+
+#[test]
+fn unreported_validator_rated_average_performance() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(NomineesElectionModule::performance_ratio(&3, 0), Ratio::one());
+
+		NomineesElectionModule::on_validator_performance(
+			0,
+			vec![
+				(
+					1,
+					ValidatorPerformance {
+						commission: Perbill::from_percent(50),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+				(
+					2,
+					ValidatorPerformance {
+						commission: Perbill::zero(),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+			],
+		);
+		assert_eq!(
+			NomineesElectionModule::average_performance_ratio(),
+			Some(Ratio::saturating_from_rational(3, 4))
+		);
+		assert_eq!(
+			NomineesElectionModule::performance_ratio(&1, 100),
+			Ratio::saturating_from_rational(1, 2)
+		);
+		assert_eq!(NomineesElectionModule::performance_ratio(&2, 100), Ratio::one());
+		assert_eq!(
+			NomineesElectionModule::performance_ratio(&3, 100),
+			Ratio::saturating_from_rational(3, 4)
+		);
+		assert_eq!(NomineesElectionModule::score(&3, 600, 100), 450);
+	});
+}
+
+#[test]
+fn rebalance_by_score() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 1000));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 600));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5, 6]
+		));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![6, 7]));
+		NomineesElectionModule::on_validator_performance(
+			0,
+			vec![
+				(
+					1,
+					ValidatorPerformance {
+						commission: Perbill::from_percent(50),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+				(
+					2,
+					ValidatorPerformance {
+						commission: Perbill::zero(),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+			],
+		);
+
+		NomineesElectionModule::rebalance();
+		// the votes of 6 are capped to 800, 1 is scored 400 with 50% commission, and
+		// the unreported validators are rated the average 75%
+		let mut nominees = NomineesElectionModule::nominees();
+		nominees.sort();
+		assert_eq!(nominees, vec![2, 3, 4, 5, 6]);
+	});
+}
+
+#[test]
+fn blacklist_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2]));
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 2);
+
+		assert_noop!(
+			NomineesElectionModule::add_blacklist(Origin::signed(ALICE), 1),
+			BadOrigin
+		);
+		assert_ok!(NomineesElectionModule::add_blacklist(Origin::root(), 1));
+		let blacklisted_event = TestEvent::nominees_election(RawEvent::ValidatorBlacklisted(1));
+		assert!(System::events().iter().any(|record| record.event == blacklisted_event));
+		assert_eq!(NomineesElectionModule::blacklist(1), true);
+		assert_eq!(NomineesElectionModule::nominees(), vec![2]);
+		assert_noop!(
+			NomineesElectionModule::nominate(Origin::signed(BOB), vec![1]),
+			Error::<Runtime>::BlacklistedTarget
+		);
+
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees(), vec![2]);
+
+		assert_ok!(NomineesElectionModule::remove_blacklist(Origin::root(), 1));
+		assert_eq!(NomineesElectionModule::blacklist(1), false);
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 2);
+	});
+}
+
+#[test]
+fn slashed_validator_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2]));
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 2);
+
+		NomineesElectionModule::on_validator_performance(
+			1,
+			vec![(
+				1,
+				ValidatorPerformance {
+					commission: Perbill::zero(),
+					era_points: 0,
+					slashed: true,
+				},
+			)],
+		);
+		let removed_event = TestEvent::nominees_election(RawEvent::SlashedValidatorRemoved(1, 1));
+		assert!(System::events().iter().any(|record| record.event == removed_event));
+		assert_eq!(NomineesElectionModule::nominees(), vec![2]);
+		assert_eq!(NomineesElectionModule::slashes(1), (1, 1));
+
+		// excluded from election until the slash exclusion duration passed
+		assert_eq!(NomineesElectionModule::is_eligible(&1, 2), false);
+		assert_eq!(NomineesElectionModule::is_eligible(&1, 3), true);
+		NomineesElectionModule::on_new_era(2);
+		assert_eq!(NomineesElectionModule::nominees(), vec![2]);
+	});
+}
+
+#[test]
+fn conviction_weighted_votes() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![10]));
+		assert_eq!(NomineesElectionModule::votes(10), 100);
+
+		assert_ok!(NomineesElectionModule::set_conviction(
+			Origin::signed(ALICE),
+			Conviction::Locked3x
+		));
+		let updated_event = TestEvent::nominees_election(RawEvent::ConvictionUpdated(ALICE, Conviction::Locked3x));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+		assert_eq!(NomineesElectionModule::votes(10), 300);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::votes(10), 600);
+
+		// unbonded funds are locked for 4x bonding duration
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::votes(10), 300);
+		assert_eq!(NomineesElectionModule::ledger(&ALICE).unlocking[0].era, 16);
+
+		assert_noop!(
+			NomineesElectionModule::set_conviction(Origin::signed(ALICE), Conviction::None),
+			Error::<Runtime>::ConvictionLocked,
+		);
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::votes(10), 0);
+
+		// the conviction is kept until the unlocking funds are withdrawn
+		assert_noop!(
+			NomineesElectionModule::set_conviction(Origin::signed(ALICE), Conviction::None),
+			Error::<Runtime>::ConvictionLocked,
+		);
+		assert_ok!(NomineesElectionModule::rebond(Origin::signed(ALICE), 100));
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::ledger(&ALICE).unlocking[1].era, 16);
+
+		NomineesElectionModule::on_new_era(16);
+		assert_ok!(NomineesElectionModule::withdraw_unbonded(Origin::signed(ALICE)));
+		assert_ok!(NomineesElectionModule::set_conviction(
+			Origin::signed(ALICE),
+			Conviction::None
+		));
+	});
+}
+
+#[test]
+fn delegate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(BOB), BOB),
+			Error::<Runtime>::SelfDelegation,
+		);
+		assert_noop!(
+			NomineesElectionModule::undelegate(Origin::signed(BOB)),
+			Error::<Runtime>::NotDelegated,
+		);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![10]));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 200));
+		assert_ok!(NomineesElectionModule::delegate(Origin::signed(BOB), ALICE));
+		let delegated_event = TestEvent::nominees_election(RawEvent::Delegated(BOB, ALICE));
+		assert!(System::events().iter().any(|record| record.event == delegated_event));
+		assert_eq!(NomineesElectionModule::delegated_votes(&ALICE), 200);
+		assert_eq!(NomineesElectionModule::votes(10), 300);
+		assert_noop!(
+			NomineesElectionModule::nominate(Origin::signed(BOB), vec![11]),
+			Error::<Runtime>::AlreadyDelegated,
+		);
+
+		// votes follow the ledger and conviction of the delegator
+		assert_ok!(NomineesElectionModule::set_conviction(
+			Origin::signed(BOB),
+			Conviction::Locked2x
+		));
+		assert_eq!(NomineesElectionModule::votes(10), 500);
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(BOB), 100));
+		assert_eq!(NomineesElectionModule::votes(10), 300);
+
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![11]));
+		assert_eq!(NomineesElectionModule::votes(10), 0);
+		assert_eq!(NomineesElectionModule::votes(11), 300);
+
+		assert_ok!(NomineesElectionModule::undelegate(Origin::signed(BOB)));
+		let undelegated_event = TestEvent::nominees_election(RawEvent::Undelegated(BOB));
+		assert!(System::events().iter().any(|record| record.event == undelegated_event));
+		assert_eq!(NomineesElectionModule::delegated_votes(&ALICE), 0);
+		assert_eq!(NomineesElectionModule::delegator_count(&ALICE), 0);
+		assert_eq!(NomineesElectionModule::votes(11), 100);
+	});
+}
+
+#[test]
+fn delegation_depth_limited() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(CHARLIE), 100));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(CHARLIE), vec![10]));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 100));
+		assert_ok!(NomineesElectionModule::delegate(Origin::signed(BOB), CHARLIE));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_ok!(NomineesElectionModule::delegate(Origin::signed(ALICE), BOB));
+		assert_eq!(NomineesElectionModule::votes(10), 300);
+
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(DAVE), ALICE),
+			Error::<Runtime>::DelegationTooDeep,
+		);
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(BOB), DAVE),
+			Error::<Runtime>::HasDelegators,
+		);
+
+		// the change at the start of chain reaches the end
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 50));
+		assert_eq!(NomineesElectionModule::delegated_votes(&BOB), 150);
+		assert_eq!(NomineesElectionModule::delegated_votes(&CHARLIE), 250);
+		assert_eq!(NomineesElectionModule::votes(10), 350);
+	});
+}
+
+#[test]
+fn voters_rewarded_from_commission() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// no voters to share the commission
+		NomineesElectionModule::on_commission(LDOT, 1000);
+		assert_eq!(LDOTCurrency::free_balance(&RewardPool::get()), 0);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 600));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 400));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![2]));
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(RewardPoolId::get(), BOB).0,
+			400
+		);
+
+		NomineesElectionModule::on_validator_performance(
+			0,
+			vec![
+				(
+					1,
+					ValidatorPerformance {
+						commission: Perbill::zero(),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+				(
+					2,
+					ValidatorPerformance {
+						commission: Perbill::from_percent(50),
+						era_points: 100,
+						slashed: false,
+					},
+				),
+			],
+		);
+		assert_eq!(NomineesElectionModule::average_era_points(), 100);
+		NomineesElectionModule::on_new_era(1);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(RewardPoolId::get(), BOB).0,
+			400
+		);
+
+		// the shares are refreshed in the following blocks
+		NomineesElectionModule::on_initialize(2);
+		assert_eq!(RewardShareUpdates::<Runtime>::iter_prefix(1).count(), 1);
+		NomineesElectionModule::on_initialize(3);
+		assert_eq!(RewardShareUpdates::<Runtime>::iter_prefix(1).count(), 0);
+		assert_eq!(RewardShareUpdates::<Runtime>::iter_prefix(2).count(), 2);
+		assert_eq!(NomineesElectionModule::next_reward_share_update_era(), 2);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(RewardPoolId::get(), ALICE).0,
+			600
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(RewardPoolId::get(), BOB).0,
+			200
+		);
+
+		NomineesElectionModule::on_commission(LDOT, 1000);
+		let accumulated_event = TestEvent::nominees_election(RawEvent::VoterRewardsAccumulated(200));
+		assert!(System::events().iter().any(|record| record.event == accumulated_event));
+		assert_eq!(LDOTCurrency::free_balance(&RewardPool::get()), 200);
+		assert_eq!(RewardsModule::pools(RewardPoolId::get()).total_rewards, 200);
+
+		// commission in other currencies is not shared
+		NomineesElectionModule::on_commission(CurrencyId::DOT, 1000);
+		assert_eq!(RewardsModule::pools(RewardPoolId::get()).total_rewards, 200);
+
+		assert_ok!(NomineesElectionModule::claim_rewards(Origin::signed(ALICE)));
+		assert_eq!(LDOTCurrency::free_balance(&ALICE), 1150);
+		assert_ok!(NomineesElectionModule::claim_rewards(Origin::signed(BOB)));
+		assert_eq!(LDOTCurrency::free_balance(&BOB), 1050);
+		assert_eq!(LDOTCurrency::free_balance(&RewardPool::get()), 0);
+	});
+}
@@ -1,20 +1,157 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{debug, decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, Parameter};
-use frame_system::{self as system, ensure_root, ensure_signed};
+use codec::{Decode, Encode};
+use frame_support::{
+	debug, decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::migration::{get_storage_value, remove_storage_prefix},
+	traits::{EnsureOrigin, Get},
+	weights::Weight,
+	Parameter,
+};
+use frame_system::{self as system, ensure_root};
 use orml_traits::BasicCurrency;
 use orml_utilities::with_transaction_result;
 use primitives::{Balance, EraIndex};
 use sp_runtime::{
 	traits::{CheckedSub, MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
-	DispatchResult, FixedPointNumber,
+	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::{fmt::Debug, prelude::*};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 use support::{
-	OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
-	PolkadotUnlockChunk, Rate,
+	OnNewEra, OnPolkadotBridgeResult, OnValidatorPerformance, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
+	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, Rate, SubAccountIndex, ValidatorPerformance,
 };
 
+mod mock;
+mod tests;
+
+pub type MessageId = u64;
+
+/// The staking calls of the sub-accounts of parachain sent to relay chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum RelaychainCall<PolkadotAccountId> {
+	BondExtra(Balance),
+	Unbond(Balance),
+	Rebond(Balance),
+	WithdrawUnbonded,
+	Nominate(Vec<PolkadotAccountId>),
+	PayoutNominator,
+	/// Transfer from parachain to the sub-account at relay chain.
+	TransferToRelaychain(Balance),
+	/// Transfer from the sub-account at relay chain to parachain.
+	TransferToParachain(Balance),
+}
+
+/// The staking ledger and free balance of a sub-account at relay chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct RelaychainLedger {
+	pub active: Balance,
+	pub unlocking: Vec<PolkadotUnlockChunk<Balance, EraIndex>>,
+	pub free: Balance,
+	pub era: EraIndex,
+}
+
+impl RelaychainLedger {
+	/// active + total_unlocking
+	pub fn total(&self) -> Balance {
+		self.unlocking
+			.iter()
+			.fold(self.active, |total, chunk| total.saturating_add(chunk.value))
+	}
+
+	/// active + total_unlocking + free
+	pub fn balance(&self) -> Balance {
+		self.total().saturating_add(self.free)
+	}
+
+	/// Apply the effect of staking call to the ledger, except the staking
+	/// reward of `PayoutNominator` which is only known by relay chain.
+	pub fn apply<PolkadotAccountId>(
+		&mut self,
+		call: &RelaychainCall<PolkadotAccountId>,
+		bonding_duration: EraIndex,
+	) -> Result<(), &'static str> {
+		match call {
+			RelaychainCall::BondExtra(amount) => {
+				self.free = self.free.checked_sub(*amount).ok_or("free balance is not enough")?;
+				self.active = self.active.saturating_add(*amount);
+			}
+			RelaychainCall::Unbond(amount) => {
+				self.active = self.active.checked_sub(*amount).ok_or("active is not enough")?;
+				self.unlocking.push(PolkadotUnlockChunk {
+					value: *amount,
+					era: self.era.saturating_add(bonding_duration),
+				});
+			}
+			RelaychainCall::Rebond(amount) => {
+				let mut rebond_balance: Balance = Zero::zero();
+				while let Some(last) = self.unlocking.last_mut() {
+					if rebond_balance.saturating_add(last.value) <= *amount {
+						rebond_balance = rebond_balance.saturating_add(last.value);
+						self.unlocking.pop();
+					} else {
+						let diff = amount.saturating_sub(rebond_balance);
+						rebond_balance = rebond_balance.saturating_add(diff);
+						last.value = last.value.saturating_sub(diff);
+					}
+
+					if rebond_balance >= *amount {
+						break;
+					}
+				}
+				if rebond_balance < *amount {
+					return Err("unlocking is not enough");
+				}
+				self.active = self.active.saturating_add(rebond_balance);
+			}
+			RelaychainCall::WithdrawUnbonded => {
+				let era = self.era;
+				let mut free = self.free;
+				self.unlocking.retain(|chunk| {
+					if chunk.era > era {
+						true
+					} else {
+						free = free.saturating_add(chunk.value);
+						false
+					}
+				});
+				self.free = free;
+			}
+			RelaychainCall::TransferToRelaychain(amount) => {
+				self.free = self.free.saturating_add(*amount);
+			}
+			RelaychainCall::TransferToParachain(amount) => {
+				self.free = self.free.checked_sub(*amount).ok_or("free balance is not enough")?;
+			}
+			RelaychainCall::Nominate(_) | RelaychainCall::PayoutNominator => {}
+		}
+
+		Ok(())
+	}
+}
+
+/// The message sent to relay chain and waiting for the result.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct PendingMessage<AccountId, PolkadotAccountId> {
+	/// The sub-account which makes the call.
+	pub account_index: SubAccountIndex,
+	pub call: RelaychainCall<PolkadotAccountId>,
+	/// The account to deposit to when `TransferToParachain` succeeds, or to
+	/// refund when `TransferToRelaychain` fails.
+	pub account: Option<AccountId>,
+}
+
+/// The transport of cross-chain messages to relay chain.
+pub trait RelaychainTransport<BlockNumber> {
+	/// Send the encoded `(SubAccountIndex, RelaychainCall)` to relay chain.
+	/// The result should be reported back by `confirm_message` with the same
+	/// `message_id`.
+	fn send_message(message_id: MessageId, message: Vec<u8>) -> DispatchResult;
+
+	/// Called at the end of each block.
+	fn on_finalize(_now: BlockNumber) {}
+}
+
 pub trait Trait: system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 	type DOTCurrency: BasicCurrency<Self::AccountId, Balance = Balance>;
@@ -22,6 +159,25 @@ pub trait Trait: system::Trait {
 	type BondingDuration: Get<EraIndex>;
 	type EraLength: Get<Self::BlockNumber>;
 	type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
+
+	/// The number of sub-accounts staking at relay chain, each of them is
+	/// limited by the number of nominators allowed.
+	type SubAccountCount: Get<SubAccountIndex>;
+
+	/// The transport to send messages to relay chain.
+	type Transport: RelaychainTransport<Self::BlockNumber>;
+
+	/// The origin which reports the results and ledger of relay chain.
+	type RelaychainOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The handler of the validator performance reported from relay chain.
+	type OnValidatorPerformance: OnValidatorPerformance<Self::PolkadotAccountId, EraIndex>;
+
+	/// The handler of the confirmed results of payouts and transfers.
+	type OnResult: OnPolkadotBridgeResult<Self::AccountId, Balance>;
+
+	/// The max number of pending messages of each sub-account.
+	type MaxPendingMessages: Get<u32>;
 }
 
 decl_event!(
@@ -30,8 +186,20 @@ decl_event!(
 		<T as system::Trait>::AccountId,
 		Balance = Balance,
 	{
-		/// \[account, amount\]
-		Mint(AccountId, Balance),
+		/// \[message_id\]
+		MessageSent(MessageId),
+		/// \[message_id\]
+		MessageConfirmed(MessageId),
+		/// \[message_id\]
+		MessageFailed(MessageId),
+		/// \[to, amount\]
+		ReceivedFromRelaychain(AccountId, Balance),
+		/// \[who, amount\]
+		TransferToRelaychainRefunded(AccountId, Balance),
+		/// \[era\]
+		NewEra(EraIndex),
+		/// \[era, count\]
+		ValidatorPerformanceReported(EraIndex, u32),
 	}
 );
 
@@ -40,17 +208,43 @@ decl_error! {
 	pub enum Error for Module<T: Trait> {
 		NotEnough,
 		Overflow,
+		/// The message does not exist or has been confirmed
+		MessageNotFound,
+		/// The sub-account index is out of the sub-account count
+		InvalidSubAccount,
+		/// The sub-account has too many messages waiting for the result
+		TooManyPendingMessages,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as PolkadotBridge {
-		pub Bonded get(fn bonded): Balance;	// active
-		pub Available get(fn available): Balance; // balance - bonded
-		pub Unbonding get(fn unbonding): Vec<(Balance, EraIndex)>;
 		pub CurrentEra get(fn current_era): EraIndex;
-		pub EraStartBlockNumber get(fn era_start_block_number): T::BlockNumber;
-		pub ForcedEra get(fn forced_era): Option<T::BlockNumber>;
+
+		/// The new era reported by relay chain, which is handled at the
+		/// beginning of next block. A later era replaces the unhandled one.
+		pub PendingNewEra get(fn pending_new_era): Option<EraIndex>;
+
+		/// The ledger of each sub-account at relay chain, only updated by
+		/// confirmed results.
+		pub ConfirmedLedger get(fn confirmed_ledger): map hasher(twox_64_concat) SubAccountIndex => RelaychainLedger;
+
+		/// The ledger of each sub-account expected after all its pending
+		/// messages are confirmed, used to check the calls before sending.
+		/// It is the confirmed ledger with the pending messages applied.
+		pub ExpectedLedger get(fn expected_ledger): map hasher(twox_64_concat) SubAccountIndex => RelaychainLedger;
+
+		pub PendingMessages get(fn pending_messages): map hasher(twox_64_concat) MessageId => Option<PendingMessage<T::AccountId, T::PolkadotAccountId>>;
+		pub NextMessageId get(fn next_message_id): MessageId;
+
+		/// The ids of the pending messages of each sub-account, in the order
+		/// they are sent.
+		pub PendingMessageIds get(fn pending_message_ids): map hasher(twox_64_concat) SubAccountIndex => Vec<MessageId>;
+
+		/// The state of the local relay chain stand-in.
+		pub SimulatedLedger get(fn simulated_ledger): map hasher(twox_64_concat) SubAccountIndex => RelaychainLedger;
+		pub SimulatedEraStartBlockNumber get(fn simulated_era_start_block_number): T::BlockNumber;
+		pub SimulatedForcedEra get(fn simulated_forced_era): Option<T::BlockNumber>;
 		pub MockRewardRate get(fn mock_reward_rate) config(): Option<Rate>;
 	}
 }
@@ -62,75 +256,85 @@ decl_module! {
 
 		const BondingDuration: EraIndex = T::BondingDuration::get();
 		const EraLength: T::BlockNumber = T::EraLength::get();
+		const SubAccountCount: SubAccountIndex = T::SubAccountCount::get();
+		const MaxPendingMessages: u32 = T::MaxPendingMessages::get();
 
-		#[weight = 10_000]
-		pub fn set_mock_reward_rate(origin, mock_reward_rate: Option<Rate>) {
-			with_transaction_result(|| {
-				ensure_root(origin)?;
-				if let Some(mock_reward_rate) = mock_reward_rate {
-					MockRewardRate::put(mock_reward_rate);
-				} else {
-					MockRewardRate::kill();
-				}
-				Ok(())
-			})?;
+		/// Migrate the single ledger of the old bridge simulator to the
+		/// sub-account 0.
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_ledger()
 		}
 
-		#[weight = 10_000]
-		pub fn simulate_bond(origin, amount: Balance) {
-			with_transaction_result(|| {
-				ensure_root(origin)?;
-				Self::bond_extra(amount)?;
-				Ok(())
-			})?;
+		/// Handle the new era reported in previous block.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			if let Some(era) = PendingNewEra::take() {
+				T::OnNewEra::on_new_era(era).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
 		}
 
-		#[weight = 10_000]
-		pub fn simulate_unbond(origin, amount: Balance) {
+		/// Report the result of the message and the ledger after it from
+		/// relay chain.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(6, 6) + T::DbWeight::get().reads(T::MaxPendingMessages::get() as Weight)]
+		pub fn confirm_message(origin, message_id: MessageId, success: bool, ledger: RelaychainLedger) {
 			with_transaction_result(|| {
-				ensure_root(origin)?;
-				Self::unbond(amount)?;
+				T::RelaychainOrigin::ensure_origin(origin)?;
+				Self::on_message_result(message_id, success, ledger)?;
 				Ok(())
 			})?;
 		}
 
-		#[weight = 10_000]
-		pub fn simulate_withdraw_unbonded(origin) {
+		/// Report the ledger of a sub-account at relay chain, e.g. at new era
+		/// or after slash.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3) + T::DbWeight::get().reads(T::MaxPendingMessages::get() as Weight)]
+		pub fn update_ledger(origin, account_index: SubAccountIndex, ledger: RelaychainLedger) {
 			with_transaction_result(|| {
-				// ignore because we don't care who send the message
-				let _ = ensure_signed(origin)?;
-				Self::withdraw_unbonded();
+				T::RelaychainOrigin::ensure_origin(origin)?;
+				ensure!(account_index < T::SubAccountCount::get(), Error::<T>::InvalidSubAccount);
+				Self::on_ledger_updated(account_index, ledger);
 				Ok(())
 			})?;
 		}
 
+		/// Report the performance of validators at relay chain in the era.
 		#[weight = 10_000]
-		pub fn simulate_slash(origin, amount: Balance) {
+		pub fn report_validator_performance(
+			origin,
+			era: EraIndex,
+			performances: Vec<(T::PolkadotAccountId, ValidatorPerformance)>,
+		) {
 			with_transaction_result(|| {
-				ensure_root(origin)?;
-				Bonded::mutate(|balance| *balance = balance.saturating_sub(amount));
+				T::RelaychainOrigin::ensure_origin(origin)?;
+				let count = performances.len() as u32;
+				T::OnValidatorPerformance::on_validator_performance(era, performances);
+				<Module<T>>::deposit_event(RawEvent::ValidatorPerformanceReported(era, count));
 				Ok(())
 			})?;
 		}
 
 		#[weight = 10_000]
-		pub fn simualte_receive(origin, to: T::AccountId, amount: Balance) {
+		pub fn set_mock_reward_rate(origin, mock_reward_rate: Option<Rate>) {
 			with_transaction_result(|| {
 				ensure_root(origin)?;
-				let new_available = Self::available().checked_sub(amount).ok_or(Error::<T>::NotEnough)?;
-				T::DOTCurrency::deposit(&to, amount)?;
-				Available::put(new_available);
+				if let Some(mock_reward_rate) = mock_reward_rate {
+					MockRewardRate::put(mock_reward_rate);
+				} else {
+					MockRewardRate::kill();
+				}
 				Ok(())
 			})?;
 		}
 
-		#[weight = 10_000]
-		pub fn simulate_redeem(origin, _to: T::PolkadotAccountId, amount: Balance) {
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4) + T::DbWeight::get().reads(T::MaxPendingMessages::get() as Weight)]
+		pub fn simulate_slash(origin, account_index: SubAccountIndex, amount: Balance) {
 			with_transaction_result(|| {
-				let from = ensure_signed(origin)?;
-				let new_available = Self::available().checked_add(amount).ok_or(Error::<T>::Overflow)?;
-				T::DOTCurrency::withdraw(&from, amount)?;
-				Available::put(new_available);
+				ensure_root(origin)?;
+				ensure!(account_index < T::SubAccountCount::get(), Error::<T>::InvalidSubAccount);
+				let mut ledger = Self::simulated_ledger(account_index);
+				ledger.active = ledger.active.saturating_sub(amount);
+				SimulatedLedger::insert(account_index, ledger.clone());
+				Self::on_ledger_updated(account_index, ledger);
 				Ok(())
 			})?;
 		}
@@ -140,209 +344,289 @@ decl_module! {
 			with_transaction_result(|| {
 				ensure_root(origin)?;
 				if at > <system::Module<T>>::block_number() {
-					<ForcedEra<T>>::put(at);
+					<SimulatedForcedEra<T>>::put(at);
 				}
 				Ok(())
 			})?;
 		}
 
 		fn on_finalize(now: T::BlockNumber) {
-			let force_era = Self::forced_era().map_or(false, |block| {
-				if block == now {
-					<ForcedEra<T>>::kill();
-					true
-				} else {
-					false
-				}
-			});
-			let len = now.checked_sub(&Self::era_start_block_number()).unwrap_or_default();
-
-			if len >= T::EraLength::get() || force_era {
-				Self::new_era(now);
-			}
+			T::Transport::on_finalize(now);
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
-	fn new_era(now: T::BlockNumber) {
-		let new_era = CurrentEra::mutate(|era| {
-			*era += 1;
-			*era
-		});
-		<EraStartBlockNumber<T>>::put(now);
-		T::OnNewEra::on_new_era(new_era);
+	/// Send the staking call of the sub-account to relay chain, the expected
+	/// ledger is updated at once and the confirmed ledger is updated by the
+	/// result.
+	fn send(
+		account_index: SubAccountIndex,
+		call: RelaychainCall<T::PolkadotAccountId>,
+		account: Option<T::AccountId>,
+	) -> DispatchResult {
+		ensure!(account_index < T::SubAccountCount::get(), Error::<T>::InvalidSubAccount);
+		let previous_expected_ledger = Self::expected_ledger(account_index);
+		let mut expected_ledger = previous_expected_ledger.clone();
+		expected_ledger
+			.apply(&call, T::BondingDuration::get())
+			.map_err(|_| Error::<T>::NotEnough)?;
+
+		let mut message_ids = Self::pending_message_ids(account_index);
+		ensure!(
+			(message_ids.len() as u32) < T::MaxPendingMessages::get(),
+			Error::<T>::TooManyPendingMessages,
+		);
+
+		let message_id = Self::next_message_id();
+		NextMessageId::put(message_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+		message_ids.push(message_id);
+		PendingMessageIds::insert(account_index, message_ids);
+		<PendingMessages<T>>::insert(
+			message_id,
+			PendingMessage {
+				account_index,
+				call: call.clone(),
+				account,
+			},
+		);
+		ExpectedLedger::insert(account_index, expected_ledger);
+		<Module<T>>::deposit_event(RawEvent::MessageSent(message_id));
+
+		if let Err(e) = T::Transport::send_message(message_id, (account_index, call).encode()) {
+			<PendingMessages<T>>::remove(message_id);
+			Self::remove_pending_message_id(account_index, message_id);
+			ExpectedLedger::insert(account_index, previous_expected_ledger);
+			return Err(e);
+		}
+
+		Ok(())
 	}
-}
 
-impl<T: Trait> PolkadotBridgeType<T::BlockNumber, EraIndex> for Module<T> {
-	type BondingDuration = T::BondingDuration;
-	type EraLength = T::EraLength;
-	type PolkadotAccountId = T::PolkadotAccountId;
-}
+	/// Handle the result of the message, `ledger` is the ledger of the
+	/// sub-account which sent it. The results of payouts and transfers are
+	/// passed to `OnResult` after the ledger is updated.
+	pub fn on_message_result(message_id: MessageId, success: bool, ledger: RelaychainLedger) -> DispatchResult {
+		let message = <PendingMessages<T>>::take(message_id).ok_or(Error::<T>::MessageNotFound)?;
+		let account_index = message.account_index;
+		Self::remove_pending_message_id(account_index, message_id);
+		let previous_free = Self::confirmed_ledger(account_index).free;
+		let (era, free) = (ledger.era, ledger.free);
+		Self::set_ledger(account_index, ledger);
+
+		match (success, message.call, message.account) {
+			(true, RelaychainCall::TransferToParachain(amount), Some(to)) => {
+				T::DOTCurrency::deposit(&to, amount)?;
+				<Module<T>>::deposit_event(RawEvent::ReceivedFromRelaychain(to.clone(), amount));
+				T::OnResult::on_receive_from_bridge(&to, account_index, amount, true);
+			}
+			(false, RelaychainCall::TransferToParachain(amount), Some(to)) => {
+				T::OnResult::on_receive_from_bridge(&to, account_index, amount, false);
+			}
+			(false, RelaychainCall::TransferToRelaychain(amount), Some(from)) => {
+				T::DOTCurrency::deposit(&from, amount)?;
+				<Module<T>>::deposit_event(RawEvent::TransferToRelaychainRefunded(from.clone(), amount));
+				T::OnResult::on_transfer_to_bridge_failed(&from, account_index, amount);
+			}
+			(true, RelaychainCall::PayoutNominator, _) => {
+				// payout only increases the free balance of the sub-account
+				let reward = free.saturating_sub(previous_free);
+				if !reward.is_zero() {
+					T::OnResult::on_payout(account_index, reward);
+				}
+			}
+			_ => {}
+		}
 
-impl<T: Trait> PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraIndex> for Module<T> {
-	// simulate bond extra
-	fn bond_extra(amount: Balance) -> DispatchResult {
-		let free_balance = Self::available();
-
-		if !amount.is_zero() {
-			ensure!(free_balance >= amount, Error::<T>::NotEnough);
-			Bonded::mutate(|balance| *balance += amount);
-			Available::mutate(|balance| *balance -= amount);
-
-			debug::debug!(
-				target: "polkadot bridge simulator",
-				"bond extra: {:?}",
-				amount,
-			);
+		if success {
+			<Module<T>>::deposit_event(RawEvent::MessageConfirmed(message_id));
+		} else {
+			<Module<T>>::deposit_event(RawEvent::MessageFailed(message_id));
 		}
 
+		Self::update_era(era);
 		Ok(())
 	}
 
-	// simulate unbond
-	fn unbond(amount: Balance) -> DispatchResult {
-		let bonded = Self::bonded();
-
-		if !amount.is_zero() {
-			ensure!(bonded >= amount, Error::<T>::NotEnough);
-			let mut unbonding = Self::unbonding();
-			let current_era = Self::current_era();
-			let unbonded_era_index = current_era + T::BondingDuration::get();
-			unbonding.push((amount, unbonded_era_index));
-
-			Bonded::mutate(|bonded| *bonded -= amount);
-			Unbonding::put(unbonding);
-
-			debug::debug!(
-				target: "polkadot bridge simulator",
-				"unbond: {:?} at {:?}",
-				amount, current_era,
-			);
+	pub fn on_ledger_updated(account_index: SubAccountIndex, ledger: RelaychainLedger) {
+		let era = ledger.era;
+		Self::set_ledger(account_index, ledger);
+		Self::update_era(era);
+	}
+
+	/// Update the confirmed ledger, and rebuild the expected ledger from it
+	/// so the changes reported by relay chain, e.g. slash, are not lost.
+	fn set_ledger(account_index: SubAccountIndex, ledger: RelaychainLedger) {
+		let mut expected_ledger = ledger.clone();
+		for message in Self::pending_messages_of(account_index) {
+			// the call which can not be applied any more will fail at relay chain
+			let mut next_ledger = expected_ledger.clone();
+			if next_ledger.apply(&message.call, T::BondingDuration::get()).is_ok() {
+				expected_ledger = next_ledger;
+			}
 		}
+		ConfirmedLedger::insert(account_index, ledger);
+		ExpectedLedger::insert(account_index, expected_ledger);
+	}
 
-		Ok(())
+	/// Update the current era, the new era is handled in `on_initialize`
+	/// of next block.
+	fn update_era(era: EraIndex) {
+		if era > Self::current_era() {
+			CurrentEra::put(era);
+			PendingNewEra::put(era);
+			<Module<T>>::deposit_event(RawEvent::NewEra(era));
+		}
 	}
 
-	// simulate rebond
-	fn rebond(amount: Balance) -> DispatchResult {
-		let mut unbonding = Self::unbonding();
-		let mut bonded = Self::bonded();
-		let mut rebond_balance: Balance = Zero::zero();
-
-		while let Some(last) = unbonding.last_mut() {
-			if rebond_balance + last.0 <= amount {
-				rebond_balance += last.0;
-				bonded += last.0;
-				unbonding.pop();
-			} else {
-				let diff = amount - rebond_balance;
+	/// The pending messages of the sub-account, in the order they are sent.
+	fn pending_messages_of(
+		account_index: SubAccountIndex,
+	) -> impl Iterator<Item = PendingMessage<T::AccountId, T::PolkadotAccountId>> {
+		Self::pending_message_ids(account_index)
+			.into_iter()
+			.filter_map(Self::pending_messages)
+	}
 
-				rebond_balance += diff;
-				bonded += diff;
-				last.0 -= diff;
+	fn remove_pending_message_id(account_index: SubAccountIndex, message_id: MessageId) {
+		PendingMessageIds::mutate_exists(account_index, |maybe_ids| {
+			if let Some(ids) = maybe_ids {
+				ids.retain(|id| *id != message_id);
+				if ids.is_empty() {
+					*maybe_ids = None;
+				}
 			}
+		});
+	}
 
-			if rebond_balance >= amount {
-				break;
-			}
+	/// Migrate the bonded, available and unbonding balances of the old
+	/// bridge simulator to the ledgers of sub-account 0, and the simulated
+	/// era schedule.
+	fn migrate_ledger() -> Weight {
+		let bonded = get_storage_value::<Balance>(b"PolkadotBridge", b"Bonded", &[]);
+		let available = get_storage_value::<Balance>(b"PolkadotBridge", b"Available", &[]);
+		let unbonding = get_storage_value::<Vec<(Balance, EraIndex)>>(b"PolkadotBridge", b"Unbonding", &[]);
+		let era_start = get_storage_value::<T::BlockNumber>(b"PolkadotBridge", b"EraStartBlockNumber", &[]);
+		let forced_era = get_storage_value::<T::BlockNumber>(b"PolkadotBridge", b"ForcedEra", &[]);
+		let mut weight = T::DbWeight::get().reads(5);
+
+		if bonded.is_some() || available.is_some() || unbonding.is_some() {
+			let ledger = RelaychainLedger {
+				active: bonded.unwrap_or_default(),
+				unlocking: unbonding
+					.unwrap_or_default()
+					.into_iter()
+					.map(|(value, era)| PolkadotUnlockChunk { value, era })
+					.collect(),
+				free: available.unwrap_or_default(),
+				era: Self::current_era(),
+			};
+			ConfirmedLedger::insert(0, ledger.clone());
+			ExpectedLedger::insert(0, ledger.clone());
+			SimulatedLedger::insert(0, ledger);
+			remove_storage_prefix(b"PolkadotBridge", b"Bonded", &[]);
+			remove_storage_prefix(b"PolkadotBridge", b"Available", &[]);
+			remove_storage_prefix(b"PolkadotBridge", b"Unbonding", &[]);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 6));
 		}
-		ensure!(rebond_balance >= amount, Error::<T>::NotEnough);
-		if !rebond_balance.is_zero() {
-			Bonded::put(bonded);
-			Unbonding::put(unbonding);
-
-			debug::debug!(
-				target: "polkadot bridge simulator",
-				"rebond: {:?}",
-				rebond_balance,
-			);
+		if let Some(era_start) = era_start {
+			<SimulatedEraStartBlockNumber<T>>::put(era_start);
+			remove_storage_prefix(b"PolkadotBridge", b"EraStartBlockNumber", &[]);
+			weight = weight.saturating_add(T::DbWeight::get().writes(2));
 		}
-		Ok(())
+		if let Some(forced_era) = forced_era {
+			<SimulatedForcedEra<T>>::put(forced_era);
+			remove_storage_prefix(b"PolkadotBridge", b"ForcedEra", &[]);
+			weight = weight.saturating_add(T::DbWeight::get().writes(2));
+		}
+
+		weight
 	}
+}
 
-	// simulate withdraw unbonded
-	fn withdraw_unbonded() {
-		let current_era = Self::current_era();
-		let mut available = Self::available();
-		let unbonding = Self::unbonding()
-			.into_iter()
-			.filter(|(value, era_index)| {
-				if *era_index > current_era {
-					true
-				} else {
-					available = available.saturating_add(*value);
-					false
-				}
-			})
-			.collect::<Vec<_>>();
+impl<T: Trait> PolkadotBridgeType<T::BlockNumber, EraIndex> for Module<T> {
+	type BondingDuration = T::BondingDuration;
+	type EraLength = T::EraLength;
+	type PolkadotAccountId = T::PolkadotAccountId;
+	type SubAccountCount = T::SubAccountCount;
+}
 
-		Available::put(available);
-		Unbonding::put(unbonding);
+impl<T: Trait> PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraIndex> for Module<T> {
+	fn bond_extra(account_index: SubAccountIndex, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		Self::send(account_index, RelaychainCall::BondExtra(amount), None)
 	}
 
-	// simulate receive staking reward
-	fn payout_nominator() {
-		if let Some(mock_reward_rate) = Self::mock_reward_rate() {
-			let reward = mock_reward_rate.saturating_mul_int(Self::bonded());
-			Available::mutate(|balance| *balance = balance.saturating_add(reward));
-
-			debug::debug!(
-				target: "polkadot bridge simulator",
-				"get reward: {:?}",
-				reward,
-			);
+	fn unbond(account_index: SubAccountIndex, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
 		}
+		Self::send(account_index, RelaychainCall::Unbond(amount), None)
 	}
 
-	fn nominate(_targets: Vec<Self::PolkadotAccountId>) {}
+	fn rebond(account_index: SubAccountIndex, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		Self::send(account_index, RelaychainCall::Rebond(amount), None)
+	}
 
-	// simulate transfer dot from acala to parachain account in polkadot
-	fn transfer_to_bridge(from: &T::AccountId, amount: Balance) -> DispatchResult {
+	fn withdraw_unbonded(account_index: SubAccountIndex) {
+		let _ = Self::send(account_index, RelaychainCall::WithdrawUnbonded, None);
+	}
+
+	fn nominate(account_index: SubAccountIndex, targets: Vec<Self::PolkadotAccountId>) {
+		let _ = Self::send(account_index, RelaychainCall::Nominate(targets), None);
+	}
+
+	fn payout_nominator(account_index: SubAccountIndex) {
+		let _ = Self::send(account_index, RelaychainCall::PayoutNominator, None);
+	}
+
+	fn transfer_to_bridge(from: &T::AccountId, to: SubAccountIndex, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
 		T::DOTCurrency::withdraw(from, amount)?;
-		Available::mutate(|balance| *balance = balance.saturating_add(amount));
-		Ok(())
+		Self::send(to, RelaychainCall::TransferToRelaychain(amount), Some(from.clone())).or_else(|e| {
+			T::DOTCurrency::deposit(from, amount)?;
+			Err(e)
+		})
 	}
 
-	// simulate receive dot from parachain account in polkadot to acala
-	fn receive_from_bridge(to: &T::AccountId, amount: Balance) -> DispatchResult {
-		let new_available = Self::available().checked_sub(amount).ok_or(Error::<T>::NotEnough)?;
-		Available::put(new_available);
-		T::DOTCurrency::deposit(&to, amount)?;
-		Ok(())
+	/// The staking currency is deposited to `to` when the transfer is
+	/// confirmed by relay chain.
+	fn receive_from_bridge(to: &T::AccountId, from: SubAccountIndex, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		Self::send(from, RelaychainCall::TransferToParachain(amount), Some(to.clone()))
 	}
 }
 
 impl<T: Trait> PolkadotBridgeState<Balance, EraIndex> for Module<T> {
-	fn ledger() -> PolkadotStakingLedger<Balance, EraIndex> {
-		let active = Self::bonded();
-		let mut total = active;
-		let unlocking = Self::unbonding()
-			.into_iter()
-			.map(|(balance, era_index)| {
-				total = total.saturating_add(balance);
-				PolkadotUnlockChunk {
-					value: balance,
-					era: era_index,
-				}
-			})
-			.collect::<_>();
-
+	fn ledger(account_index: SubAccountIndex) -> PolkadotStakingLedger<Balance, EraIndex> {
+		// the expected ledger, which the following calls are checked against
+		let ledger = Self::expected_ledger(account_index);
 		PolkadotStakingLedger {
-			total,
-			active,
-			unlocking,
+			total: ledger.total(),
+			active: ledger.active,
+			unlocking: ledger.unlocking,
 		}
 	}
 
-	fn balance() -> Balance {
-		// bonded + total_unlocking + available
-		Self::unbonding()
-			.iter()
-			.fold(Self::bonded().saturating_add(Self::available()), |x, (balance, _)| {
-				x.saturating_add(*balance)
-			})
+	fn balance(account_index: SubAccountIndex) -> Balance {
+		// the confirmed balance with the transfers in flight
+		Self::pending_messages_of(account_index).fold(
+			Self::confirmed_ledger(account_index).balance(),
+			|balance, message| match message.call {
+				RelaychainCall::TransferToRelaychain(amount) => balance.saturating_add(amount),
+				RelaychainCall::TransferToParachain(amount) => balance.saturating_sub(amount),
+				_ => balance,
+			},
+		)
 	}
 
 	fn current_era() -> EraIndex {
@@ -351,3 +635,69 @@ impl<T: Trait> PolkadotBridgeState<Balance, EraIndex> for Module<T> {
 }
 
 impl<T: Trait> PolkadotBridge<T::AccountId, T::BlockNumber, Balance, EraIndex> for Module<T> {}
+
+/// A local in-process relay chain stand-in. It executes the messages at
+/// once on the simulated ledgers of sub-accounts and reports the results
+/// back, until the real cross-chain transport is available.
+pub struct LocalRelaychain<T>(PhantomData<T>);
+
+impl<T: Trait> RelaychainTransport<T::BlockNumber> for LocalRelaychain<T> {
+	fn send_message(message_id: MessageId, message: Vec<u8>) -> DispatchResult {
+		let (account_index, call) =
+			<(SubAccountIndex, RelaychainCall<T::PolkadotAccountId>)>::decode(&mut &message[..])
+				.map_err(|_| DispatchError::Other("invalid relay chain message"))?;
+		let mut ledger = <Module<T>>::simulated_ledger(account_index);
+		let result = ledger.apply(&call, T::BondingDuration::get());
+
+		if result.is_ok() {
+			if let (RelaychainCall::PayoutNominator, Some(mock_reward_rate)) = (&call, <Module<T>>::mock_reward_rate())
+			{
+				let reward = mock_reward_rate.saturating_mul_int(ledger.active);
+				ledger.free = ledger.free.saturating_add(reward);
+
+				debug::debug!(
+					target: "polkadot bridge simulator",
+					"sub-account {:?} get reward: {:?}",
+					account_index, reward,
+				);
+			}
+			SimulatedLedger::insert(account_index, ledger);
+		}
+
+		debug::debug!(
+			target: "polkadot bridge simulator",
+			"message {:?} of sub-account {:?}: {:?} {:?}",
+			message_id, account_index, call, result,
+		);
+
+		<Module<T>>::on_message_result(message_id, result.is_ok(), <Module<T>>::simulated_ledger(account_index))
+	}
+
+	fn on_finalize(now: T::BlockNumber) {
+		let force_era = <Module<T>>::simulated_forced_era().map_or(false, |block| {
+			if block == now {
+				<SimulatedForcedEra<T>>::kill();
+				true
+			} else {
+				false
+			}
+		});
+		let len = now
+			.checked_sub(&<Module<T>>::simulated_era_start_block_number())
+			.unwrap_or_default();
+
+		if len >= T::EraLength::get() || force_era {
+			<SimulatedEraStartBlockNumber<T>>::put(now);
+			let era = <Module<T>>::current_era().saturating_add(1);
+
+			// update the ledgers of all sub-accounts before new era is triggered
+			for account_index in 0..T::SubAccountCount::get() {
+				let mut ledger = <Module<T>>::simulated_ledger(account_index);
+				ledger.era = era;
+				SimulatedLedger::insert(account_index, ledger.clone());
+				<Module<T>>::set_ledger(account_index, ledger);
+			}
+			<Module<T>>::update_era(era);
+		}
+	}
+}
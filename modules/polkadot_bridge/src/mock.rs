@@ -0,0 +1,230 @@
+//! Mocks for polkadot bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use primitives::{Amount, CurrencyId};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 0;
+pub const BOB: AccountId = 1;
+pub const ACA: CurrencyId = CurrencyId::ACA;
+pub const DOT: CurrencyId = CurrencyId::DOT;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Runtime;
+
+mod polkadot_bridge {
+	pub use super::super::*;
+}
+
+impl_outer_origin! {
+	pub enum Origin for Runtime {}
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Runtime {
+		frame_system<T>,
+		polkadot_bridge<T>,
+		orml_tokens<T>,
+		pallet_balances<T>,
+		orml_currencies<T>,
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: u32 = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+}
+pub type System = frame_system::Module<Runtime>;
+
+impl orml_tokens::Trait for Runtime {
+	type Event = TestEvent;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type OnReceived = ();
+	type WeightInfo = ();
+}
+pub type TokensModule = orml_tokens::Module<Runtime>;
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = TestEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+type PalletBalances = pallet_balances::Module<Runtime>;
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = ACA;
+	pub const GetDOTCurrencyId: CurrencyId = DOT;
+}
+
+pub type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, PalletBalances, Amount, BlockNumber>;
+pub type DOTCurrency = orml_currencies::Currency<Runtime, GetDOTCurrencyId>;
+
+impl orml_currencies::Trait for Runtime {
+	type Event = TestEvent;
+	type MultiCurrency = TokensModule;
+	type NativeCurrency = NativeCurrency;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static NEW_ERAS: RefCell<Vec<EraIndex>> = RefCell::new(vec![]);
+	static HOLD_MESSAGES: RefCell<bool> = RefCell::new(false);
+	pub static SENT_MESSAGES: RefCell<Vec<(MessageId, Vec<u8>)>> = RefCell::new(vec![]);
+	pub static VALIDATOR_PERFORMANCES: RefCell<Vec<(AccountId, ValidatorPerformance)>> = RefCell::new(vec![]);
+	pub static PAYOUTS: RefCell<Vec<(SubAccountIndex, Balance)>> = RefCell::new(vec![]);
+	pub static RECEIVED: RefCell<Vec<(AccountId, SubAccountIndex, Balance, bool)>> = RefCell::new(vec![]);
+	pub static REFUNDED: RefCell<Vec<(AccountId, SubAccountIndex, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct MockOnNewEra;
+impl OnNewEra<EraIndex> for MockOnNewEra {
+	fn on_new_era(era: EraIndex) -> Weight {
+		NEW_ERAS.with(|v| v.borrow_mut().push(era));
+		0
+	}
+}
+
+pub struct MockOnValidatorPerformance;
+impl OnValidatorPerformance<AccountId, EraIndex> for MockOnValidatorPerformance {
+	fn on_validator_performance(_era: EraIndex, performances: Vec<(AccountId, ValidatorPerformance)>) {
+		VALIDATOR_PERFORMANCES.with(|v| v.borrow_mut().extend(performances));
+	}
+}
+
+pub struct MockOnResult;
+impl OnPolkadotBridgeResult<AccountId, Balance> for MockOnResult {
+	fn on_payout(account_index: SubAccountIndex, reward: Balance) {
+		PAYOUTS.with(|v| v.borrow_mut().push((account_index, reward)));
+	}
+
+	fn on_transfer_to_bridge_failed(from: &AccountId, account_index: SubAccountIndex, amount: Balance) {
+		REFUNDED.with(|v| v.borrow_mut().push((*from, account_index, amount)));
+	}
+
+	fn on_receive_from_bridge(to: &AccountId, account_index: SubAccountIndex, amount: Balance, success: bool) {
+		RECEIVED.with(|v| v.borrow_mut().push((*to, account_index, amount, success)));
+	}
+}
+
+/// Hold the messages to be confirmed manually instead of sending them to
+/// the local relay chain.
+pub fn hold_messages() {
+	HOLD_MESSAGES.with(|v| *v.borrow_mut() = true);
+}
+
+pub struct MockTransport;
+impl RelaychainTransport<BlockNumber> for MockTransport {
+	fn send_message(message_id: MessageId, message: Vec<u8>) -> DispatchResult {
+		if HOLD_MESSAGES.with(|v| *v.borrow()) {
+			SENT_MESSAGES.with(|v| v.borrow_mut().push((message_id, message)));
+			Ok(())
+		} else {
+			LocalRelaychain::<Runtime>::send_message(message_id, message)
+		}
+	}
+
+	fn on_finalize(now: BlockNumber) {
+		LocalRelaychain::<Runtime>::on_finalize(now);
+	}
+}
+
+parameter_types! {
+	pub const BondingDuration: EraIndex = 2;
+	pub const EraLength: BlockNumber = 10;
+	pub const SubAccountCount: SubAccountIndex = 2;
+	pub const MaxPendingMessages: u32 = 3;
+}
+
+impl Trait for Runtime {
+	type Event = TestEvent;
+	type DOTCurrency = DOTCurrency;
+	type OnNewEra = MockOnNewEra;
+	type BondingDuration = BondingDuration;
+	type EraLength = EraLength;
+	type PolkadotAccountId = AccountId;
+	type SubAccountCount = SubAccountCount;
+	type Transport = MockTransport;
+	type RelaychainOrigin = EnsureRoot<AccountId>;
+	type OnValidatorPerformance = MockOnValidatorPerformance;
+	type OnResult = MockOnResult;
+	type MaxPendingMessages = MaxPendingMessages;
+}
+pub type PolkadotBridgeModule = Module<Runtime>;
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(ALICE, DOT, 1000), (BOB, DOT, 1000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,391 @@
+//! Unit tests for polkadot bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::{OnFinalize, OnInitialize, OnRuntimeUpgrade},
+};
+use mock::{
+	hold_messages, DOTCurrency, ExtBuilder, Origin, PolkadotBridgeModule, Runtime, System, TestEvent, ALICE, BOB,
+	NEW_ERAS, PAYOUTS, RECEIVED, REFUNDED, VALIDATOR_PERFORMANCES,
+};
+use sp_runtime::{traits::BadOrigin, Perbill};
+
+#[test]
+fn transfer_and_bond_through_local_relaychain() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 500));
+		assert_eq!(DOTCurrency::free_balance(&ALICE), 500);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).free, 500);
+		assert_eq!(PolkadotBridgeModule::simulated_ledger(0).free, 500);
+		assert_eq!(PolkadotBridgeModule::pending_messages(0), None);
+		assert_eq!(PolkadotBridgeModule::balance(0), 500);
+
+		let sent_event = TestEvent::polkadot_bridge(RawEvent::MessageSent(0));
+		assert!(System::events().iter().any(|record| record.event == sent_event));
+		let confirmed_event = TestEvent::polkadot_bridge(RawEvent::MessageConfirmed(0));
+		assert!(System::events().iter().any(|record| record.event == confirmed_event));
+
+		assert_ok!(PolkadotBridgeModule::bond_extra(0, 300));
+		assert_eq!(
+			PolkadotBridgeModule::ledger(0),
+			PolkadotStakingLedger {
+				total: 300,
+				active: 300,
+				unlocking: vec![],
+			}
+		);
+		assert_eq!(PolkadotBridgeModule::balance(0), 500);
+		assert_noop!(PolkadotBridgeModule::bond_extra(0, 300), Error::<Runtime>::NotEnough);
+	});
+}
+
+#[test]
+fn unbond_withdraw_and_receive_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 500));
+		assert_ok!(PolkadotBridgeModule::bond_extra(0, 300));
+		assert_ok!(PolkadotBridgeModule::unbond(0, 100));
+		assert_eq!(
+			PolkadotBridgeModule::ledger(0),
+			PolkadotStakingLedger {
+				total: 300,
+				active: 200,
+				unlocking: vec![PolkadotUnlockChunk { value: 100, era: 2 }],
+			}
+		);
+
+		PolkadotBridgeModule::on_finalize(10);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+		PolkadotBridgeModule::on_initialize(11);
+		PolkadotBridgeModule::on_finalize(20);
+		assert_eq!(PolkadotBridgeModule::current_era(), 2);
+		PolkadotBridgeModule::on_initialize(21);
+		assert_eq!(NEW_ERAS.with(|v| v.borrow().clone()), vec![1, 2]);
+
+		PolkadotBridgeModule::withdraw_unbonded(0);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).free, 300);
+		assert_eq!(PolkadotBridgeModule::ledger(0).unlocking, vec![]);
+
+		assert_ok!(PolkadotBridgeModule::receive_from_bridge(&BOB, 0, 300));
+		let received_event = TestEvent::polkadot_bridge(RawEvent::ReceivedFromRelaychain(BOB, 300));
+		assert!(System::events().iter().any(|record| record.event == received_event));
+		assert_eq!(DOTCurrency::free_balance(&BOB), 1300);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).free, 0);
+		assert_eq!(PolkadotBridgeModule::balance(0), 200);
+	});
+}
+
+#[test]
+fn payout_and_slash_at_local_relaychain() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PolkadotBridgeModule::set_mock_reward_rate(
+			Origin::root(),
+			Some(Rate::saturating_from_rational(10, 100))
+		));
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 500));
+		assert_ok!(PolkadotBridgeModule::bond_extra(0, 300));
+
+		PolkadotBridgeModule::payout_nominator(0);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).free, 230);
+		assert_eq!(PolkadotBridgeModule::balance(0), 530);
+		assert_eq!(PAYOUTS.with(|v| v.borrow().clone()), vec![(0, 30)]);
+
+		assert_noop!(
+			PolkadotBridgeModule::simulate_slash(Origin::signed(ALICE), 0, 50),
+			BadOrigin
+		);
+		assert_ok!(PolkadotBridgeModule::simulate_slash(Origin::root(), 0, 50));
+		assert_eq!(PolkadotBridgeModule::ledger(0).active, 250);
+		assert_eq!(PolkadotBridgeModule::balance(0), 480);
+	});
+}
+
+#[test]
+fn confirm_message_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		hold_messages();
+		let ledger = RelaychainLedger {
+			free: 500,
+			..Default::default()
+		};
+
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 500));
+		assert_eq!(DOTCurrency::free_balance(&ALICE), 500);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).free, 0);
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0).free, 500);
+		assert_eq!(PolkadotBridgeModule::balance(0), 500);
+
+		assert_noop!(
+			PolkadotBridgeModule::confirm_message(Origin::signed(ALICE), 0, true, ledger.clone()),
+			BadOrigin
+		);
+		assert_ok!(PolkadotBridgeModule::confirm_message(
+			Origin::root(),
+			0,
+			true,
+			ledger.clone()
+		));
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0), ledger);
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0), ledger);
+		assert_eq!(PolkadotBridgeModule::pending_messages(0), None);
+		assert_noop!(
+			PolkadotBridgeModule::confirm_message(Origin::root(), 0, true, ledger.clone()),
+			Error::<Runtime>::MessageNotFound
+		);
+
+		// failed receive does not deposit
+		assert_ok!(PolkadotBridgeModule::receive_from_bridge(&BOB, 0, 200));
+		assert_eq!(PolkadotBridgeModule::balance(0), 300);
+		assert_ok!(PolkadotBridgeModule::confirm_message(
+			Origin::root(),
+			1,
+			false,
+			ledger.clone()
+		));
+		let failed_event = TestEvent::polkadot_bridge(RawEvent::MessageFailed(1));
+		assert!(System::events().iter().any(|record| record.event == failed_event));
+		assert_eq!(DOTCurrency::free_balance(&BOB), 1000);
+		assert_eq!(PolkadotBridgeModule::balance(0), 500);
+		assert_eq!(RECEIVED.with(|v| v.borrow().clone()), vec![(BOB, 0, 200, false)]);
+
+		// failed transfer is refunded
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100));
+		assert_eq!(DOTCurrency::free_balance(&ALICE), 400);
+		assert_ok!(PolkadotBridgeModule::confirm_message(
+			Origin::root(),
+			2,
+			false,
+			ledger.clone()
+		));
+		let refunded_event = TestEvent::polkadot_bridge(RawEvent::TransferToRelaychainRefunded(ALICE, 100));
+		assert!(System::events().iter().any(|record| record.event == refunded_event));
+		assert_eq!(DOTCurrency::free_balance(&ALICE), 500);
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0), ledger);
+		assert_eq!(REFUNDED.with(|v| v.borrow().clone()), vec![(ALICE, 0, 100)]);
+	});
+}
+
+#[test]
+fn deferred_payout_reports_reward_when_confirmed() {
+	ExtBuilder::default().build().execute_with(|| {
+		hold_messages();
+		let ledger = RelaychainLedger {
+			active: 300,
+			free: 200,
+			..Default::default()
+		};
+		assert_ok!(PolkadotBridgeModule::update_ledger(Origin::root(), 0, ledger.clone()));
+
+		PolkadotBridgeModule::payout_nominator(0);
+		assert_eq!(PolkadotBridgeModule::balance(0), 500);
+		assert_eq!(PAYOUTS.with(|v| v.borrow().clone()), vec![]);
+
+		assert_ok!(PolkadotBridgeModule::confirm_message(
+			Origin::root(),
+			0,
+			true,
+			RelaychainLedger { free: 230, ..ledger }
+		));
+		assert_eq!(PolkadotBridgeModule::balance(0), 530);
+		assert_eq!(PAYOUTS.with(|v| v.borrow().clone()), vec![(0, 30)]);
+	});
+}
+
+#[test]
+fn slash_updates_expected_ledger_with_pending_messages() {
+	ExtBuilder::default().build().execute_with(|| {
+		hold_messages();
+		let ledger = RelaychainLedger {
+			active: 300,
+			free: 200,
+			..Default::default()
+		};
+		assert_ok!(PolkadotBridgeModule::update_ledger(Origin::root(), 0, ledger.clone()));
+		assert_ok!(PolkadotBridgeModule::unbond(0, 200));
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0).active, 100);
+
+		// slashed while the unbond is pending
+		assert_ok!(PolkadotBridgeModule::update_ledger(
+			Origin::root(),
+			0,
+			RelaychainLedger { active: 250, ..ledger }
+		));
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).active, 250);
+		assert_eq!(
+			PolkadotBridgeModule::expected_ledger(0),
+			RelaychainLedger {
+				active: 50,
+				unlocking: vec![PolkadotUnlockChunk { value: 200, era: 2 }],
+				free: 200,
+				era: 0,
+			}
+		);
+		assert_eq!(PolkadotBridgeModule::ledger(0).active, 50);
+		assert_noop!(PolkadotBridgeModule::unbond(0, 100), Error::<Runtime>::NotEnough);
+	});
+}
+
+#[test]
+fn update_ledger_trigger_new_era() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let ledger = RelaychainLedger {
+			era: 3,
+			..Default::default()
+		};
+
+		assert_noop!(
+			PolkadotBridgeModule::update_ledger(Origin::signed(ALICE), 0, ledger.clone()),
+			BadOrigin
+		);
+		assert_ok!(PolkadotBridgeModule::update_ledger(Origin::root(), 0, ledger.clone()));
+		let new_era_event = TestEvent::polkadot_bridge(RawEvent::NewEra(3));
+		assert!(System::events().iter().any(|record| record.event == new_era_event));
+		assert_eq!(PolkadotBridgeModule::current_era(), 3);
+		assert_eq!(PolkadotBridgeModule::pending_new_era(), Some(3));
+		assert_eq!(NEW_ERAS.with(|v| v.borrow().clone()), vec![]);
+
+		assert_ok!(PolkadotBridgeModule::update_ledger(Origin::root(), 1, ledger));
+		PolkadotBridgeModule::on_initialize(2);
+		assert_eq!(PolkadotBridgeModule::pending_new_era(), None);
+		assert_eq!(NEW_ERAS.with(|v| v.borrow().clone()), vec![3]);
+
+		PolkadotBridgeModule::on_initialize(3);
+		assert_eq!(NEW_ERAS.with(|v| v.borrow().clone()), vec![3]);
+	});
+}
+
+#[test]
+fn pending_messages_are_bounded_per_sub_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		hold_messages();
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100));
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100));
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 1, 100));
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100));
+		assert_eq!(PolkadotBridgeModule::pending_message_ids(0), vec![0, 1, 3]);
+		assert_eq!(PolkadotBridgeModule::pending_message_ids(1), vec![2]);
+		assert_noop!(
+			PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100),
+			Error::<Runtime>::TooManyPendingMessages
+		);
+		assert_eq!(PolkadotBridgeModule::balance(0), 300);
+		assert_eq!(PolkadotBridgeModule::balance(1), 100);
+
+		assert_ok!(PolkadotBridgeModule::confirm_message(
+			Origin::root(),
+			1,
+			true,
+			RelaychainLedger {
+				free: 100,
+				..Default::default()
+			}
+		));
+		assert_eq!(PolkadotBridgeModule::pending_message_ids(0), vec![0, 3]);
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0).free, 300);
+		assert_eq!(PolkadotBridgeModule::balance(0), 300);
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 100));
+		assert_eq!(PolkadotBridgeModule::pending_message_ids(0), vec![0, 3, 4]);
+	});
+}
+
+#[test]
+fn migrate_ledger_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		CurrentEra::put(5);
+		put_storage_value(b"PolkadotBridge", b"Bonded", &[], 300u128);
+		put_storage_value(b"PolkadotBridge", b"Available", &[], 200u128);
+		put_storage_value(b"PolkadotBridge", b"Unbonding", &[], vec![(100u128, 6u32)]);
+		put_storage_value(b"PolkadotBridge", b"EraStartBlockNumber", &[], 40u64);
+
+		PolkadotBridgeModule::on_runtime_upgrade();
+		let ledger = RelaychainLedger {
+			active: 300,
+			unlocking: vec![PolkadotUnlockChunk { value: 100, era: 6 }],
+			free: 200,
+			era: 5,
+		};
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0), ledger);
+		assert_eq!(PolkadotBridgeModule::expected_ledger(0), ledger);
+		assert_eq!(PolkadotBridgeModule::simulated_ledger(0), ledger);
+		assert_eq!(PolkadotBridgeModule::balance(0), 600);
+		assert_eq!(PolkadotBridgeModule::simulated_era_start_block_number(), 40);
+		assert_eq!(PolkadotBridgeModule::simulated_forced_era(), None);
+		assert_eq!(get_storage_value::<Balance>(b"PolkadotBridge", b"Bonded", &[]), None);
+		assert_eq!(
+			get_storage_value::<Vec<(Balance, EraIndex)>>(b"PolkadotBridge", b"Unbonding", &[]),
+			None
+		);
+		assert_eq!(
+			get_storage_value::<u64>(b"PolkadotBridge", b"EraStartBlockNumber", &[]),
+			None
+		);
+	});
+}
+
+#[test]
+fn sub_accounts_have_separate_ledgers() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&ALICE, 0, 300));
+		assert_ok!(PolkadotBridgeModule::transfer_to_bridge(&BOB, 1, 200));
+		assert_ok!(PolkadotBridgeModule::bond_extra(1, 200));
+		assert_noop!(PolkadotBridgeModule::bond_extra(1, 100), Error::<Runtime>::NotEnough);
+		assert_noop!(
+			PolkadotBridgeModule::transfer_to_bridge(&ALICE, 2, 100),
+			Error::<Runtime>::InvalidSubAccount
+		);
+		assert_eq!(DOTCurrency::free_balance(&ALICE), 700);
+
+		assert_eq!(PolkadotBridgeModule::ledger(0).total, 0);
+		assert_eq!(PolkadotBridgeModule::balance(0), 300);
+		assert_eq!(PolkadotBridgeModule::ledger(1).active, 200);
+		assert_eq!(PolkadotBridgeModule::balance(1), 200);
+
+		// new era is applied to all sub-accounts
+		PolkadotBridgeModule::on_finalize(10);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(0).era, 1);
+		assert_eq!(PolkadotBridgeModule::confirmed_ledger(1).era, 1);
+		PolkadotBridgeModule::on_initialize(11);
+		assert_eq!(NEW_ERAS.with(|v| v.borrow().clone()), vec![1]);
+	});
+}
+
+#[test]
+fn report_validator_performance_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let performance = ValidatorPerformance {
+			commission: Perbill::from_percent(5),
+			era_points: 100,
+			slashed: false,
+		};
+
+		assert_noop!(
+			PolkadotBridgeModule::report_validator_performance(
+				Origin::signed(ALICE),
+				1,
+				vec![(10, performance.clone())]
+			),
+			BadOrigin
+		);
+		assert_ok!(PolkadotBridgeModule::report_validator_performance(
+			Origin::root(),
+			1,
+			vec![(10, performance.clone())]
+		));
+		let reported_event = TestEvent::polkadot_bridge(RawEvent::ValidatorPerformanceReported(1, 1));
+		assert!(System::events().iter().any(|record| record.event == reported_event));
+		assert_eq!(
+			VALIDATOR_PERFORMANCES.with(|v| v.borrow().clone()),
+			vec![(10, performance)]
+		);
+	});
+}
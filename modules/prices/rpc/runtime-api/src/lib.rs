@@ -0,0 +1,38 @@
+//! Runtime API definition for prices module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+pub use support::{offchain_price_key, OFFCHAIN_PRICE_HISTORY_PREFIX};
+
+/// The aggregated price and the sources it is made up of.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AggregatedPrice<TimestampedPrice, DataProviderId> {
+	/// The aggregated price
+	pub price: TimestampedPrice,
+	/// The sources used by the aggregation
+	pub sources: Vec<DataProviderId>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait PricesApi<CurrencyId, TimestampedPrice, DataProviderId, BlockNumber, Price> where
+		CurrencyId: Codec,
+		TimestampedPrice: Codec,
+		DataProviderId: Codec,
+		BlockNumber: Codec,
+		Price: Codec,
+	{
+		fn get_aggregated_price(currency_id: CurrencyId) -> Option<AggregatedPrice<TimestampedPrice, DataProviderId>>;
+
+		fn get_price_at(currency_id: CurrencyId, block_number: BlockNumber) -> Option<Price>;
+	}
+}
@@ -0,0 +1,126 @@
+//! RPC interface for the prices module.
+
+use codec::{Codec, Decode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_prices_rpc_runtime_api::{offchain_price_key, AggregatedPrice};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as PricesClient;
+pub use module_prices_rpc_runtime_api::PricesApi as PricesRuntimeApi;
+
+#[rpc]
+pub trait PricesApi<BlockHash, CurrencyId, BlockNumber, Price, ResponseType> {
+	#[rpc(name = "prices_getAggregatedPrice")]
+	fn get_aggregated_price(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<ResponseType>>;
+
+	#[rpc(name = "prices_getPriceAt")]
+	fn get_price_at(
+		&self,
+		currency_id: CurrencyId,
+		block_number: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Option<Price>>;
+}
+
+/// A struct that implements the [`PricesApi`].
+pub struct Prices<C, B, S> {
+	client: Arc<C>,
+	offchain_storage: Option<S>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B, S> Prices<C, B, S> {
+	/// Create new `Prices` with the given reference to the client, and the
+	/// offchain storage to read the indexed price history.
+	pub fn new(client: Arc<C>, offchain_storage: Option<S>) -> Self {
+		Prices {
+			client,
+			offchain_storage,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, S, CurrencyId, TimestampedPrice, DataProviderId, BlockNumber, Price>
+	PricesApi<
+		<Block as BlockT>::Hash,
+		CurrencyId,
+		BlockNumber,
+		Price,
+		AggregatedPrice<TimestampedPrice, DataProviderId>,
+	> for Prices<C, Block, S>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PricesRuntimeApi<Block, CurrencyId, TimestampedPrice, DataProviderId, BlockNumber, Price>,
+	S: OffchainStorage + 'static,
+	CurrencyId: Codec,
+	TimestampedPrice: Codec,
+	DataProviderId: Codec,
+	BlockNumber: Codec,
+	Price: Codec,
+{
+	fn get_aggregated_price(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AggregatedPrice<TimestampedPrice, DataProviderId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_aggregated_price(&at, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get aggregated price.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_price_at(
+		&self,
+		currency_id: CurrencyId,
+		block_number: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Price>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		let key = offchain_price_key(&currency_id, &block_number);
+		let price = api.get_price_at(&at, currency_id, block_number).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get price at block.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		// the prices older than the price history can be found in offchain
+		// index if the node enabled offchain indexing
+		Ok(price.or_else(|| {
+			self.offchain_storage
+				.as_ref()
+				.and_then(|storage| storage.get(STORAGE_PREFIX, &key))
+				.and_then(|encoded| Price::decode(&mut &encoded[..]).ok())
+		}))
+	}
+}
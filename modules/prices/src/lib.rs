@@ -7,29 +7,82 @@
 //!   - specify a fixed price for stable currency
 //!   - feed price in USD or related price bewteen two currencies
 //!   - lock/unlock the price data get from oracle
+//!   - sanity check the price data get from oracle, and fallback to DEX TWAP
+//!     price or last good price if the check failed
+//!   - record the recent prices in a ring buffer to query the price at
+//!     specific block, the prices are also written to offchain index for the
+//!     nodes enabled offchain indexing to keep older prices
+//!
+//! Prices module also provides `AggregatedDataProvider`, which aggregates the
+//! raw feeds of multiple oracle sources by the aggregation policy of each
+//! currency set by governance: median, weighted mean or primary source with
+//! failover. A source is only used if it has enough fresh feeds.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
-	decl_event, decl_module, decl_storage,
-	traits::{EnsureOrigin, Get},
-	weights::DispatchClass,
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get, Time},
+	weights::{DispatchClass, Weight},
+	IterableStorageDoubleMap, IterableStorageMap,
 };
 use frame_system::{self as system};
-use orml_traits::{DataFeeder, DataProvider};
+use orml_oracle::TimestampedValue;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
 use orml_utilities::with_transaction_result;
-use primitives::CurrencyId;
-use sp_runtime::traits::{CheckedDiv, CheckedMul};
-use support::{ExchangeRateProvider, Price, PriceProvider};
+use primitives::{Balance, CurrencyId, DataProviderId};
+use sp_runtime::{
+	traits::{CheckedDiv, CheckedMul, Saturating, Zero},
+	DispatchResult, FixedPointNumber, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use support::{offchain_price_key, DEXManager, ExchangeRateProvider, Price, PriceProvider, Ratio};
 
 mod mock;
 mod tests;
 
+pub type MomentOf<T> = <<T as Trait>::Time as Time>::Moment;
+pub type TimestampedPriceOf<T> = TimestampedValue<Price, MomentOf<T>>;
+pub type PriceSanityParamsOf<T> = PriceSanityParams<MomentOf<T>, <T as system::Trait>::BlockNumber>;
+pub type FeedFreshnessOf<T> = FeedFreshness<MomentOf<T>>;
+
+/// The raw price feeds of multiple oracle sources.
+pub trait PriceFeedSources<Moment> {
+	/// All the sources could be aggregated.
+	fn sources() -> Vec<DataProviderId>;
+
+	/// The currencies which have been fed by `source`.
+	fn keys(source: DataProviderId) -> Vec<CurrencyId>;
+
+	/// The raw feeds of the price of `currency_id` from `source`.
+	fn raw_feeds(source: DataProviderId, currency_id: CurrencyId) -> Vec<TimestampedValue<Price, Moment>>;
+}
+
+impl<Moment> PriceFeedSources<Moment> for () {
+	fn sources() -> Vec<DataProviderId> {
+		vec![]
+	}
+
+	fn keys(_: DataProviderId) -> Vec<CurrencyId> {
+		vec![]
+	}
+
+	fn raw_feeds(_: DataProviderId, _: CurrencyId) -> Vec<TimestampedValue<Price, Moment>> {
+		vec![]
+	}
+}
+
 pub trait Trait: system::Trait {
 	type Event: From<Event> + Into<<Self as system::Trait>::Event>;
 
 	/// The data source, such as Oracle.
-	type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
+	type Source: DataProvider<CurrencyId, Price>
+		+ DataProviderExtended<CurrencyId, TimestampedPriceOf<Self>>
+		+ DataFeeder<CurrencyId, Price, Self::AccountId>;
+
+	/// Time provider to check the staleness of price from source.
+	type Time: Time;
 
 	/// The stable currency id, it should be AUSD in Acala.
 	type GetStableCurrencyId: Get<CurrencyId>;
@@ -43,12 +96,95 @@ pub trait Trait: system::Trait {
 	/// The liquid currency id, it should be LDOT in Acala.
 	type GetLiquidCurrencyId: Get<CurrencyId>;
 
-	/// The origin which may lock and unlock prices feed to system.
+	/// The origin which may lock and unlock prices feed to system, and update
+	/// the price sanity params.
 	type LockOrigin: EnsureOrigin<Self::Origin>;
 
 	/// The provider of the exchange rate between liquid currency and staking
 	/// currency.
 	type LiquidStakingExchangeRateProvider: ExchangeRateProvider;
+
+	/// DEX to sample the fallback TWAP price.
+	type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
+
+	/// The number of blocks sampled to compute a DEX TWAP price.
+	type DexTwapPeriod: Get<u32>;
+
+	/// The oracle sources aggregated by `AggregatedDataProvider`.
+	type FeedSources: PriceFeedSources<MomentOf<Self>>;
+
+	/// The default expiry of the raw feeds of the sources whose freshness
+	/// requirement is not set.
+	type DefaultFeedExpiresIn: Get<MomentOf<Self>>;
+
+	/// The currencies whose price history is recorded.
+	type PriceHistoryCurrencyIds: Get<Vec<CurrencyId>>;
+
+	/// The maximum number of recent price changes kept in the price history
+	/// of each currency.
+	type PriceHistoryLength: Get<u32>;
+}
+
+/// Sanity check params for the price of specific currency from source.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct PriceSanityParams<Moment, BlockNumber> {
+	/// The maximum age of the price. `None` means no limit.
+	pub max_age: Option<Moment>,
+	/// The maximum deviation from the last good price. `None` means no limit.
+	pub max_deviation_per_update: Option<Ratio>,
+	/// The price rejected by `max_deviation_per_update` is accepted as the new
+	/// last good price once the source stays within `max_deviation_per_update`
+	/// of it for more than this number of blocks.
+	pub deviation_recovery_period: BlockNumber,
+	/// The maximum deviation from the reference price of the window, and the
+	/// window length in blocks. `None` means no limit.
+	pub max_deviation_per_window: Option<(Ratio, BlockNumber)>,
+	/// Fallback to DEX TWAP price if the price from source is rejected.
+	pub dex_twap_fallback: bool,
+	/// Fallback to the last good price which is not older than `max_age`
+	/// if the price from source is rejected.
+	pub last_good_price_fallback: bool,
+}
+
+/// The reason why the price from source is rejected.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum PriceRejectReason {
+	/// Source has no price
+	Unavailable,
+	/// The price is older than the max age
+	Stale,
+	/// The price deviates too much from the last good price
+	ExcessiveDeviationPerUpdate,
+	/// The price deviates too much from the reference price of the window
+	ExcessiveDeviationPerWindow,
+}
+
+/// The rule to aggregate the prices of multiple sources.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum AggregationPolicy {
+	/// The median of the prices of all available sources
+	Median,
+	/// The mean of the prices of available sources weighted by the weights
+	WeightedMean(Vec<(DataProviderId, u32)>),
+	/// The price of the primary source, failover to the secondary source if
+	/// the primary source is unavailable
+	PrimaryWithFailover(DataProviderId, DataProviderId),
+}
+
+impl Default for AggregationPolicy {
+	fn default() -> Self {
+		AggregationPolicy::Median
+	}
+}
+
+/// The freshness requirement of the raw feeds of a source.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct FeedFreshness<Moment> {
+	/// The minimum number of fresh feeds, the source is unavailable if
+	/// there are fewer. At least one feed is always required.
+	pub min_fresh_feeds: u32,
+	/// The feeds older than this are not fresh. `None` means never expire.
+	pub expires_in: Option<Moment>,
 }
 
 decl_event!(
@@ -57,24 +193,86 @@ decl_event!(
 		LockPrice(CurrencyId, Price),
 		/// Unlock price. \[currency_id\]
 		UnlockPrice(CurrencyId),
+		/// Update price sanity params. \[currency_id\]
+		PriceSanityParamsUpdated(CurrencyId),
+		/// The price from source failed the sanity check. \[currency_id, reason\]
+		PriceRejected(CurrencyId, PriceRejectReason),
+		/// Update aggregation policy. \[currency_id, policy\]
+		AggregationPolicyUpdated(CurrencyId, AggregationPolicy),
+		/// Update feed freshness requirement. \[source\]
+		FeedFreshnessUpdated(DataProviderId),
 	}
 );
 
+decl_error! {
+	/// Error for prices module.
+	pub enum Error for Module<T: Trait> {
+		/// The aggregation policy refers to unknown source or has no weight
+		InvalidAggregationPolicy,
+		/// The source is unknown
+		InvalidSource,
+	}
+}
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Prices {
 		/// Mapping from currency id to it's locked price
 		LockedPrice get(fn locked_price): map hasher(twox_64_concat) CurrencyId => Option<Price>;
+
+		/// Mapping from currency id to the sanity check params of its price
+		pub SanityParams get(fn sanity_params): map hasher(twox_64_concat) CurrencyId => Option<PriceSanityParamsOf<T>>;
+
+		/// The number of currencies whose sanity check params are set
+		pub SanityParamsCount get(fn sanity_params_count): u32;
+
+		/// Mapping from currency id to the last price passed the sanity check
+		pub LastGoodPrice get(fn last_good_price): map hasher(twox_64_concat) CurrencyId => Option<TimestampedPriceOf<T>>;
+
+		/// Mapping from currency id to the reference price of current deviation window
+		/// and the block number the window started
+		pub WindowReferencePrice get(fn window_reference_price): map hasher(twox_64_concat) CurrencyId => Option<(Price, T::BlockNumber)>;
+
+		/// Mapping from currency id to the sum of sampled DEX prices and the number of samples
+		pub DexPriceAccumulator get(fn dex_price_accumulator): map hasher(twox_64_concat) CurrencyId => (Price, u32);
+
+		/// Mapping from currency id to the price rejected by the deviation per update check
+		/// and the block number it was first rejected
+		pub DeviatingPrice get(fn deviating_price): map hasher(twox_64_concat) CurrencyId => Option<(Price, T::BlockNumber)>;
+
+		/// Mapping from currency id to the DEX TWAP price of the last completed period
+		/// and the time it was completed
+		pub DexTwapPrice get(fn dex_twap_price): map hasher(twox_64_concat) CurrencyId => Option<TimestampedPriceOf<T>>;
+
+		/// Mapping from currency id to the rule to aggregate its prices from sources
+		pub AggregationPolicies get(fn aggregation_policy): map hasher(twox_64_concat) CurrencyId => AggregationPolicy;
+
+		/// Mapping from source to the freshness requirement of its raw feeds
+		pub FeedFreshnessParams get(fn feed_freshness): map hasher(twox_64_concat) DataProviderId => FeedFreshnessOf<T> = FeedFreshness {
+			min_fresh_feeds: 1,
+			expires_in: Some(T::DefaultFeedExpiresIn::get()),
+		};
+
+		/// The ring buffer of the recent price changes of specific currency,
+		/// CurrencyId, Slot -> (BlockNumber, Price)
+		pub PriceHistory get(fn price_history): double_map hasher(twox_64_concat) CurrencyId, hasher(twox_64_concat) u32 => Option<(T::BlockNumber, Price)>;
+
+		/// Mapping from currency id to the slot of its price history to be written next
+		pub PriceHistoryHead get(fn price_history_head): map hasher(twox_64_concat) CurrencyId => u32;
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
 		fn deposit_event() = default;
 
 		const GetStableCurrencyId: CurrencyId = T::GetStableCurrencyId::get();
 		const StableCurrencyFixedPrice: Price = T::StableCurrencyFixedPrice::get();
 		const GetStakingCurrencyId: CurrencyId = T::GetStakingCurrencyId::get();
 		const GetLiquidCurrencyId: CurrencyId = T::GetLiquidCurrencyId::get();
+		const DexTwapPeriod: u32 = T::DexTwapPeriod::get();
+		const PriceHistoryLength: u32 = T::PriceHistoryLength::get();
 
 		/// Lock the price and feed it to system.
 		///
@@ -103,10 +301,393 @@ decl_module! {
 				Ok(())
 			})?;
 		}
+
+		/// Update the sanity check params of the price of specific currency.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `params`: sanity check params, `None` means do not check the
+		///   price from source, and clears the recorded last good price.
+		#[weight = (10_000, DispatchClass::Operational)]
+		fn set_sanity_params(origin, currency_id: CurrencyId, params: Option<PriceSanityParamsOf<T>>) {
+			with_transaction_result(|| {
+				T::LockOrigin::ensure_origin(origin)?;
+				let exists = <SanityParams<T>>::contains_key(currency_id);
+				if let Some(params) = params {
+					if !exists {
+						SanityParamsCount::mutate(|count| *count = count.saturating_add(1));
+					}
+					<SanityParams<T>>::insert(currency_id, params);
+				} else {
+					if exists {
+						SanityParamsCount::mutate(|count| *count = count.saturating_sub(1));
+					}
+					<SanityParams<T>>::remove(currency_id);
+					<LastGoodPrice<T>>::remove(currency_id);
+					<WindowReferencePrice<T>>::remove(currency_id);
+					<DeviatingPrice<T>>::remove(currency_id);
+					DexPriceAccumulator::remove(currency_id);
+					<DexTwapPrice<T>>::remove(currency_id);
+				}
+				Self::deposit_event(Event::PriceSanityParamsUpdated(currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Update the aggregation policy of the prices of specific currency.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `policy`: the rule to aggregate prices of sources.
+		#[weight = (10_000, DispatchClass::Operational)]
+		fn set_aggregation_policy(origin, currency_id: CurrencyId, policy: AggregationPolicy) {
+			with_transaction_result(|| {
+				T::LockOrigin::ensure_origin(origin)?;
+				let sources = T::FeedSources::sources();
+				match &policy {
+					AggregationPolicy::Median => {}
+					AggregationPolicy::WeightedMean(weights) => {
+						ensure!(
+							weights.iter().all(|(source, _)| sources.contains(source))
+								&& weights.iter().any(|(_, weight)| !weight.is_zero()),
+							Error::<T>::InvalidAggregationPolicy,
+						);
+					}
+					AggregationPolicy::PrimaryWithFailover(primary, secondary) => {
+						ensure!(
+							sources.contains(primary) && sources.contains(secondary),
+							Error::<T>::InvalidAggregationPolicy,
+						);
+					}
+				}
+				AggregationPolicies::insert(currency_id, policy.clone());
+				Self::deposit_event(Event::AggregationPolicyUpdated(currency_id, policy));
+				Ok(())
+			})?;
+		}
+
+		/// Update the freshness requirement of the raw feeds of specific source.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `source`: the oracle source.
+		/// - `freshness`: the freshness requirement.
+		#[weight = (10_000, DispatchClass::Operational)]
+		fn set_feed_freshness(origin, source: DataProviderId, freshness: FeedFreshnessOf<T>) {
+			with_transaction_result(|| {
+				T::LockOrigin::ensure_origin(origin)?;
+				ensure!(T::FeedSources::sources().contains(&source), Error::<T>::InvalidSource);
+				<FeedFreshnessParams<T>>::insert(source, freshness);
+				Self::deposit_event(Event::FeedFreshnessUpdated(source));
+				Ok(())
+			})?;
+		}
+
+		/// The weight of the sanity checks, DEX price sampling and price
+		/// history recording in `on_finalize`, which scales with the
+		/// currencies whose sanity params are set and the currencies whose
+		/// price history is recorded.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			// every checked currency reads the sanity params, locked price, source price,
+			// last good price, deviating price, window reference price, DEX liquidity pool
+			// and price accumulator, and writes the last good price, deviating price,
+			// window reference price, DEX price accumulator and TWAP price.
+			let per_checked_currency = T::DbWeight::get().reads_writes(9, 5);
+			// every recorded currency gets its price, which reads at most as much as a
+			// checked currency, and reads and writes the price history and its head.
+			let per_recorded_currency = T::DbWeight::get().reads_writes(11, 2);
+			T::DbWeight::get()
+				.reads(1)
+				.saturating_add(per_checked_currency.saturating_mul(Self::sanity_params_count() as Weight))
+				.saturating_add(
+					per_recorded_currency.saturating_mul(T::PriceHistoryCurrencyIds::get().len() as Weight),
+				)
+		}
+
+		/// Record the prices passed the sanity check and sample DEX prices
+		fn on_finalize(now: T::BlockNumber) {
+			for (currency_id, params) in <SanityParams<T>>::iter() {
+				if params.dex_twap_fallback {
+					Self::sample_dex_price(currency_id);
+				}
+
+				// locked price will not be affected by the source
+				if Self::locked_price(currency_id).is_some() {
+					continue;
+				}
+
+				match Self::checked_source_price(currency_id, &params) {
+					Ok(timestamped_price) => Self::on_good_price(currency_id, &params, timestamped_price, now),
+					Err(reason) => {
+						if reason == PriceRejectReason::ExcessiveDeviationPerUpdate {
+							Self::on_deviating_price(currency_id, &params, now);
+						}
+						Self::deposit_event(Event::PriceRejected(currency_id, reason));
+					}
+				}
+			}
+
+			for currency_id in T::PriceHistoryCurrencyIds::get() {
+				if let Some(price) = Self::get_price(currency_id) {
+					Self::record_price(currency_id, price, now);
+				}
+			}
+		}
 	}
 }
 
-impl<T: Trait> Module<T> {}
+impl<T: Trait> Module<T> {
+	/// Get the price from source and check it by the sanity params.
+	fn checked_source_price(
+		currency_id: CurrencyId,
+		params: &PriceSanityParamsOf<T>,
+	) -> sp_std::result::Result<TimestampedPriceOf<T>, PriceRejectReason> {
+		let timestamped_price = T::Source::get_no_op(&currency_id).ok_or(PriceRejectReason::Unavailable)?;
+
+		if let Some(max_age) = params.max_age {
+			if T::Time::now().saturating_sub(timestamped_price.timestamp) > max_age {
+				return Err(PriceRejectReason::Stale);
+			}
+		}
+
+		if let (Some(max_deviation), Some(last_good_price)) =
+			(params.max_deviation_per_update, Self::last_good_price(currency_id))
+		{
+			// the source recovers if it keeps within the bound of the deviating price
+			// for the recovery period
+			let recovered = Self::deviating_price(currency_id).map_or(false, |(deviating_price, since)| {
+				<system::Module<T>>::block_number() > since.saturating_add(params.deviation_recovery_period)
+					&& !Self::is_excessive_deviation(timestamped_price.value, deviating_price, max_deviation)
+			});
+			if !recovered && Self::is_excessive_deviation(timestamped_price.value, last_good_price.value, max_deviation)
+			{
+				return Err(PriceRejectReason::ExcessiveDeviationPerUpdate);
+			}
+		}
+
+		// the reference price of an expired window is not used
+		if let (Some((max_deviation, window)), Some((reference_price, start))) = (
+			params.max_deviation_per_window,
+			Self::window_reference_price(currency_id),
+		) {
+			if <system::Module<T>>::block_number() < start.saturating_add(window)
+				&& Self::is_excessive_deviation(timestamped_price.value, reference_price, max_deviation)
+			{
+				return Err(PriceRejectReason::ExcessiveDeviationPerWindow);
+			}
+		}
+
+		Ok(timestamped_price)
+	}
+
+	fn is_excessive_deviation(price: Price, reference_price: Price, max_deviation: Ratio) -> bool {
+		if reference_price.is_zero() {
+			return !price.is_zero();
+		}
+		let diff = if price > reference_price {
+			price.saturating_sub(reference_price)
+		} else {
+			reference_price.saturating_sub(price)
+		};
+		diff.checked_div(&reference_price)
+			.map_or(true, |deviation| deviation > max_deviation)
+	}
+
+	fn on_good_price(
+		currency_id: CurrencyId,
+		params: &PriceSanityParamsOf<T>,
+		timestamped_price: TimestampedPriceOf<T>,
+		now: T::BlockNumber,
+	) {
+		if let Some((_, window)) = params.max_deviation_per_window {
+			let window_expired = Self::window_reference_price(currency_id)
+				.map_or(true, |(_, start)| now >= start.saturating_add(window));
+			if window_expired {
+				<WindowReferencePrice<T>>::insert(currency_id, (timestamped_price.value, now));
+			}
+		}
+		<LastGoodPrice<T>>::insert(currency_id, timestamped_price);
+		<DeviatingPrice<T>>::remove(currency_id);
+	}
+
+	/// Record the price rejected by the deviation per update check, restart
+	/// the recovery period if it also deviates from the recorded one.
+	fn on_deviating_price(currency_id: CurrencyId, params: &PriceSanityParamsOf<T>, now: T::BlockNumber) {
+		if let (Some(timestamped_price), Some(max_deviation)) =
+			(T::Source::get_no_op(&currency_id), params.max_deviation_per_update)
+		{
+			let keep = Self::deviating_price(currency_id).map_or(false, |(deviating_price, _)| {
+				!Self::is_excessive_deviation(timestamped_price.value, deviating_price, max_deviation)
+			});
+			if !keep {
+				<DeviatingPrice<T>>::insert(currency_id, (timestamped_price.value, now));
+			}
+		}
+	}
+
+	/// Sample the DEX price of `currency_id` in USD, update the TWAP price
+	/// when a period is completed.
+	fn sample_dex_price(currency_id: CurrencyId) {
+		let (other_currency_amount, base_currency_amount) = T::DEX::get_liquidity_pool(currency_id);
+		let spot_price = Price::checked_from_rational(base_currency_amount, other_currency_amount)
+			.and_then(|n| n.checked_mul(&T::StableCurrencyFixedPrice::get()));
+
+		if let Some(spot_price) = spot_price {
+			let (sum, count) = Self::dex_price_accumulator(currency_id);
+			let (sum, count) = (sum.saturating_add(spot_price), count.saturating_add(1));
+
+			if count >= T::DexTwapPeriod::get() {
+				if let Some(twap_price) = sum.checked_div(&Price::saturating_from_integer(count)) {
+					<DexTwapPrice<T>>::insert(
+						currency_id,
+						TimestampedValue {
+							value: twap_price,
+							timestamp: T::Time::now(),
+						},
+					);
+				}
+				DexPriceAccumulator::remove(currency_id);
+			} else {
+				DexPriceAccumulator::insert(currency_id, (sum, count));
+			}
+		}
+	}
+
+	/// Combine the fresh raw feeds of `source` into the median price, `None`
+	/// if there are not enough fresh feeds.
+	fn source_feed_price(source: DataProviderId, currency_id: CurrencyId) -> Option<TimestampedPriceOf<T>> {
+		let freshness = Self::feed_freshness(source);
+		let now = T::Time::now();
+		let fresh_feeds = T::FeedSources::raw_feeds(source, currency_id)
+			.into_iter()
+			.filter(|feed| {
+				freshness
+					.expires_in
+					.map_or(true, |expires_in| now.saturating_sub(feed.timestamp) <= expires_in)
+			})
+			.collect::<Vec<_>>();
+
+		if (fresh_feeds.len() as u32) < freshness.min_fresh_feeds.max(1) {
+			return None;
+		}
+		Self::median(fresh_feeds)
+	}
+
+	fn median(mut prices: Vec<TimestampedPriceOf<T>>) -> Option<TimestampedPriceOf<T>> {
+		prices.sort_by(|a, b| a.value.cmp(&b.value));
+		let mid = prices.len() / 2;
+		prices.into_iter().nth(mid)
+	}
+
+	/// Aggregate the prices of sources by the aggregation policy of
+	/// `currency_id`, return the price and the sources it is made up of.
+	pub fn aggregated_price(currency_id: CurrencyId) -> Option<(TimestampedPriceOf<T>, Vec<DataProviderId>)> {
+		match Self::aggregation_policy(currency_id) {
+			AggregationPolicy::Median => {
+				let (sources, prices): (Vec<_>, Vec<_>) = T::FeedSources::sources()
+					.into_iter()
+					.filter_map(|source| Self::source_feed_price(source, currency_id).map(|price| (source, price)))
+					.unzip();
+				Self::median(prices).map(|price| (price, sources))
+			}
+			AggregationPolicy::WeightedMean(weights) => {
+				let mut sources = vec![];
+				let mut sum = Price::zero();
+				let mut total_weight: u32 = 0;
+				let mut timestamp: Option<MomentOf<T>> = None;
+
+				for (source, weight) in weights.into_iter().filter(|(_, weight)| !weight.is_zero()) {
+					if let Some(price) = Self::source_feed_price(source, currency_id) {
+						sum = sum.saturating_add(price.value.saturating_mul(Price::saturating_from_integer(weight)));
+						total_weight = total_weight.saturating_add(weight);
+						// the aggregated price is as old as the oldest price used
+						timestamp = Some(timestamp.map_or(price.timestamp, |t| t.min(price.timestamp)));
+						sources.push(source);
+					}
+				}
+
+				let value = sum.checked_div(&Price::saturating_from_integer(total_weight))?;
+				timestamp.map(|timestamp| (TimestampedValue { value, timestamp }, sources))
+			}
+			AggregationPolicy::PrimaryWithFailover(primary, secondary) => Self::source_feed_price(primary, currency_id)
+				.map(|price| (price, vec![primary]))
+				.or_else(|| Self::source_feed_price(secondary, currency_id).map(|price| (price, vec![secondary]))),
+		}
+	}
+
+	/// Record the price in the price history if it changed, and write it
+	/// to offchain index.
+	fn record_price(currency_id: CurrencyId, price: Price, now: T::BlockNumber) {
+		sp_io::offchain_index::set(&offchain_price_key(&currency_id, &now), &price.encode());
+
+		let length = T::PriceHistoryLength::get();
+		if length.is_zero() {
+			return;
+		}
+
+		let head = Self::price_history_head(currency_id) % length;
+		let last_slot = head.checked_sub(1).unwrap_or(length - 1);
+		let changed = Self::price_history(currency_id, last_slot).map_or(true, |(_, last_price)| last_price != price);
+		if changed {
+			<PriceHistory<T>>::insert(currency_id, head, (now, price));
+			PriceHistoryHead::insert(currency_id, (head + 1) % length);
+		}
+	}
+
+	/// Get the price of `currency_id` at `block_number` from the price
+	/// history, which is the last recorded price not after the block.
+	/// `None` if the block is older than the price history.
+	pub fn price_at(currency_id: CurrencyId, block_number: T::BlockNumber) -> Option<Price> {
+		if block_number > <system::Module<T>>::block_number() {
+			return None;
+		}
+
+		<PriceHistory<T>>::iter_prefix(currency_id)
+			.map(|(_, record)| record)
+			.filter(|(recorded_at, _)| *recorded_at <= block_number)
+			.max_by_key(|(recorded_at, _)| *recorded_at)
+			.map(|(_, price)| price)
+	}
+
+	/// Whether the recorded price is not older than the max age.
+	fn is_fresh(timestamped_price: &TimestampedPriceOf<T>, params: &PriceSanityParamsOf<T>) -> bool {
+		params.max_age.map_or(true, |max_age| {
+			T::Time::now().saturating_sub(timestamped_price.timestamp) <= max_age
+		})
+	}
+
+	/// Get the price from source. If the sanity params of the currency are
+	/// set, the price must pass the check, otherwise fallback to DEX TWAP
+	/// price or last good price as configured.
+	fn source_price(currency_id: CurrencyId) -> Option<Price> {
+		match Self::sanity_params(currency_id) {
+			None => T::Source::get(&currency_id),
+			Some(params) => Self::checked_source_price(currency_id, &params)
+				.map(|timestamped_price| timestamped_price.value)
+				.ok()
+				.or_else(|| {
+					if params.dex_twap_fallback {
+						Self::dex_twap_price(currency_id)
+							.filter(|twap_price| Self::is_fresh(twap_price, &params))
+							.map(|twap_price| twap_price.value)
+					} else {
+						None
+					}
+				})
+				.or_else(|| {
+					if params.last_good_price_fallback {
+						Self::last_good_price(currency_id)
+							.filter(|last_good_price| Self::is_fresh(last_good_price, &params))
+							.map(|last_good_price| last_good_price.value)
+					} else {
+						None
+					}
+				}),
+		}
+	}
+}
 
 impl<T: Trait> PriceProvider<CurrencyId> for Module<T> {
 	/// get related price between two currency types
@@ -131,14 +712,14 @@ impl<T: Trait> PriceProvider<CurrencyId> for Module<T> {
 			Self::get_price(T::GetStakingCurrencyId::get())
 				.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()))
 		} else {
-			// if locked price exists, return it, otherwise return latest price from oracle.
-			Self::locked_price(currency_id).or_else(|| T::Source::get(&currency_id))
+			// if locked price exists, return it, otherwise return the checked price from source.
+			Self::locked_price(currency_id).or_else(|| Self::source_price(currency_id))
 		}
 	}
 
 	fn lock_price(currency_id: CurrencyId) {
 		// lock price when get valid price from source
-		if let Some(val) = T::Source::get(&currency_id) {
+		if let Some(val) = Self::source_price(currency_id) {
 			LockedPrice::insert(currency_id, val);
 			<Module<T>>::deposit_event(Event::LockPrice(currency_id, val));
 		}
@@ -149,3 +730,38 @@ impl<T: Trait> PriceProvider<CurrencyId> for Module<T> {
 		<Module<T>>::deposit_event(Event::UnlockPrice(currency_id));
 	}
 }
+
+/// Data provider which aggregates the prices of `T::FeedSources` by the
+/// aggregation policy of each currency.
+pub struct AggregatedDataProvider<T>(PhantomData<T>);
+
+impl<T: Trait> DataProvider<CurrencyId, Price> for AggregatedDataProvider<T> {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		<Module<T>>::aggregated_price(*currency_id).map(|(price, _)| price.value)
+	}
+}
+
+impl<T: Trait> DataProviderExtended<CurrencyId, TimestampedPriceOf<T>> for AggregatedDataProvider<T> {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedPriceOf<T>> {
+		<Module<T>>::aggregated_price(*currency_id).map(|(price, _)| price)
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedPriceOf<T>>)> {
+		let mut keys = T::FeedSources::sources()
+			.into_iter()
+			.flat_map(T::FeedSources::keys)
+			.collect::<Vec<_>>();
+		keys.sort();
+		keys.dedup();
+		keys.into_iter()
+			.map(|currency_id| (currency_id, Self::get_no_op(&currency_id)))
+			.collect()
+	}
+}
+
+// Aggregated data provider cannot feed.
+impl<T: Trait> DataFeeder<CurrencyId, Price, T::AccountId> for AggregatedDataProvider<T> {
+	fn feed_value(_: T::AccountId, _: CurrencyId, _: Price) -> DispatchResult {
+		Err("Not supported".into())
+	}
+}
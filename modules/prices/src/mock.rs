@@ -5,13 +5,14 @@
 use super::*;
 use frame_support::{impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
-use orml_traits::DataFeeder;
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, FixedPointNumber, Perbill};
+use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use sp_std::cell::RefCell;
 use support::ExchangeRate;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
+pub type Moment = u64;
 
 pub const ACA: CurrencyId = CurrencyId::ACA;
 pub const AUSD: CurrencyId = CurrencyId::AUSD;
@@ -73,9 +74,46 @@ impl frame_system::Trait for Runtime {
 }
 pub type System = frame_system::Module<Runtime>;
 
+thread_local! {
+	static FEED_PRICES: RefCell<Vec<(CurrencyId, Option<Price>, Moment)>> = RefCell::new(vec![]);
+	static NOW: RefCell<Moment> = RefCell::new(0);
+	static DEX_LIQUIDITY_POOL: RefCell<(Balance, Balance)> = RefCell::new((0, 0));
+	static RAW_FEEDS: RefCell<Vec<(DataProviderId, CurrencyId, Price, Moment)>> = RefCell::new(vec![]);
+}
+
+pub fn mock_feed_price(currency_id: CurrencyId, price: Option<Price>, timestamp: Moment) {
+	FEED_PRICES.with(|v| v.borrow_mut().push((currency_id, price, timestamp)))
+}
+
+pub fn set_now(now: Moment) {
+	NOW.with(|v| *v.borrow_mut() = now)
+}
+
+pub fn set_dex_liquidity_pool(pool: (Balance, Balance)) {
+	DEX_LIQUIDITY_POOL.with(|v| *v.borrow_mut() = pool)
+}
+
+pub fn mock_raw_feed(source: DataProviderId, currency_id: CurrencyId, price: Price, timestamp: Moment) {
+	RAW_FEEDS.with(|v| v.borrow_mut().push((source, currency_id, price, timestamp)))
+}
+
 pub struct MockDataProvider;
 impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		<Self as DataProviderExtended<CurrencyId, TimestampedPriceOf<Runtime>>>::get_no_op(currency_id)
+			.map(|timestamped_price| timestamped_price.value)
+	}
+}
+
+impl DataProviderExtended<CurrencyId, TimestampedPriceOf<Runtime>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedPriceOf<Runtime>> {
+		// the latest fed price overrides the default one
+		if let Some((_, price, timestamp)) =
+			FEED_PRICES.with(|v| v.borrow().iter().rev().find(|(id, _, _)| id == currency_id).cloned())
+		{
+			return price.map(|value| TimestampedValue { value, timestamp });
+		}
+
 		match currency_id {
 			&AUSD => Some(Price::saturating_from_rational(99, 100)),
 			&BTC => Some(Price::saturating_from_integer(5000)),
@@ -83,6 +121,11 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 			&ACA => Some(Price::zero()),
 			_ => None,
 		}
+		.map(|value| TimestampedValue { value, timestamp: 0 })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedPriceOf<Runtime>>)> {
+		vec![]
 	}
 }
 
@@ -92,6 +135,82 @@ impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	}
 }
 
+pub struct MockFeedSources;
+impl PriceFeedSources<Moment> for MockFeedSources {
+	fn sources() -> Vec<DataProviderId> {
+		vec![DataProviderId::Acala, DataProviderId::Band]
+	}
+
+	fn keys(source: DataProviderId) -> Vec<CurrencyId> {
+		RAW_FEEDS.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|(s, _, _, _)| *s == source)
+				.map(|(_, currency_id, _, _)| *currency_id)
+				.collect()
+		})
+	}
+
+	fn raw_feeds(source: DataProviderId, currency_id: CurrencyId) -> Vec<TimestampedPriceOf<Runtime>> {
+		RAW_FEEDS.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|(s, id, _, _)| *s == source && *id == currency_id)
+				.map(|(_, _, value, timestamp)| TimestampedValue {
+					value: *value,
+					timestamp: *timestamp,
+				})
+				.collect()
+		})
+	}
+}
+
+pub struct MockTime;
+impl Time for MockTime {
+	type Moment = Moment;
+
+	fn now() -> Self::Moment {
+		NOW.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockDEX;
+impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
+	fn get_target_amount(_: CurrencyId, _: CurrencyId, _: Balance) -> Balance {
+		unimplemented!()
+	}
+
+	fn get_supply_amount(_: CurrencyId, _: CurrencyId, _: Balance) -> Balance {
+		unimplemented!()
+	}
+
+	fn exchange_currency(
+		_: AccountId,
+		_: CurrencyId,
+		_: Balance,
+		_: CurrencyId,
+		_: Balance,
+	) -> sp_std::result::Result<Balance, sp_runtime::DispatchError> {
+		unimplemented!()
+	}
+
+	fn get_exchange_slippage(_: CurrencyId, _: CurrencyId, _: Balance) -> Option<Ratio> {
+		unimplemented!()
+	}
+
+	fn get_liquidity_pool(_: CurrencyId) -> (Balance, Balance) {
+		DEX_LIQUIDITY_POOL.with(|v| *v.borrow())
+	}
+
+	fn get_shares(_: CurrencyId, _: &AccountId) -> Balance {
+		unimplemented!()
+	}
+
+	fn withdraw_liquidity(_: AccountId, _: CurrencyId, _: Balance) -> sp_runtime::DispatchResult {
+		unimplemented!()
+	}
+}
+
 pub struct MockLiquidStakingExchangeProvider;
 impl ExchangeRateProvider for MockLiquidStakingExchangeProvider {
 	fn get_exchange_rate() -> ExchangeRate {
@@ -108,19 +227,31 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 	pub StableCurrencyFixedPrice: Price = Price::one();
+	pub const DexTwapPeriod: u32 = 3;
+	pub const DefaultFeedExpiresIn: Moment = 100;
+	pub PriceHistoryCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
+	pub const PriceHistoryLength: u32 = 3;
 }
 
 impl Trait for Runtime {
 	type Event = TestEvent;
 	type Source = MockDataProvider;
+	type Time = MockTime;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
+	type DEX = MockDEX;
+	type DexTwapPeriod = DexTwapPeriod;
+	type FeedSources = MockFeedSources;
+	type DefaultFeedExpiresIn = DefaultFeedExpiresIn;
+	type PriceHistoryCurrencyIds = PriceHistoryCurrencyIds;
+	type PriceHistoryLength = PriceHistoryLength;
 }
 pub type PricesModule = Module<Runtime>;
+pub type AggregatedPrices = AggregatedDataProvider<Runtime>;
 
 pub struct ExtBuilder;
 
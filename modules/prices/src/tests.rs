@@ -3,8 +3,11 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{ExtBuilder, Origin, PricesModule, System, TestEvent, ACA, AUSD, BTC, DOT, LDOT};
+use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use mock::{
+	mock_feed_price, mock_raw_feed, set_dex_liquidity_pool, set_now, AggregatedPrices, ExtBuilder, Origin,
+	PricesModule, System, TestEvent, ACA, AUSD, BTC, DOT, LDOT,
+};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
 #[test]
@@ -92,3 +95,613 @@ fn unlock_price_call_work() {
 		assert_eq!(PricesModule::locked_price(BTC), None);
 	});
 }
+
+#[test]
+fn set_sanity_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = PriceSanityParams {
+			max_age: Some(10),
+			..Default::default()
+		};
+		assert_noop!(
+			PricesModule::set_sanity_params(Origin::signed(5), BTC, Some(params.clone())),
+			BadOrigin,
+		);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(params.clone())
+		));
+
+		let sanity_params_updated_event = TestEvent::prices(Event::PriceSanityParamsUpdated(BTC));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == sanity_params_updated_event));
+		assert_eq!(PricesModule::sanity_params(BTC), Some(params));
+
+		PricesModule::on_finalize(1);
+		assert_eq!(
+			PricesModule::last_good_price(BTC).map(|p| p.value),
+			Some(Price::saturating_from_integer(5000))
+		);
+		assert_ok!(PricesModule::set_sanity_params(Origin::signed(1), BTC, None));
+		assert_eq!(PricesModule::sanity_params(BTC), None);
+		assert_eq!(PricesModule::last_good_price(BTC), None);
+	});
+}
+
+#[test]
+fn sanity_params_count_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let params = PriceSanityParams {
+			max_age: Some(10),
+			..Default::default()
+		};
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(params.clone())
+		));
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(params.clone())
+		));
+		assert_eq!(PricesModule::sanity_params_count(), 1);
+		assert_ok!(PricesModule::set_sanity_params(Origin::signed(1), DOT, Some(params)));
+		assert_eq!(PricesModule::sanity_params_count(), 2);
+
+		assert_ok!(PricesModule::set_sanity_params(Origin::signed(1), BTC, None));
+		assert_ok!(PricesModule::set_sanity_params(Origin::signed(1), BTC, None));
+		assert_eq!(PricesModule::sanity_params_count(), 1);
+		assert_ok!(PricesModule::set_sanity_params(Origin::signed(1), ACA, None));
+		assert_eq!(PricesModule::sanity_params_count(), 1);
+	});
+}
+
+#[test]
+fn reject_stale_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		set_now(100);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_age: Some(10),
+				..Default::default()
+			})
+		));
+		assert_eq!(PricesModule::get_price(BTC), None);
+		assert_eq!(PricesModule::get_relative_price(BTC, AUSD), None);
+
+		PricesModule::on_finalize(1);
+		let price_rejected_event = TestEvent::prices(Event::PriceRejected(BTC, PriceRejectReason::Stale));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == price_rejected_event));
+
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(5100)), 95);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5100)));
+	});
+}
+
+#[test]
+fn reject_excessive_deviation_per_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_deviation_per_update: Some(Ratio::saturating_from_rational(1, 10)),
+				..Default::default()
+			})
+		));
+		PricesModule::on_finalize(1);
+
+		System::set_block_number(2);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(6000)), 0);
+		assert_eq!(PricesModule::get_price(BTC), None);
+		PricesModule::on_finalize(2);
+		let price_rejected_event = TestEvent::prices(Event::PriceRejected(
+			BTC,
+			PriceRejectReason::ExcessiveDeviationPerUpdate,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == price_rejected_event));
+		assert_eq!(
+			PricesModule::last_good_price(BTC).map(|p| p.value),
+			Some(Price::saturating_from_integer(5000))
+		);
+
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(4600)), 0);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4600)));
+	});
+}
+
+#[test]
+fn recover_from_excessive_deviation_per_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_deviation_per_update: Some(Ratio::saturating_from_rational(1, 10)),
+				deviation_recovery_period: 2,
+				..Default::default()
+			})
+		));
+		PricesModule::on_finalize(1);
+
+		System::set_block_number(2);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(6000)), 0);
+		PricesModule::on_finalize(2);
+		assert_eq!(
+			PricesModule::deviating_price(BTC),
+			Some((Price::saturating_from_integer(6000), 2))
+		);
+
+		// still within the recovery period
+		System::set_block_number(4);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(6100)), 0);
+		assert_eq!(PricesModule::get_price(BTC), None);
+		PricesModule::on_finalize(4);
+
+		// deviating from the deviating price restarts the recovery period
+		System::set_block_number(5);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(7000)), 0);
+		PricesModule::on_finalize(5);
+		assert_eq!(
+			PricesModule::deviating_price(BTC),
+			Some((Price::saturating_from_integer(7000), 5))
+		);
+
+		System::set_block_number(8);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(6900)), 0);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(6900)));
+		PricesModule::on_finalize(8);
+		assert_eq!(
+			PricesModule::last_good_price(BTC).map(|p| p.value),
+			Some(Price::saturating_from_integer(6900))
+		);
+		assert_eq!(PricesModule::deviating_price(BTC), None);
+	});
+}
+
+#[test]
+fn reject_excessive_deviation_per_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_deviation_per_window: Some((Ratio::saturating_from_rational(2, 10), 10)),
+				..Default::default()
+			})
+		));
+		PricesModule::on_finalize(1);
+		assert_eq!(
+			PricesModule::window_reference_price(BTC),
+			Some((Price::saturating_from_integer(5000), 1))
+		);
+
+		System::set_block_number(2);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(5900)), 0);
+		PricesModule::on_finalize(2);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(6500)), 0);
+		assert_eq!(PricesModule::get_price(BTC), None);
+
+		// the window expired, the reference price is refreshed
+		System::set_block_number(11);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(6500)));
+		PricesModule::on_finalize(11);
+		assert_eq!(
+			PricesModule::window_reference_price(BTC),
+			Some((Price::saturating_from_integer(6500), 11))
+		);
+	});
+}
+
+#[test]
+fn fallback_to_dex_twap_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				dex_twap_fallback: true,
+				..Default::default()
+			})
+		));
+		set_dex_liquidity_pool((100, 500_000));
+		PricesModule::on_finalize(1);
+		set_dex_liquidity_pool((100, 600_000));
+		PricesModule::on_finalize(2);
+		assert_eq!(PricesModule::dex_twap_price(BTC), None);
+		set_dex_liquidity_pool((100, 700_000));
+		PricesModule::on_finalize(3);
+		assert_eq!(
+			PricesModule::dex_twap_price(BTC).map(|p| p.value),
+			Some(Price::saturating_from_integer(6000))
+		);
+		assert_eq!(PricesModule::dex_price_accumulator(BTC), (Price::zero(), 0));
+
+		mock_feed_price(BTC, None, 0);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(6000)));
+	});
+}
+
+#[test]
+fn reject_stale_dex_twap_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		set_now(100);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(5000)), 100);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_age: Some(10),
+				dex_twap_fallback: true,
+				..Default::default()
+			})
+		));
+		set_dex_liquidity_pool((100, 500_000));
+		PricesModule::on_finalize(1);
+		PricesModule::on_finalize(2);
+		PricesModule::on_finalize(3);
+		assert_eq!(
+			PricesModule::dex_twap_price(BTC),
+			Some(TimestampedValue {
+				value: Price::saturating_from_integer(5000),
+				timestamp: 100,
+			})
+		);
+
+		mock_feed_price(BTC, None, 0);
+		set_now(110);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the DEX TWAP price is too old
+		set_now(111);
+		assert_eq!(PricesModule::get_price(BTC), None);
+	});
+}
+
+#[test]
+fn fallback_to_last_good_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		set_now(100);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(5000)), 100);
+		assert_ok!(PricesModule::set_sanity_params(
+			Origin::signed(1),
+			BTC,
+			Some(PriceSanityParams {
+				max_age: Some(10),
+				last_good_price_fallback: true,
+				..Default::default()
+			})
+		));
+		PricesModule::on_finalize(1);
+
+		mock_feed_price(BTC, None, 0);
+		set_now(105);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the last good price is too old
+		set_now(111);
+		assert_eq!(PricesModule::get_price(BTC), None);
+	});
+}
+
+#[test]
+fn set_aggregation_policy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let policy = AggregationPolicy::WeightedMean(vec![(DataProviderId::Acala, 3), (DataProviderId::Band, 1)]);
+		assert_noop!(
+			PricesModule::set_aggregation_policy(Origin::signed(5), BTC, policy.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_aggregation_policy(
+				Origin::signed(1),
+				BTC,
+				AggregationPolicy::WeightedMean(vec![(DataProviderId::Acala, 0)])
+			),
+			Error::<Runtime>::InvalidAggregationPolicy
+		);
+		assert_noop!(
+			PricesModule::set_aggregation_policy(
+				Origin::signed(1),
+				BTC,
+				AggregationPolicy::PrimaryWithFailover(DataProviderId::Aggregated, DataProviderId::Band)
+			),
+			Error::<Runtime>::InvalidAggregationPolicy
+		);
+
+		assert_eq!(PricesModule::aggregation_policy(BTC), AggregationPolicy::Median);
+		assert_ok!(PricesModule::set_aggregation_policy(
+			Origin::signed(1),
+			BTC,
+			policy.clone()
+		));
+		assert_eq!(PricesModule::aggregation_policy(BTC), policy.clone());
+
+		let update_policy_event = TestEvent::prices(Event::AggregationPolicyUpdated(BTC, policy));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_policy_event));
+	});
+}
+
+#[test]
+fn set_feed_freshness_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let freshness = FeedFreshness {
+			min_fresh_feeds: 2,
+			expires_in: Some(10),
+		};
+		assert_noop!(
+			PricesModule::set_feed_freshness(Origin::signed(5), DataProviderId::Acala, freshness.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_feed_freshness(Origin::signed(1), DataProviderId::Aggregated, freshness.clone()),
+			Error::<Runtime>::InvalidSource
+		);
+
+		assert_eq!(
+			PricesModule::feed_freshness(DataProviderId::Acala),
+			FeedFreshness {
+				min_fresh_feeds: 1,
+				expires_in: Some(100),
+			}
+		);
+		assert_ok!(PricesModule::set_feed_freshness(
+			Origin::signed(1),
+			DataProviderId::Acala,
+			freshness.clone()
+		));
+		assert_eq!(PricesModule::feed_freshness(DataProviderId::Acala), freshness);
+
+		let update_freshness_event = TestEvent::prices(Event::FeedFreshnessUpdated(DataProviderId::Acala));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_freshness_event));
+	});
+}
+
+#[test]
+fn aggregate_median_price_of_fresh_sources() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_now(200);
+		assert_eq!(PricesModule::aggregated_price(BTC), None);
+
+		mock_raw_feed(DataProviderId::Acala, BTC, Price::saturating_from_integer(5000), 150);
+		mock_raw_feed(DataProviderId::Acala, BTC, Price::saturating_from_integer(5200), 160);
+		mock_raw_feed(DataProviderId::Acala, BTC, Price::saturating_from_integer(5100), 170);
+		// expired feed of band
+		mock_raw_feed(DataProviderId::Band, BTC, Price::saturating_from_integer(6000), 50);
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(5100),
+					timestamp: 170
+				},
+				vec![DataProviderId::Acala]
+			))
+		);
+
+		// acala does not have enough fresh feeds
+		assert_ok!(PricesModule::set_feed_freshness(
+			Origin::signed(1),
+			DataProviderId::Acala,
+			FeedFreshness {
+				min_fresh_feeds: 4,
+				expires_in: None,
+			}
+		));
+		assert_eq!(PricesModule::aggregated_price(BTC), None);
+
+		assert_ok!(PricesModule::set_feed_freshness(
+			Origin::signed(1),
+			DataProviderId::Acala,
+			FeedFreshness {
+				min_fresh_feeds: 1,
+				expires_in: None,
+			}
+		));
+		mock_raw_feed(DataProviderId::Band, BTC, Price::saturating_from_integer(5300), 180);
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(5300),
+					timestamp: 180
+				},
+				vec![DataProviderId::Acala, DataProviderId::Band]
+			))
+		);
+		assert_eq!(AggregatedPrices::get(&BTC), Some(Price::saturating_from_integer(5300)));
+		assert_eq!(
+			AggregatedPrices::get_all_values(),
+			vec![(
+				BTC,
+				Some(TimestampedValue {
+					value: Price::saturating_from_integer(5300),
+					timestamp: 180
+				})
+			)]
+		);
+	});
+}
+
+#[test]
+fn aggregate_weighted_mean_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_now(200);
+		mock_raw_feed(DataProviderId::Acala, BTC, Price::saturating_from_integer(5000), 150);
+		mock_raw_feed(DataProviderId::Band, BTC, Price::saturating_from_integer(6000), 160);
+		assert_ok!(PricesModule::set_aggregation_policy(
+			Origin::signed(1),
+			BTC,
+			AggregationPolicy::WeightedMean(vec![(DataProviderId::Acala, 3), (DataProviderId::Band, 1)])
+		));
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(5250),
+					timestamp: 150
+				},
+				vec![DataProviderId::Acala, DataProviderId::Band]
+			))
+		);
+
+		// the unavailable source is excluded
+		set_now(255);
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(6000),
+					timestamp: 160
+				},
+				vec![DataProviderId::Band]
+			))
+		);
+	});
+}
+
+#[test]
+fn aggregate_primary_with_failover_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_now(200);
+		mock_raw_feed(DataProviderId::Acala, BTC, Price::saturating_from_integer(5000), 150);
+		mock_raw_feed(DataProviderId::Band, BTC, Price::saturating_from_integer(6000), 160);
+		assert_ok!(PricesModule::set_aggregation_policy(
+			Origin::signed(1),
+			BTC,
+			AggregationPolicy::PrimaryWithFailover(DataProviderId::Band, DataProviderId::Acala)
+		));
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(6000),
+					timestamp: 160
+				},
+				vec![DataProviderId::Band]
+			))
+		);
+
+		assert_ok!(PricesModule::set_feed_freshness(
+			Origin::signed(1),
+			DataProviderId::Band,
+			FeedFreshness {
+				min_fresh_feeds: 2,
+				expires_in: None,
+			}
+		));
+		assert_eq!(
+			PricesModule::aggregated_price(BTC),
+			Some((
+				TimestampedValue {
+					value: Price::saturating_from_integer(5000),
+					timestamp: 150
+				},
+				vec![DataProviderId::Acala]
+			))
+		);
+		assert!(<AggregatedPrices as DataFeeder<_, _, _>>::feed_value(1, BTC, Price::one()).is_err());
+	});
+}
+
+#[test]
+fn record_price_history_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		PricesModule::on_finalize(1);
+		assert_eq!(
+			PricesModule::price_history(BTC, 0),
+			Some((1, Price::saturating_from_integer(5000)))
+		);
+		assert_eq!(
+			PricesModule::price_history(DOT, 0),
+			Some((1, Price::saturating_from_integer(100)))
+		);
+		assert_eq!(PricesModule::price_history_head(BTC), 1);
+
+		// unchanged price is not recorded
+		System::set_block_number(2);
+		PricesModule::on_finalize(2);
+		assert_eq!(PricesModule::price_history(BTC, 1), None);
+		assert_eq!(PricesModule::price_history_head(BTC), 1);
+
+		for (block_number, price) in vec![(3, 5100), (4, 5200), (5, 5300)] {
+			System::set_block_number(block_number);
+			mock_feed_price(BTC, Some(Price::saturating_from_integer(price)), 0);
+			PricesModule::on_finalize(block_number);
+		}
+
+		// the oldest price is overwritten
+		assert_eq!(
+			PricesModule::price_history(BTC, 0),
+			Some((5, Price::saturating_from_integer(5300)))
+		);
+		assert_eq!(PricesModule::price_history_head(BTC), 1);
+		assert_eq!(PricesModule::price_history_head(DOT), 1);
+	});
+}
+
+#[test]
+fn price_at_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for (block_number, price) in vec![(1, 5000), (3, 5100), (6, 5200)] {
+			System::set_block_number(block_number);
+			mock_feed_price(BTC, Some(Price::saturating_from_integer(price)), 0);
+			PricesModule::on_finalize(block_number);
+		}
+		System::set_block_number(7);
+
+		assert_eq!(PricesModule::price_at(BTC, 0), None);
+		assert_eq!(
+			PricesModule::price_at(BTC, 1),
+			Some(Price::saturating_from_integer(5000))
+		);
+		assert_eq!(
+			PricesModule::price_at(BTC, 2),
+			Some(Price::saturating_from_integer(5000))
+		);
+		assert_eq!(
+			PricesModule::price_at(BTC, 5),
+			Some(Price::saturating_from_integer(5100))
+		);
+		assert_eq!(
+			PricesModule::price_at(BTC, 7),
+			Some(Price::saturating_from_integer(5200))
+		);
+		assert_eq!(PricesModule::price_at(BTC, 8), None);
+
+		// the block is older than the price history
+		System::set_block_number(8);
+		mock_feed_price(BTC, Some(Price::saturating_from_integer(5300)), 0);
+		PricesModule::on_finalize(8);
+		assert_eq!(PricesModule::price_at(BTC, 2), None);
+		assert_eq!(
+			PricesModule::price_at(BTC, 3),
+			Some(Price::saturating_from_integer(5100))
+		);
+	});
+}
@@ -22,6 +22,55 @@ pub struct BalanceInfo<Balance> {
 	pub amount: Balance,
 }
 
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RedeemRequestStatus<Balance> {
+	pub request_id: support::RedeemRequestId,
+	/// The position in redeem queue, starts from 0.
+	pub position: u32,
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub remaining: Balance,
+	/// The estimated era at which the request is fully filled, `None` if it
+	/// can't be estimated.
+	pub expected_era: Option<u32>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct UnlockChunk<Balance> {
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub value: Balance,
+	pub era: u32,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SubAccountLedger<Balance> {
+	pub account_index: support::SubAccountIndex,
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total: Balance,
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub active: Balance,
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	pub unlocking: Vec<UnlockChunk<Balance>>,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -35,6 +84,8 @@ fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(dese
 }
 
 sp_api::decl_runtime_apis! {
+	/// Version 2 adds `get_redeem_requests` and `get_sub_account_ledgers`.
+	#[api_version(2)]
 	pub trait StakingPoolApi<AccountId, Balance> where
 		AccountId: Codec,
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
@@ -44,5 +95,11 @@ sp_api::decl_runtime_apis! {
 		) -> BalanceInfo<Balance>;
 
 		fn get_liquid_staking_exchange_rate() -> support::ExchangeRate;
+
+		fn get_redeem_requests(
+			account: AccountId
+		) -> Vec<RedeemRequestStatus<Balance>>;
+
+		fn get_sub_account_ledgers() -> Vec<SubAccountLedger<Balance>>;
 	}
 }
@@ -3,7 +3,7 @@
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use module_staking_pool_rpc_runtime_api::BalanceInfo;
+use module_staking_pool_rpc_runtime_api::{BalanceInfo, RedeemRequestStatus, SubAccountLedger};
 use module_support::ExchangeRate;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -17,12 +17,19 @@ pub use self::gen_client::Client as StakingPoolClient;
 pub use module_staking_pool_rpc_runtime_api::StakingPoolApi as StakingPoolRuntimeApi;
 
 #[rpc]
-pub trait StakingPoolApi<BlockHash, AccountId, ResponseType> {
+pub trait StakingPoolApi<BlockHash, AccountId, ResponseType, RedeemRequestsResponseType, SubAccountLedgersResponseType>
+{
 	#[rpc(name = "stakingPool_getAvailableUnbonded")]
 	fn get_available_unbonded(&self, account: AccountId, at: Option<BlockHash>) -> Result<ResponseType>;
 
 	#[rpc(name = "stakingPool_getLiquidStakingExchangeRate")]
 	fn get_liquid_staking_exchange_rate(&self, at: Option<BlockHash>) -> Result<ExchangeRate>;
+
+	#[rpc(name = "stakingPool_getRedeemRequests")]
+	fn get_redeem_requests(&self, account: AccountId, at: Option<BlockHash>) -> Result<RedeemRequestsResponseType>;
+
+	#[rpc(name = "stakingPool_getSubAccountLedgers")]
+	fn get_sub_account_ledgers(&self, at: Option<BlockHash>) -> Result<SubAccountLedgersResponseType>;
 }
 
 /// A struct that implements the [`StakingPoolApi`].
@@ -53,8 +60,14 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block, AccountId, Balance> StakingPoolApi<<Block as BlockT>::Hash, AccountId, BalanceInfo<Balance>>
-	for StakingPool<C, Block>
+impl<C, Block, AccountId, Balance>
+	StakingPoolApi<
+		<Block as BlockT>::Hash,
+		AccountId,
+		BalanceInfo<Balance>,
+		Vec<RedeemRequestStatus<Balance>>,
+		Vec<SubAccountLedger<Balance>>,
+	> for StakingPool<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
@@ -93,4 +106,36 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_redeem_requests(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<RedeemRequestStatus<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_redeem_requests(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get redeem requests.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_sub_account_ledgers(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<SubAccountLedger<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_sub_account_ledgers(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get sub-account ledgers.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
@@ -1,17 +1,25 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, IterableStorageDoubleMap};
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::Get,
+	weights::{constants::WEIGHT_PER_NANOS, Weight},
+	IterableStorageDoubleMap, IterableStorageMap,
+};
 use frame_system::{self as system};
 use orml_traits::MultiCurrency;
+use orml_utilities::with_transaction_result;
 use primitives::{Balance, CurrencyId, EraIndex};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating, Zero},
-	DispatchError, DispatchResult, FixedPointNumber, ModuleId,
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, Zero},
+	DispatchError, DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
 };
 use sp_std::prelude::*;
 use support::{
-	ExchangeRate, HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall,
-	PolkadotBridgeState, PolkadotBridgeType, Rate, Ratio,
+	ExchangeRate, HomaProtocol, NomineesProvider, OnCommission, OnNewEra, OnPolkadotBridgeResult, PolkadotBridge,
+	PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger, Rate, Ratio, RedeemRequestId,
+	SubAccountIndex,
 };
 
 mod mock;
@@ -20,6 +28,19 @@ mod tests;
 type PolkadotAccountIdOf<T> =
 	<<T as Trait>::Bridge as PolkadotBridgeType<<T as system::Trait>::BlockNumber, EraIndex>>::PolkadotAccountId;
 
+/// A request to redeem liquid currency waiting in the redeem queue.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RedeemRequest<AccountId> {
+	/// The owner of this request.
+	pub who: AccountId,
+	/// The liquid currency remaining to be redeemed.
+	pub remaining: Balance,
+	/// The max fee rate the owner accepts.
+	pub max_fee_rate: Rate,
+	/// The era at which this request is made.
+	pub era: EraIndex,
+}
+
 pub trait Trait: system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 	type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
@@ -38,7 +59,24 @@ pub trait Trait: system::Trait {
 	/// protocol.
 	type ModuleId: Get<ModuleId>;
 
-	// TODO: add RewardFeeRatio
+	/// The ratio of staking rewards taken as commission to Homa treasury.
+	type RewardFeeRatio: Get<Ratio>;
+
+	/// The max number of requests in the redeem queue.
+	type MaxRedeemRequests: Get<u32>;
+
+	/// The max number of requests in the redeem queue visited by one mint.
+	type MaxRedeemRequestsPerMint: Get<u32>;
+
+	/// The max number of requests of an account in the redeem queue.
+	type MaxRedeemRequestsPerAccount: Get<u32>;
+
+	/// The minimum liquid amount of a redeem request.
+	type MinRedeemRequestAmount: Get<Balance>;
+
+	/// The ratio of the returned redeem fee rebated to the minter who fills
+	/// redeem requests.
+	type MintRebateRatio: Get<Ratio>;
 }
 
 decl_event!(
@@ -55,6 +93,18 @@ decl_event!(
 		RedeemByFreeUnbonded(AccountId, Balance, Balance, Balance),
 		/// \[who, target_era, fee, redeem_amount, unbond_amount\]
 		RedeemByClaimUnbonding(AccountId, EraIndex, Balance, Balance, Balance),
+		/// \[era, slash_amount\]
+		Slashed(EraIndex, Balance),
+		/// \[era, reward_amount, commission_in_liquid\]
+		RewardReceived(EraIndex, Balance, Balance),
+		/// \[who, request_id, liquid_amount, max_fee_rate\]
+		RedeemRequested(AccountId, RedeemRequestId, Balance, Rate),
+		/// \[who, request_id, fee_in_liquid, liquid_amount_redeemed, staking_amount_retrived\]
+		RedeemRequestFilled(AccountId, RedeemRequestId, Balance, Balance, Balance),
+		/// \[minter, request_id, staking_amount, rebate_in_liquid\]
+		MintFilledRedeemRequest(AccountId, RedeemRequestId, Balance, Balance),
+		/// \[who, request_id, liquid_amount_returned\]
+		RedeemRequestCancelled(AccountId, RedeemRequestId, Balance),
 	}
 );
 
@@ -64,6 +114,18 @@ decl_error! {
 		LiquidCurrencyNotEnough,
 		InvalidEra,
 		Overflow,
+		/// The redeem queue is full
+		RedeemQueueFull,
+		/// The redeem request does not exist or is not owned by the caller
+		RedeemRequestNotFound,
+		/// The active bonded of sub-accounts at bridge is not enough to unbond
+		BridgeActiveNotEnough,
+		/// The amount of redeem request is below the minimum
+		RedeemRequestAmountTooLow,
+		/// The account has too many redeem requests in the queue
+		TooManyRedeemRequests,
+		/// The unbonded has not been received from bridge yet
+		UnbondedNotReceived,
 	}
 }
 
@@ -75,11 +137,35 @@ decl_storage! {
 		pub Unbonding get(fn unbonding): map hasher(twox_64_concat) EraIndex => (Balance, Balance); // (value, claimed), value - claimed = unbond to free
 
 		pub ClaimedUnbond get(fn claimed_unbond): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) EraIndex => Balance;
+		/// The remaining ratio of claimed unbond at the era after slashes,
+		/// the actual claimed unbond of an account is `ClaimedUnbond * ClaimedUnbondRatio`.
+		pub ClaimedUnbondRatio get(fn claimed_unbond_ratio): map hasher(twox_64_concat) EraIndex => Option<Ratio>;
 		pub TotalClaimedUnbonded get(fn total_claimed_unbonded): Balance;
 
 		pub TotalBonded get(fn total_bonded): Balance;
 		pub UnbondingToFree get(fn unbonding_to_free): Balance;
 		pub FreeUnbonded get(fn free_unbonded): Balance;
+
+		/// The balance of bridge recorded at the end of last rebalance, the
+		/// shortfall of bridge balance to it at the next era is slashed.
+		pub BridgeBalanceRecord get(fn bridge_balance_record): Balance;
+
+		/// The unbonded and reward at bridge which are assigned to (claimed, free) pool but not
+		/// received yet, they are credited to the pools once the receipts are confirmed.
+		pub UnbondedAtBridge get(fn unbonded_at_bridge): (Balance, Balance);
+		/// The amount being received from bridge and waiting for confirmation.
+		pub ReceivingFromBridge get(fn receiving_from_bridge): Balance;
+
+		/// The redeem requests, their liquid currency is kept by staking pool until filled or cancelled.
+		pub RedeemRequests get(fn redeem_requests): map hasher(twox_64_concat) RedeemRequestId => Option<RedeemRequest<T::AccountId>>;
+		/// The ids of redeem requests in FIFO order.
+		pub RedeemQueue get(fn redeem_queue): Vec<RedeemRequestId>;
+		/// The number of redeem requests of an account in the queue.
+		pub RedeemRequestsCount get(fn redeem_requests_count): map hasher(twox_64_concat) T::AccountId => u32;
+		pub NextRedeemRequestId get(fn next_redeem_request_id): RedeemRequestId;
+
+		/// The unbonding netted by bond at the same era, it is kept by staking pool rather than bridge.
+		pub NettedUnbonding get(fn netted_unbonding): map hasher(twox_64_concat) EraIndex => Balance;
 	}
 }
 
@@ -96,6 +182,12 @@ decl_module! {
 		const DefaultExchangeRate: ExchangeRate = T::DefaultExchangeRate::get();
 		const ClaimFeeReturnRatio: Ratio = T::ClaimFeeReturnRatio::get();
 		const ModuleId: ModuleId = T::ModuleId::get();
+		const RewardFeeRatio: Ratio = T::RewardFeeRatio::get();
+		const MaxRedeemRequests: u32 = T::MaxRedeemRequests::get();
+		const MaxRedeemRequestsPerMint: u32 = T::MaxRedeemRequestsPerMint::get();
+		const MaxRedeemRequestsPerAccount: u32 = T::MaxRedeemRequestsPerAccount::get();
+		const MinRedeemRequestAmount: Balance = T::MinRedeemRequestAmount::get();
+		const MintRebateRatio: Ratio = T::MintRebateRatio::get();
 	}
 }
 
@@ -117,6 +209,7 @@ impl<T: Trait> Module<T> {
 		Self::get_communal_bonded()
 			.saturating_add(Self::free_unbonded())
 			.saturating_add(Self::unbonding_to_free())
+			.saturating_add(Self::unbonded_at_bridge().1)
 	}
 
 	/// communal_bonded_ratio = communal_bonded / total_communal_balance
@@ -139,12 +232,35 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// The claimed unbond of `who` at `era` after slashes.
+	pub fn get_claimed_unbond(who: &T::AccountId, era: EraIndex) -> Balance {
+		Self::claimed_unbond_ratio_or_one(era).saturating_mul_int(Self::claimed_unbond(who, era))
+	}
+
+	fn claimed_unbond_ratio_or_one(era: EraIndex) -> Ratio {
+		Self::claimed_unbond_ratio(era).unwrap_or_else(Ratio::one)
+	}
+
+	/// Add `amount` to the claimed unbond of `who` at `era`, it is scaled by
+	/// the remaining ratio of this era to be counted after slashes.
+	fn add_claimed_unbond(who: &T::AccountId, era: EraIndex, amount: Balance) -> DispatchResult {
+		let scaled_amount = Ratio::checked_from_integer(amount)
+			.and_then(|amount| amount.checked_div(&Self::claimed_unbond_ratio_or_one(era)))
+			.map(|scaled_amount| scaled_amount.into_inner() / Ratio::accuracy())
+			.ok_or(Error::<T>::Overflow)?;
+		<ClaimedUnbond<T>>::try_mutate(who, era, |balance| -> DispatchResult {
+			*balance = balance.checked_add(scaled_amount).ok_or(Error::<T>::Overflow)?;
+			Ok(())
+		})
+	}
+
 	pub fn get_available_unbonded(who: &T::AccountId) -> Balance {
 		let current_era = Self::current_era();
 		<ClaimedUnbond<T>>::iter_prefix(who)
 			.filter(|(era_index, _)| era_index <= &current_era)
-			.fold(Zero::zero(), |available_unbonded, (_, claimed)| {
-				available_unbonded.saturating_add(claimed)
+			.fold(Zero::zero(), |available_unbonded, (era_index, claimed)| {
+				available_unbonded
+					.saturating_add(Self::claimed_unbond_ratio_or_one(era_index).saturating_mul_int(claimed))
 			})
 	}
 
@@ -156,10 +272,15 @@ impl<T: Trait> Module<T> {
 		<ClaimedUnbond<T>>::iter_prefix(who)
 			.filter(|(era_index, _)| era_index <= &current_era)
 			.for_each(|(era_index, claimed)| {
-				withdrawn_amount = withdrawn_amount.saturating_add(claimed);
+				withdrawn_amount = withdrawn_amount
+					.saturating_add(Self::claimed_unbond_ratio_or_one(era_index).saturating_mul_int(claimed));
 				<ClaimedUnbond<T>>::remove(who, era_index);
 			});
 
+		ensure!(
+			withdrawn_amount <= Self::total_claimed_unbonded(),
+			Error::<T>::UnbondedNotReceived,
+		);
 		T::Currency::transfer(staking_currency_id, &Self::account_id(), who, withdrawn_amount)?;
 		TotalClaimedUnbonded::mutate(|balance| *balance = balance.saturating_sub(withdrawn_amount));
 		Ok(withdrawn_amount)
@@ -170,8 +291,10 @@ impl<T: Trait> Module<T> {
 			return Ok(());
 		}
 
-		T::Bridge::transfer_to_bridge(&Self::account_id(), amount)?;
-		T::Bridge::bond_extra(amount)?;
+		for (account_index, amount) in Self::distribute_bond(amount) {
+			T::Bridge::transfer_to_bridge(&Self::account_id(), account_index, amount)?;
+			T::Bridge::bond_extra(account_index, amount)?;
+		}
 		FreeUnbonded::mutate(|free_unbonded| -> DispatchResult {
 			*free_unbonded = free_unbonded.checked_sub(amount).ok_or(Error::<T>::Overflow)?;
 			Ok(())
@@ -179,7 +302,9 @@ impl<T: Trait> Module<T> {
 		TotalBonded::try_mutate(|total_bonded| -> DispatchResult {
 			*total_bonded = total_bonded.checked_add(amount).ok_or(Error::<T>::Overflow)?;
 			Ok(())
-		})
+		})?;
+		BridgeBalanceRecord::mutate(|record| *record = record.saturating_add(amount));
+		Ok(())
 	}
 
 	pub fn deposit_free_pool(who: &T::AccountId, amount: Balance) -> DispatchResult {
@@ -247,12 +372,7 @@ impl<T: Trait> Module<T> {
 					.ok_or(Error::<T>::Overflow)?;
 				Ok(())
 			})?;
-			<ClaimedUnbond<T>>::try_mutate(who, unbonded_era_index, |balance| -> DispatchResult {
-				*balance = balance
-					.checked_add(staking_amount_to_unbond)
-					.ok_or(Error::<T>::Overflow)?;
-				Ok(())
-			})?;
+			Self::add_claimed_unbond(who, unbonded_era_index, staking_amount_to_unbond)?;
 
 			<Module<T>>::deposit_event(RawEvent::RedeemByUnbond(
 				who.clone(),
@@ -355,12 +475,7 @@ impl<T: Trait> Module<T> {
 			let liquid_currency_id = T::LiquidCurrencyId::get();
 			T::Currency::withdraw(liquid_currency_id, who, liquid_amount_to_redeem)
 				.map_err(|_| Error::<T>::LiquidCurrencyNotEnough)?;
-			<ClaimedUnbond<T>>::try_mutate(who, target_era, |claimed_unbond| -> DispatchResult {
-				*claimed_unbond = claimed_unbond
-					.checked_add(staking_amount_to_claim)
-					.ok_or(Error::<T>::Overflow)?;
-				Ok(())
-			})?;
+			Self::add_claimed_unbond(who, target_era, staking_amount_to_claim)?;
 			Unbonding::try_mutate(target_era, |(_, claimed_unbonding)| -> DispatchResult {
 				*claimed_unbonding = claimed_unbonding
 					.checked_add(staking_amount_to_claim)
@@ -391,58 +506,688 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
-	pub fn unbond_and_update(era: EraIndex) {
+	/// This function must to be called in `with_transaction_result` scope to
+	/// ensure atomic
+	pub fn request_redeem(
+		who: &T::AccountId,
+		amount: Balance,
+		max_fee_rate: Rate,
+	) -> sp_std::result::Result<RedeemRequestId, DispatchError> {
+		ensure!(
+			amount >= T::MinRedeemRequestAmount::get(),
+			Error::<T>::RedeemRequestAmountTooLow,
+		);
+		let mut queue = Self::redeem_queue();
+		ensure!(
+			(queue.len() as u32) < T::MaxRedeemRequests::get(),
+			Error::<T>::RedeemQueueFull,
+		);
+		<RedeemRequestsCount<T>>::try_mutate(who, |count| -> DispatchResult {
+			ensure!(
+				*count < T::MaxRedeemRequestsPerAccount::get(),
+				Error::<T>::TooManyRedeemRequests,
+			);
+			*count = count.saturating_add(1);
+			Ok(())
+		})?;
+
+		T::Currency::transfer(T::LiquidCurrencyId::get(), who, &Self::account_id(), amount)
+			.map_err(|_| Error::<T>::LiquidCurrencyNotEnough)?;
+
+		let request_id = Self::next_redeem_request_id();
+		NextRedeemRequestId::put(request_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+		<RedeemRequests<T>>::insert(
+			request_id,
+			RedeemRequest {
+				who: who.clone(),
+				remaining: amount,
+				max_fee_rate,
+				era: Self::current_era(),
+			},
+		);
+		queue.push(request_id);
+		RedeemQueue::put(queue);
+
+		<Module<T>>::deposit_event(RawEvent::RedeemRequested(who.clone(), request_id, amount, max_fee_rate));
+		Ok(request_id)
+	}
+
+	/// This function must to be called in `with_transaction_result` scope to
+	/// ensure atomic
+	pub fn cancel_redeem_request(who: &T::AccountId, request_id: RedeemRequestId) -> DispatchResult {
+		let request = Self::redeem_requests(request_id)
+			.filter(|request| &request.who == who)
+			.ok_or(Error::<T>::RedeemRequestNotFound)?;
+
+		T::Currency::transfer(T::LiquidCurrencyId::get(), &Self::account_id(), who, request.remaining)?;
+		Self::remove_redeem_request(request_id, who);
+		RedeemQueue::mutate(|queue| queue.retain(|id| *id != request_id));
+
+		<Module<T>>::deposit_event(RawEvent::RedeemRequestCancelled(
+			who.clone(),
+			request_id,
+			request.remaining,
+		));
+		Ok(())
+	}
+
+	/// The cost of decoding, scanning and encoding a full redeem queue.
+	fn redeem_queue_weight() -> Weight {
+		WEIGHT_PER_NANOS
+			.saturating_mul(100)
+			.saturating_mul(T::MaxRedeemRequests::get() as Weight)
+	}
+
+	/// Remove the redeem request from storage, the queue is cleaned up by
+	/// the caller.
+	fn remove_redeem_request(request_id: RedeemRequestId, who: &T::AccountId) {
+		<RedeemRequests<T>>::remove(request_id);
+		<RedeemRequestsCount<T>>::mutate_exists(who, |maybe_count| {
+			*maybe_count = maybe_count
+				.map(|count| count.saturating_sub(1))
+				.filter(|count| !count.is_zero());
+		});
+	}
+
+	/// The fee rate of a redeem request made at `era` if it is filled at
+	/// current era. It decreases to zero when the request has waited as long
+	/// as redeeming by unbond.
+	pub fn redeem_request_fee_rate(era: EraIndex) -> Rate {
+		let unbonded_era_index = era
+			.saturating_add(EraIndex::one())
+			.saturating_add(<<T as Trait>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get());
+		Self::claim_period_percent(unbonded_era_index).saturating_mul(T::MaxClaimFee::get())
+	}
+
+	/// Calculate \[liquid_amount_to_redeem, fee_in_liquid, liquid_amount_net,
+	/// staking_amount\] to fill `liquid_amount` of a redeem request at
+	/// `fee_rate`, with at most `available_staking_amount`.
+	fn calculate_redeem_request_fill(
+		liquid_amount: Balance,
+		fee_rate: Rate,
+		available_staking_amount: Balance,
+	) -> (Balance, Balance, Balance, Balance) {
+		let mut liquid_amount_to_redeem = liquid_amount;
+		let mut fee_in_liquid_currency = fee_rate.saturating_mul_int(liquid_amount_to_redeem);
+		let mut liquid_amount_net = liquid_amount_to_redeem.saturating_sub(fee_in_liquid_currency);
+		let mut staking_amount = Self::liquid_exchange_rate().saturating_mul_int(liquid_amount_net);
+
+		// if available_staking_amount is not enough, fill partially
+		if staking_amount > available_staking_amount {
+			let ratio = Ratio::checked_from_rational(available_staking_amount, staking_amount)
+				.expect("staking_amount is not zero; qed");
+			liquid_amount_to_redeem = ratio.saturating_mul_int(liquid_amount_to_redeem);
+			fee_in_liquid_currency = sp_std::cmp::min(
+				liquid_amount_to_redeem,
+				ratio.saturating_mul_int(fee_in_liquid_currency),
+			);
+			liquid_amount_net = liquid_amount_to_redeem.saturating_sub(fee_in_liquid_currency);
+			staking_amount = available_staking_amount;
+		}
+
+		(
+			liquid_amount_to_redeem,
+			fee_in_liquid_currency,
+			liquid_amount_net,
+			staking_amount,
+		)
+	}
+
+	/// Fill the redeem requests in FIFO order with the staking currency of
+	/// minter directly, at most `MaxRedeemRequestsPerMint` requests from the
+	/// head of the queue are visited. The minter gets the liquid currency of
+	/// requests and a rebate of the returned fee. Return the used staking
+	/// amount and the received liquid amount.
+	///
+	/// This function must to be called in `with_transaction_result` scope to
+	/// ensure atomic
+	pub fn fill_redeem_requests_by_mint(
+		minter: &T::AccountId,
+		amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let liquid_currency_id = T::LiquidCurrencyId::get();
+		let mut queue = Self::redeem_queue();
+		let mut staking_amount_used: Balance = Zero::zero();
+		let mut liquid_amount_received: Balance = Zero::zero();
+
+		for request_id in queue.iter().take(T::MaxRedeemRequestsPerMint::get() as usize) {
+			let available_staking_amount = amount.saturating_sub(staking_amount_used);
+			if available_staking_amount.is_zero() {
+				break;
+			}
+
+			let mut request = match Self::redeem_requests(request_id) {
+				Some(request) => request,
+				None => continue,
+			};
+			let fee_rate = Self::redeem_request_fee_rate(request.era);
+			if fee_rate > request.max_fee_rate {
+				continue;
+			}
+
+			let (liquid_amount_to_redeem, fee_in_liquid_currency, liquid_amount_to_minter, staking_amount) =
+				Self::calculate_redeem_request_fill(request.remaining, fee_rate, available_staking_amount);
+			if staking_amount.is_zero() {
+				continue;
+			}
+
+			// the fee is split to commission of homa, rebate of minter, and the rest is burned
+			let commission_to_homa = Ratio::one()
+				.saturating_sub(T::ClaimFeeReturnRatio::get())
+				.saturating_mul_int(fee_in_liquid_currency);
+			let rebate_to_minter =
+				T::MintRebateRatio::get().saturating_mul_int(fee_in_liquid_currency.saturating_sub(commission_to_homa));
+
+			T::Currency::transfer(T::StakingCurrencyId::get(), minter, &request.who, staking_amount)?;
+			T::Currency::transfer(
+				liquid_currency_id,
+				&Self::account_id(),
+				minter,
+				liquid_amount_to_minter.saturating_add(rebate_to_minter),
+			)?;
+			T::Currency::withdraw(
+				liquid_currency_id,
+				&Self::account_id(),
+				fee_in_liquid_currency.saturating_sub(rebate_to_minter),
+			)?;
+			T::OnCommission::on_commission(liquid_currency_id, commission_to_homa);
+
+			staking_amount_used = staking_amount_used.saturating_add(staking_amount);
+			liquid_amount_received = liquid_amount_received
+				.saturating_add(liquid_amount_to_minter)
+				.saturating_add(rebate_to_minter);
+
+			request.remaining = request.remaining.saturating_sub(liquid_amount_to_redeem);
+			if request.remaining.is_zero() {
+				Self::remove_redeem_request(*request_id, &request.who);
+			} else {
+				<RedeemRequests<T>>::insert(request_id, request.clone());
+			}
+
+			<Module<T>>::deposit_event(RawEvent::RedeemRequestFilled(
+				request.who,
+				*request_id,
+				fee_in_liquid_currency,
+				liquid_amount_to_minter,
+				staking_amount,
+			));
+			<Module<T>>::deposit_event(RawEvent::MintFilledRedeemRequest(
+				minter.clone(),
+				*request_id,
+				staking_amount,
+				rebate_to_minter,
+			));
+		}
+
+		queue.retain(|request_id| <RedeemRequests<T>>::contains_key(request_id));
+		RedeemQueue::put(queue);
+
+		Ok((staking_amount_used, liquid_amount_received))
+	}
+
+	/// Fill the redeem requests in FIFO order with free unbonded. The request
+	/// whose max fee rate is lower than current fee rate is skipped and keeps
+	/// its position. Then unbond the shortfall of the remaining requests.
+	/// Return the number of requests visited.
+	pub fn process_redeem_queue() -> u32 {
+		let liquid_currency_id = T::LiquidCurrencyId::get();
+		let mut queue = Self::redeem_queue();
+		let mut visited: u32 = 0;
+
+		for request_id in queue.iter() {
+			let free_unbonded_pool = Self::free_unbonded();
+			if free_unbonded_pool.is_zero() {
+				break;
+			}
+			visited = visited.saturating_add(1);
+
+			let mut request = match Self::redeem_requests(request_id) {
+				Some(request) => request,
+				None => continue,
+			};
+			let fee_rate = Self::redeem_request_fee_rate(request.era);
+			if fee_rate > request.max_fee_rate {
+				continue;
+			}
+
+			let (liquid_amount_to_redeem, fee_in_liquid_currency, liquid_amount_to_burn, staking_amount) =
+				Self::calculate_redeem_request_fill(request.remaining, fee_rate, free_unbonded_pool);
+			if staking_amount.is_zero() {
+				continue;
+			}
+
+			// the liquid currency of request is kept by staking pool, burn it before
+			// paying the staking currency.
+			let paid = with_transaction_result(|| -> DispatchResult {
+				T::Currency::withdraw(liquid_currency_id, &Self::account_id(), liquid_amount_to_redeem)?;
+				T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&Self::account_id(),
+					&request.who,
+					staking_amount,
+				)
+			});
+			if paid.is_err() {
+				break;
+			}
+			FreeUnbonded::mutate(|free_unbonded| *free_unbonded = free_unbonded.saturating_sub(staking_amount));
+
+			let commission_to_homa = Ratio::one()
+				.saturating_sub(T::ClaimFeeReturnRatio::get())
+				.saturating_mul_int(fee_in_liquid_currency);
+			T::OnCommission::on_commission(liquid_currency_id, commission_to_homa);
+
+			request.remaining = request.remaining.saturating_sub(liquid_amount_to_redeem);
+			if request.remaining.is_zero() {
+				Self::remove_redeem_request(*request_id, &request.who);
+			} else {
+				<RedeemRequests<T>>::insert(request_id, request.clone());
+			}
+
+			<Module<T>>::deposit_event(RawEvent::RedeemRequestFilled(
+				request.who,
+				*request_id,
+				fee_in_liquid_currency,
+				liquid_amount_to_burn,
+				staking_amount,
+			));
+		}
+
+		queue.retain(|request_id| <RedeemRequests<T>>::contains_key(request_id));
+		let remaining_liquid_amount = queue.iter().fold(Zero::zero(), |total: Balance, request_id| {
+			total.saturating_add(Self::redeem_requests(request_id).map_or(Zero::zero(), |request| request.remaining))
+		});
+		RedeemQueue::put(queue);
+
+		// unbond the shortfall of the remaining requests to free pool
+		let (unbond_next_era, claimed_next_era) = Self::next_era_unbond();
+		let incoming_free = Self::free_unbonded()
+			.saturating_add(Self::unbonding_to_free())
+			.saturating_add(Self::unbonded_at_bridge().1)
+			.saturating_add(unbond_next_era.saturating_sub(claimed_next_era));
+		let shortfall = Self::liquid_exchange_rate()
+			.saturating_mul_int(remaining_liquid_amount)
+			.saturating_sub(incoming_free)
+			.min(Self::get_communal_bonded());
+		if !shortfall.is_zero() {
+			NextEraUnbond::mutate(|(unbond, _)| *unbond = unbond.saturating_add(shortfall));
+		}
+
+		// the remaining requests are read again to sum up their liquid amount
+		visited.saturating_add(queue.len() as u32)
+	}
+
+	/// The redeem requests of `who` with \[request_id, position_in_queue,
+	/// remaining_liquid_amount, expected_era\]. The expected era is an
+	/// estimate of when the request is fully filled, ignoring the max fee rate.
+	pub fn get_redeem_requests(who: &T::AccountId) -> Vec<(RedeemRequestId, u32, Balance, Option<EraIndex>)> {
+		let current_era = Self::current_era();
+		let bonding_duration = <<T as Trait>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get();
+		let last_era = current_era
+			.saturating_add(EraIndex::one())
+			.saturating_add(bonding_duration);
+		let liquid_exchange_rate = Self::liquid_exchange_rate();
+		let (unbond_next_era, claimed_next_era) = Self::next_era_unbond();
+
+		// the free unbonded of each upcoming era
+		let incoming_free = |era: EraIndex| -> Balance {
+			let (value, claimed) = Self::unbonding(era);
+			let incoming = value.saturating_sub(claimed);
+			if era == last_era {
+				incoming.saturating_add(unbond_next_era.saturating_sub(claimed_next_era))
+			} else {
+				incoming
+			}
+		};
+
+		let mut requests: Vec<(RedeemRequestId, u32, Balance, Option<EraIndex>)> = Vec::new();
+		let mut total_liquid_amount: Balance = Zero::zero();
+		let mut expected_era = current_era.saturating_add(EraIndex::one());
+		let mut total_free = Self::free_unbonded().saturating_add(incoming_free(expected_era));
+		for (position, request_id) in Self::redeem_queue().into_iter().enumerate() {
+			if let Some(request) = Self::redeem_requests(request_id) {
+				total_liquid_amount = total_liquid_amount.saturating_add(request.remaining);
+				let total_staking_amount = liquid_exchange_rate.saturating_mul_int(total_liquid_amount);
+				while total_free < total_staking_amount && expected_era < last_era {
+					expected_era += 1;
+					total_free = total_free.saturating_add(incoming_free(expected_era));
+				}
+
+				if &request.who == who {
+					// the shortfall will be unbonded at next era if communal bonded is enough
+					let expected = if total_free >= total_staking_amount {
+						Some(expected_era)
+					} else if total_free.saturating_add(Self::get_communal_bonded()) >= total_staking_amount {
+						Some(last_era)
+					} else {
+						None
+					};
+					requests.push((request_id, position as u32, request.remaining, expected));
+				}
+			}
+		}
+
+		requests
+	}
+
+	/// Unbond the next era unbond and bond `bond_amount` of free unbonded.
+	/// The offsetting part of them is netted and kept by staking pool until
+	/// the unbonded era, only the net amount is bonded or unbonded at bridge.
+	pub fn unbond_and_update(era: EraIndex, bond_amount: Balance) {
 		let (total_to_unbond, claimed_to_unbond) = Self::next_era_unbond();
 		let bonding_duration = <<T as Trait>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get();
 		let unbonded_era_index = era.saturating_add(bonding_duration);
+		let mut bond_amount = bond_amount.min(Self::free_unbonded());
+
+		if !total_to_unbond.is_zero() {
+			let netted = bond_amount.min(total_to_unbond);
+			let unbond_at_bridge = total_to_unbond.saturating_sub(netted);
+
+			if unbond_at_bridge.is_zero()
+				|| with_transaction_result(|| Self::unbond_from_sub_accounts(unbond_at_bridge)).is_ok()
+			{
+				NextEraUnbond::kill();
+				TotalBonded::mutate(|bonded| *bonded = bonded.saturating_sub(unbond_at_bridge));
+				Unbonding::insert(unbonded_era_index, (total_to_unbond, claimed_to_unbond));
+				UnbondingToFree::mutate(|unbonding| {
+					*unbonding = unbonding.saturating_add(total_to_unbond.saturating_sub(claimed_to_unbond))
+				});
+				if !netted.is_zero() {
+					FreeUnbonded::mutate(|free_unbonded| *free_unbonded = free_unbonded.saturating_sub(netted));
+					NettedUnbonding::mutate(unbonded_era_index, |balance| *balance = balance.saturating_add(netted));
+					bond_amount = bond_amount.saturating_sub(netted);
+				}
+			}
+		}
 
-		if !total_to_unbond.is_zero() && T::Bridge::unbond(total_to_unbond).is_ok() {
-			NextEraUnbond::kill();
-			TotalBonded::mutate(|bonded| *bonded = bonded.saturating_sub(total_to_unbond));
-			Unbonding::insert(unbonded_era_index, (total_to_unbond, claimed_to_unbond));
-			UnbondingToFree::mutate(|unbonding| {
-				*unbonding = unbonding.saturating_add(total_to_unbond.saturating_sub(claimed_to_unbond))
-			});
+		// bound more amount for staking. if it failed, just that added amount did not
+		// succeed and it should not affect the process. so ignore result to continue.
+		let _ = with_transaction_result(|| Self::bond(bond_amount));
+	}
+
+	fn sub_account_count() -> SubAccountIndex {
+		<<T as Trait>::Bridge as PolkadotBridgeType<_, _>>::SubAccountCount::get()
+	}
+
+	/// The staking ledger aggregated from all sub-accounts at bridge, the
+	/// unlocking chunks of the same era are merged.
+	pub fn get_bridge_ledger() -> PolkadotStakingLedger<Balance, EraIndex> {
+		let mut ledger = PolkadotStakingLedger::<Balance, EraIndex>::default();
+		for account_index in 0..Self::sub_account_count() {
+			let sub_account_ledger = T::Bridge::ledger(account_index);
+			ledger.total = ledger.total.saturating_add(sub_account_ledger.total);
+			ledger.active = ledger.active.saturating_add(sub_account_ledger.active);
+			for chunk in sub_account_ledger.unlocking {
+				match ledger.unlocking.iter_mut().find(|c| c.era == chunk.era) {
+					Some(c) => c.value = c.value.saturating_add(chunk.value),
+					None => ledger.unlocking.push(chunk),
+				}
+			}
 		}
+		ledger.unlocking.sort_by_key(|chunk| chunk.era);
+		ledger
 	}
 
-	pub fn rebalance(era: EraIndex) {
-		// #1: bridge withdraw unbonded and withdraw payout
-		T::Bridge::withdraw_unbonded();
+	/// The balance of all sub-accounts at bridge.
+	pub fn get_bridge_balance() -> Balance {
+		(0..Self::sub_account_count()).fold(Zero::zero(), |total: Balance, account_index| {
+			total.saturating_add(T::Bridge::balance(account_index))
+		})
+	}
 
-		// TODO: record the balances of bridge before and after do payout_nominator,
-		// and oncommision to homa treasury according to RewardFeeRatio
-		T::Bridge::payout_nominator();
+	/// The ledgers of each sub-account at bridge.
+	pub fn get_sub_account_ledgers() -> Vec<(SubAccountIndex, PolkadotStakingLedger<Balance, EraIndex>)> {
+		(0..Self::sub_account_count())
+			.map(|account_index| (account_index, T::Bridge::ledger(account_index)))
+			.collect()
+	}
 
-		// #2: update staking pool by bridge ledger
-		// TODO: adjust the amount of this era unbond by the slash situation in last era
-		let bridge_ledger = T::Bridge::ledger();
-		TotalBonded::put(bridge_ledger.active);
+	/// Spread the bond amount over sub-accounts, the sub-accounts with the
+	/// least active are leveled up first.
+	pub fn distribute_bond(amount: Balance) -> Vec<(SubAccountIndex, Balance)> {
+		let mut actives = (0..Self::sub_account_count())
+			.map(|account_index| (account_index, T::Bridge::ledger(account_index).active))
+			.collect::<Vec<_>>();
+		actives.sort_by_key(|(_, active)| *active);
+
+		// find the least actives which can be leveled up to the next one
+		let mut count: Balance = Zero::zero();
+		let mut sum: Balance = Zero::zero();
+		for (_, active) in actives.iter() {
+			if !count.is_zero() && active.saturating_mul(count) >= sum.saturating_add(amount) {
+				break;
+			}
+			count = count.saturating_add(One::one());
+			sum = sum.saturating_add(*active);
+		}
+		if count.is_zero() {
+			return Vec::new();
+		}
 
-		// #3: withdraw available from bridge ledger and update unbonded at this era
-		let bridge_available = T::Bridge::balance().saturating_sub(bridge_ledger.total);
-		if T::Bridge::receive_from_bridge(&Self::account_id(), bridge_available).is_ok() {
-			let (total_unbonded, claimed_unbonded) = Self::unbonding(era);
-			let claimed_unbonded_added = bridge_available.min(claimed_unbonded);
-			let free_unbonded_added = bridge_available.saturating_sub(claimed_unbonded_added);
-			if !claimed_unbonded_added.is_zero() {
-				TotalClaimedUnbonded::mutate(|balance| *balance = balance.saturating_add(claimed_unbonded_added));
+		let total = sum.saturating_add(amount);
+		let level = total / count;
+		let mut remainder = total % count;
+		actives
+			.into_iter()
+			.take(count as usize)
+			.filter_map(|(account_index, active)| {
+				let mut bond_amount = level.saturating_sub(active);
+				if !remainder.is_zero() {
+					bond_amount = bond_amount.saturating_add(One::one());
+					remainder = remainder.saturating_sub(One::one());
+				}
+				if bond_amount.is_zero() {
+					None
+				} else {
+					Some((account_index, bond_amount))
+				}
+			})
+			.collect()
+	}
+
+	/// Unbond from the sub-accounts with the largest active first, so the
+	/// amount is covered by as few relay chain messages as possible.
+	///
+	/// This function must to be called in `with_transaction_result` scope to
+	/// ensure atomic
+	fn unbond_from_sub_accounts(amount: Balance) -> DispatchResult {
+		let mut ledgers = (0..Self::sub_account_count())
+			.map(|account_index| (account_index, T::Bridge::ledger(account_index)))
+			.filter(|(_, ledger)| !ledger.active.is_zero())
+			.collect::<Vec<_>>();
+		let total_active = ledgers.iter().fold(Zero::zero(), |total: Balance, (_, ledger)| {
+			total.saturating_add(ledger.active)
+		});
+		ensure!(total_active >= amount, Error::<T>::BridgeActiveNotEnough);
+
+		ledgers.sort_by(|(_, a), (_, b)| b.active.cmp(&a.active));
+
+		let mut remaining = amount;
+		for (account_index, ledger) in ledgers {
+			if remaining.is_zero() {
+				break;
 			}
-			if !free_unbonded_added.is_zero() {
-				FreeUnbonded::mutate(|balance| *balance = balance.saturating_add(free_unbonded_added));
+			let unbond_amount = remaining.min(ledger.active);
+			T::Bridge::unbond(account_index, unbond_amount)?;
+			remaining = remaining.saturating_sub(unbond_amount);
+		}
+
+		Ok(())
+	}
+
+	/// Spread the slash over bonded, unbonding and claimed positions at
+	/// bridge in proportion, return the consumed weight.
+	pub fn on_slash(era: EraIndex, slash: Balance) -> Weight {
+		let unbondings = Unbonding::iter()
+			.map(|(era_index, (value, claimed))| (era_index, (value, claimed), Self::netted_unbonding(era_index)))
+			.collect::<Vec<_>>();
+		let total_at_bridge = unbondings
+			.iter()
+			.fold(Self::total_bonded(), |total, (_, (value, _), netted)| {
+				total.saturating_add(value.saturating_sub(*netted))
+			});
+		let remaining_ratio =
+			Ratio::checked_from_rational(total_at_bridge.saturating_sub(slash), total_at_bridge).unwrap_or_default();
+
+		TotalBonded::mutate(|bonded| *bonded = remaining_ratio.saturating_mul_int(*bonded));
+		NextEraUnbond::mutate(|(unbond, claimed)| {
+			*unbond = remaining_ratio.saturating_mul_int(*unbond);
+			*claimed = remaining_ratio.saturating_mul_int(*claimed);
+		});
+
+		// the claimed unbond of next era unbond will be unbonded at `era + bonding_duration`
+		let next_era_unbonded_era_index =
+			era.saturating_add(<<T as Trait>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get());
+		let mut unbonding_to_free: Balance = Zero::zero();
+		let unbondings_count = unbondings.len() as Weight;
+		for (era_index, (value, claimed), netted) in unbondings {
+			// the netted unbonding is not at bridge and not slashed
+			let remaining_value =
+				netted.saturating_add(remaining_ratio.saturating_mul_int(value.saturating_sub(netted)));
+			let era_remaining_ratio = Ratio::checked_from_rational(remaining_value, value).unwrap_or(remaining_ratio);
+			let claimed = era_remaining_ratio.saturating_mul_int(claimed);
+			unbonding_to_free = unbonding_to_free.saturating_add(remaining_value.saturating_sub(claimed));
+			Unbonding::insert(era_index, (remaining_value, claimed));
+			if era_index != next_era_unbonded_era_index {
+				ClaimedUnbondRatio::insert(
+					era_index,
+					Self::claimed_unbond_ratio_or_one(era_index).saturating_mul(era_remaining_ratio),
+				);
 			}
-			UnbondingToFree::mutate(|balance| {
-				*balance = balance.saturating_sub(total_unbonded.saturating_sub(claimed_unbonded))
+		}
+		ClaimedUnbondRatio::insert(
+			next_era_unbonded_era_index,
+			Self::claimed_unbond_ratio_or_one(next_era_unbonded_era_index).saturating_mul(remaining_ratio),
+		);
+		UnbondingToFree::put(unbonding_to_free);
+
+		<Module<T>>::deposit_event(RawEvent::Slashed(era, slash));
+
+		// every unbonding era reads its unbonding, netted unbonding and claimed
+		// unbond ratio, and writes the unbonding and claimed unbond ratio
+		T::DbWeight::get()
+			.reads_writes(3, 4)
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2).saturating_mul(unbondings_count))
+	}
+
+	/// Take the commission of the reward as liquid currency to Homa treasury,
+	/// and return the commission in liquid currency.
+	pub fn on_reward(era: EraIndex, reward: Balance) -> Balance {
+		let commission = T::RewardFeeRatio::get().saturating_mul_int(reward);
+		let total_communal_balance = Self::get_total_communal_balance()
+			.saturating_add(reward)
+			.saturating_sub(commission);
+		let commission_in_liquid = ExchangeRate::checked_from_rational(
+			T::Currency::total_issuance(T::LiquidCurrencyId::get()),
+			total_communal_balance,
+		)
+		.unwrap_or_else(|| T::DefaultExchangeRate::get().reciprocal().unwrap_or_default())
+		.saturating_mul_int(commission);
+
+		if !commission_in_liquid.is_zero() {
+			T::OnCommission::on_commission(T::LiquidCurrencyId::get(), commission_in_liquid);
+		}
+
+		<Module<T>>::deposit_event(RawEvent::RewardReceived(era, reward, commission_in_liquid));
+		commission_in_liquid
+	}
+
+	/// Rebalance the pools and the bonded at bridge at the new era, return
+	/// the consumed weight.
+	pub fn rebalance(era: EraIndex) -> Weight {
+		// every sub-account sends at most six bridge calls, each of them reads
+		// and writes the ledger and the pending messages at bridge, and reads
+		// its ledger and balance a few times
+		let mut weight = T::DbWeight::get().reads_writes(20, 15).saturating_add(
+			T::DbWeight::get()
+				.reads_writes(32, 24)
+				.saturating_mul(Self::sub_account_count() as Weight),
+		);
+
+		// #1: bridge withdraw unbonded of all sub-accounts
+		for account_index in 0..Self::sub_account_count() {
+			T::Bridge::withdraw_unbonded(account_index);
+		}
+
+		// #2: the shortfall of bridge balance to the record of last era is slashed
+		let slash = Self::bridge_balance_record().saturating_sub(Self::get_bridge_balance());
+		if !slash.is_zero() {
+			weight = weight.saturating_add(Self::on_slash(era, slash));
+		}
+
+		// #3: payout, the reward is handled when the payout is confirmed
+		for account_index in 0..Self::sub_account_count() {
+			T::Bridge::payout_nominator(account_index);
+		}
+
+		// #4: assign the new available at bridge to the unbonded at this era, and
+		// receive all available from the ledgers of all sub-accounts. They are
+		// credited to the pools when the receipts are confirmed.
+		let available_of_sub_accounts = (0..Self::sub_account_count())
+			.map(|account_index| {
+				let available =
+					T::Bridge::balance(account_index).saturating_sub(T::Bridge::ledger(account_index).total);
+				(account_index, available)
+			})
+			.collect::<Vec<_>>();
+		let bridge_available = available_of_sub_accounts
+			.iter()
+			.fold(Zero::zero(), |total: Balance, (_, available)| {
+				total.saturating_add(*available)
 			});
-			Unbonding::remove(era);
+		let (claimed_at_bridge, free_at_bridge) = Self::unbonded_at_bridge();
+		let assigned_at_bridge = claimed_at_bridge
+			.saturating_add(free_at_bridge)
+			.saturating_sub(Self::receiving_from_bridge());
+
+		// the netted unbonding of this era has been kept by staking pool
+		let netted = NettedUnbonding::take(era);
+		let available = bridge_available
+			.saturating_sub(assigned_at_bridge)
+			.saturating_add(netted);
+		let (total_unbonded, claimed_unbonded) = Self::unbonding(era);
+		let claimed_unbonded_added = available.min(claimed_unbonded);
+		let free_unbonded_added = available.saturating_sub(claimed_unbonded_added);
+		let netted_claimed = netted.min(claimed_unbonded_added);
+		let netted_free = netted.saturating_sub(netted_claimed);
+		if !netted_claimed.is_zero() {
+			TotalClaimedUnbonded::mutate(|balance| *balance = balance.saturating_add(netted_claimed));
+		}
+		if !netted_free.is_zero() {
+			FreeUnbonded::mutate(|balance| *balance = balance.saturating_add(netted_free));
 		}
+		UnbondedAtBridge::put((
+			claimed_at_bridge.saturating_add(claimed_unbonded_added.saturating_sub(netted_claimed)),
+			free_at_bridge.saturating_add(free_unbonded_added.saturating_sub(netted_free)),
+		));
+		UnbondingToFree::mutate(|balance| {
+			*balance = balance.saturating_sub(total_unbonded.saturating_sub(claimed_unbonded))
+		});
+		Unbonding::remove(era);
+
+		// if failed, the assigned is kept at bridge and received at next era
+		let _ = with_transaction_result(|| {
+			for (account_index, available) in available_of_sub_accounts {
+				if available.is_zero() {
+					continue;
+				}
+				ReceivingFromBridge::mutate(|receiving| *receiving = receiving.saturating_add(available));
+				T::Bridge::receive_from_bridge(&Self::account_id(), account_index, available)?;
+			}
+			Ok(())
+		});
+
+		// #5: fill the redeem queue with free unbonded, and unbond the shortfall
+		let visited = Self::process_redeem_queue();
+		weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 6).saturating_mul(visited as Weight));
 
-		// #4: according to the communal_bonded_ratio, decide to
+		// #6: according to the communal_bonded_ratio, decide to
 		// bond extra amount to bridge or unbond system bonded to free pool at this era
 		let communal_bonded_ratio = Self::get_communal_bonded_ratio();
 		let max_bond_ratio = T::MaxBondRatio::get();
 		let min_bond_ratio = T::MinBondRatio::get();
 		let total_communal_balance = Self::get_total_communal_balance();
+		let mut bond_amount: Balance = Zero::zero();
 		if communal_bonded_ratio > max_bond_ratio {
 			// unbond some to free pool
 			let unbond_to_free = communal_bonded_ratio
@@ -455,30 +1200,77 @@ impl<T: Trait> Module<T> {
 			}
 		} else if communal_bonded_ratio < min_bond_ratio {
 			// bond more
-			let bond_amount = min_bond_ratio
+			bond_amount = min_bond_ratio
 				.saturating_sub(communal_bonded_ratio)
 				.saturating_mul_int(total_communal_balance)
 				.min(Self::free_unbonded());
-
-			// bound more amount for staking. if it failed, just that added amount did not
-			// succeed and it should not affect the process. so ignore result to continue.
-			let _ = Self::bond(bond_amount);
 		}
 
-		// #5: unbond and update
-		Self::unbond_and_update(era);
+		// #7: net the bond and unbond, and update
+		Self::unbond_and_update(era, bond_amount);
+
+		BridgeBalanceRecord::put(Self::get_bridge_balance());
+		weight
 	}
 }
 
 impl<T: Trait> OnNewEra<EraIndex> for Module<T> {
-	fn on_new_era(new_era: EraIndex) {
+	fn on_new_era(new_era: EraIndex) -> Weight {
 		CurrentEra::put(new_era);
 
 		// rebalance first
-		Self::rebalance(new_era);
+		let weight = Self::rebalance(new_era);
 
-		// nominate
-		T::Bridge::nominate(T::Nominees::nominees());
+		// nominate, it is weighted in rebalance with the other bridge calls
+		let nominees = T::Nominees::nominees();
+		for account_index in 0..Self::sub_account_count() {
+			T::Bridge::nominate(account_index, nominees.clone());
+		}
+
+		weight.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+	}
+}
+
+impl<T: Trait> OnPolkadotBridgeResult<T::AccountId, Balance> for Module<T> {
+	fn on_payout(_account_index: SubAccountIndex, reward: Balance) {
+		Self::on_reward(Self::current_era(), reward);
+		UnbondedAtBridge::mutate(|(_, free)| *free = free.saturating_add(reward));
+		BridgeBalanceRecord::mutate(|record| *record = record.saturating_add(reward));
+	}
+
+	fn on_transfer_to_bridge_failed(from: &T::AccountId, _account_index: SubAccountIndex, amount: Balance) {
+		if from != &Self::account_id() {
+			return;
+		}
+
+		// the bond is reverted
+		FreeUnbonded::mutate(|free_unbonded| *free_unbonded = free_unbonded.saturating_add(amount));
+		TotalBonded::mutate(|bonded| *bonded = bonded.saturating_sub(amount));
+		BridgeBalanceRecord::mutate(|record| *record = record.saturating_sub(amount));
+	}
+
+	fn on_receive_from_bridge(to: &T::AccountId, _account_index: SubAccountIndex, amount: Balance, success: bool) {
+		if to != &Self::account_id() {
+			return;
+		}
+
+		ReceivingFromBridge::mutate(|receiving| *receiving = receiving.saturating_sub(amount));
+		if !success {
+			// the amount is kept at bridge and received again at next era
+			BridgeBalanceRecord::mutate(|record| *record = record.saturating_add(amount));
+			return;
+		}
+
+		// the claimed unbonded first, the rest goes to free pool
+		let (claimed_at_bridge, free_at_bridge) = Self::unbonded_at_bridge();
+		let claimed_received = amount.min(claimed_at_bridge);
+		let free_received = amount.saturating_sub(claimed_received);
+		UnbondedAtBridge::put((
+			claimed_at_bridge.saturating_sub(claimed_received),
+			free_at_bridge.saturating_sub(free_received),
+		));
+		TotalClaimedUnbonded::mutate(|balance| *balance = balance.saturating_add(claimed_received));
+		FreeUnbonded::mutate(|balance| *balance = balance.saturating_add(free_received));
 	}
 }
 
@@ -488,18 +1280,22 @@ impl<T: Trait> HomaProtocol<T::AccountId, Balance, EraIndex> for Module<T> {
 	/// This function must to be called in `with_transaction_result` scope to
 	/// ensure atomic
 	fn mint(who: &T::AccountId, amount: Self::Balance) -> sp_std::result::Result<Self::Balance, DispatchError> {
-		Self::deposit_free_pool(who, amount)?;
+		// fill the pending redeem requests first, without round-trip through bridge
+		let (staking_amount_used, liquid_amount_received) = Self::fill_redeem_requests_by_mint(who, amount)?;
+		let staking_amount_to_deposit = amount.saturating_sub(staking_amount_used);
+		Self::deposit_free_pool(who, staking_amount_to_deposit)?;
 
 		// issue liquid currency to who
 		let liquid_amount_to_issue = Self::liquid_exchange_rate()
 			.reciprocal()
 			.unwrap_or_default()
-			.checked_mul_int(amount)
+			.checked_mul_int(staking_amount_to_deposit)
 			.ok_or(Error::<T>::Overflow)?;
 		T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
 
-		<Module<T>>::deposit_event(RawEvent::MintLiquid(who.clone(), amount, liquid_amount_to_issue));
-		Ok(liquid_amount_to_issue)
+		let total_liquid_amount = liquid_amount_to_issue.saturating_add(liquid_amount_received);
+		<Module<T>>::deposit_event(RawEvent::MintLiquid(who.clone(), amount, total_liquid_amount));
+		Ok(total_liquid_amount)
 	}
 
 	fn redeem_by_unbond(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
@@ -517,4 +1313,40 @@ impl<T: Trait> HomaProtocol<T::AccountId, Balance, EraIndex> for Module<T> {
 	fn withdraw_redemption(who: &T::AccountId) -> sp_std::result::Result<Self::Balance, DispatchError> {
 		Self::withdraw_unbonded(who)
 	}
+
+	fn request_redeem(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		max_fee_rate: Rate,
+	) -> sp_std::result::Result<RedeemRequestId, DispatchError> {
+		Self::request_redeem(who, amount, max_fee_rate)
+	}
+
+	fn cancel_redeem_request(who: &T::AccountId, request_id: RedeemRequestId) -> DispatchResult {
+		Self::cancel_redeem_request(who, request_id)
+	}
+
+	fn mint_weight() -> Weight {
+		// the queue, the exchange rate and the deposit to free pool, plus the
+		// transfers, burn and commission of each visited redeem request
+		T::DbWeight::get().reads_writes(10, 6).saturating_add(
+			T::DbWeight::get()
+				.reads_writes(8, 8)
+				.saturating_mul(T::MaxRedeemRequestsPerMint::get() as Weight),
+		)
+	}
+
+	fn request_redeem_weight() -> Weight {
+		// the queue, the request count, the transfer and the new request
+		T::DbWeight::get()
+			.reads_writes(6, 6)
+			.saturating_add(Self::redeem_queue_weight())
+	}
+
+	fn cancel_redeem_request_weight() -> Weight {
+		// the request, the transfer, the request count and the queue
+		T::DbWeight::get()
+			.reads_writes(5, 6)
+			.saturating_add(Self::redeem_queue_weight())
+	}
 }
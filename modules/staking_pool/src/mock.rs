@@ -8,7 +8,7 @@ use primitives::Amount;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
 use sp_std::cell::RefCell;
-use support::PolkadotStakingLedger;
+use support::PolkadotUnlockChunk;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -139,52 +139,230 @@ parameter_types! {
 	pub const EraLength: BlockNumber = 10;
 }
 
+parameter_types! {
+	pub const SubAccountCount: SubAccountIndex = 2;
+}
+
+#[derive(Clone, Default)]
+struct MockSubAccount {
+	active: Balance,
+	unlocking: Vec<PolkadotUnlockChunk<Balance, EraIndex>>,
+	free: Balance,
+}
+
+thread_local! {
+	static BRIDGE_SUB_ACCOUNTS: RefCell<Vec<MockSubAccount>> =
+		RefCell::new(vec![Default::default(); SubAccountCount::get() as usize]);
+	static BRIDGE_PENDING_REWARD: RefCell<Balance> = RefCell::new(0);
+	static DEFER_RESULTS: RefCell<bool> = RefCell::new(false);
+	static PENDING_RESULTS: RefCell<Vec<MockResult>> = RefCell::new(vec![]);
+}
+
+/// The result of bridge call waiting for confirmation.
+enum MockResult {
+	Payout(SubAccountIndex, Balance),
+	Receive(AccountId, SubAccountIndex, Balance),
+}
+
+fn mutate_sub_account<R>(
+	account_index: SubAccountIndex,
+	f: impl FnOnce(&mut MockSubAccount) -> Result<R, &'static str>,
+) -> Result<R, DispatchError> {
+	BRIDGE_SUB_ACCOUNTS.with(|v| {
+		let mut sub_accounts = v.borrow_mut();
+		let sub_account = sub_accounts
+			.get_mut(account_index as usize)
+			.ok_or(DispatchError::Other("invalid sub-account"))?;
+		f(sub_account).map_err(DispatchError::Other)
+	})
+}
+
+/// Set the ledger of sub-account at bridge.
+pub fn mock_bridge_ledger(
+	account_index: SubAccountIndex,
+	active: Balance,
+	unlocking: Vec<PolkadotUnlockChunk<Balance, EraIndex>>,
+) {
+	let _ = mutate_sub_account(account_index, |sub_account| {
+		sub_account.active = active;
+		sub_account.unlocking = unlocking;
+		Ok(())
+	});
+}
+
+/// Slash the active of sub-accounts in order.
+pub fn mock_slash(amount: Balance) {
+	BRIDGE_SUB_ACCOUNTS.with(|v| {
+		let mut remaining = amount;
+		for sub_account in v.borrow_mut().iter_mut() {
+			let slash = remaining.min(sub_account.active);
+			sub_account.active -= slash;
+			remaining -= slash;
+		}
+	});
+}
+
+/// The reward to sub-account 0 at the next payout.
+pub fn mock_reward(amount: Balance) {
+	BRIDGE_PENDING_REWARD.with(|v| *v.borrow_mut() += amount);
+}
+
+/// Defer the results of payouts and receipts until they are confirmed
+/// manually.
+pub fn defer_results(defer: bool) {
+	DEFER_RESULTS.with(|v| *v.borrow_mut() = defer);
+}
+
+/// Confirm all the deferred results with `success`.
+pub fn confirm_results(success: bool) {
+	for result in PENDING_RESULTS.with(|v| v.replace(vec![])) {
+		match result {
+			MockResult::Payout(account_index, reward) => {
+				if success {
+					settle_payout(account_index, reward);
+				}
+			}
+			MockResult::Receive(to, account_index, amount) => {
+				let _ = settle_receive(&to, account_index, amount, success);
+			}
+		}
+	}
+}
+
+fn is_deferred() -> bool {
+	DEFER_RESULTS.with(|v| *v.borrow())
+}
+
+fn settle_payout(account_index: SubAccountIndex, reward: Balance) {
+	let _ = mutate_sub_account(account_index, |sub_account| {
+		sub_account.free += reward;
+		Ok(())
+	});
+	StakingPoolModule::on_payout(account_index, reward);
+}
+
+fn settle_receive(to: &AccountId, account_index: SubAccountIndex, amount: Balance, success: bool) -> DispatchResult {
+	if success {
+		CurrenciesModule::deposit(DOT, to, amount)?;
+	} else {
+		mutate_sub_account(account_index, |sub_account| {
+			sub_account.free += amount;
+			Ok(())
+		})?;
+	}
+	StakingPoolModule::on_receive_from_bridge(to, account_index, amount, success);
+	Ok(())
+}
+
 pub struct MockBridge;
 impl PolkadotBridgeType<BlockNumber, EraIndex> for MockBridge {
 	type BondingDuration = BondingDuration;
 	type EraLength = EraLength;
 	type PolkadotAccountId = PolkadotAccountId;
+	type SubAccountCount = SubAccountCount;
 }
 
 impl PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex> for MockBridge {
-	fn bond_extra(_amount: Balance) -> DispatchResult {
-		Ok(())
+	fn bond_extra(account_index: SubAccountIndex, amount: Balance) -> DispatchResult {
+		mutate_sub_account(account_index, |sub_account| {
+			sub_account.free = sub_account.free.checked_sub(amount).ok_or("free not enough")?;
+			sub_account.active += amount;
+			Ok(())
+		})
 	}
 
-	fn unbond(_amount: Balance) -> DispatchResult {
-		Ok(())
+	fn unbond(account_index: SubAccountIndex, amount: Balance) -> DispatchResult {
+		mutate_sub_account(account_index, |sub_account| {
+			sub_account.active = sub_account.active.checked_sub(amount).ok_or("active not enough")?;
+			sub_account.unlocking.push(PolkadotUnlockChunk {
+				value: amount,
+				era: StakingPoolModule::current_era() + BondingDuration::get(),
+			});
+			Ok(())
+		})
 	}
 
-	fn rebond(_amount: Balance) -> DispatchResult {
+	fn rebond(_account_index: SubAccountIndex, _amount: Balance) -> DispatchResult {
 		Ok(())
 	}
 
-	fn withdraw_unbonded() {}
-
-	fn nominate(_targets: Vec<Self::PolkadotAccountId>) {}
+	fn withdraw_unbonded(account_index: SubAccountIndex) {
+		let current_era = StakingPoolModule::current_era();
+		let _ = mutate_sub_account(account_index, |sub_account| {
+			let withdrawn = sub_account
+				.unlocking
+				.iter()
+				.filter(|chunk| chunk.era <= current_era)
+				.fold(0, |total, chunk| total + chunk.value);
+			sub_account.unlocking.retain(|chunk| chunk.era > current_era);
+			sub_account.free += withdrawn;
+			Ok(())
+		});
+	}
 
-	fn payout_nominator() {}
+	fn nominate(_account_index: SubAccountIndex, _targets: Vec<Self::PolkadotAccountId>) {}
+
+	fn payout_nominator(account_index: SubAccountIndex) {
+		let reward = if account_index == 0 {
+			BRIDGE_PENDING_REWARD.with(|v| v.replace(0))
+		} else {
+			0
+		};
+		if reward != 0 {
+			if is_deferred() {
+				PENDING_RESULTS.with(|v| v.borrow_mut().push(MockResult::Payout(account_index, reward)));
+			} else {
+				settle_payout(account_index, reward);
+			}
+		}
+	}
 
-	fn transfer_to_bridge(from: &AccountId, amount: Balance) -> DispatchResult {
-		CurrenciesModule::withdraw(DOT, from, amount)
+	fn transfer_to_bridge(from: &AccountId, to: SubAccountIndex, amount: Balance) -> DispatchResult {
+		CurrenciesModule::withdraw(DOT, from, amount)?;
+		mutate_sub_account(to, |sub_account| {
+			sub_account.free += amount;
+			Ok(())
+		})
 	}
 
-	fn receive_from_bridge(to: &AccountId, amount: Balance) -> DispatchResult {
-		CurrenciesModule::deposit(DOT, to, amount)
+	fn receive_from_bridge(to: &AccountId, from: SubAccountIndex, amount: Balance) -> DispatchResult {
+		mutate_sub_account(from, |sub_account| {
+			sub_account.free = sub_account.free.checked_sub(amount).ok_or("free not enough")?;
+			Ok(())
+		})?;
+		if is_deferred() {
+			PENDING_RESULTS.with(|v| v.borrow_mut().push(MockResult::Receive(*to, from, amount)));
+			Ok(())
+		} else {
+			settle_receive(to, from, amount, true)
+		}
 	}
 }
 
 impl PolkadotBridgeState<Balance, EraIndex> for MockBridge {
-	fn ledger() -> PolkadotStakingLedger<Balance, EraIndex> {
-		PolkadotStakingLedger {
-			total: StakingPoolModule::total_bonded(),
-			active: StakingPoolModule::total_bonded(),
-			unlocking: vec![],
-		}
+	fn ledger(account_index: SubAccountIndex) -> PolkadotStakingLedger<Balance, EraIndex> {
+		BRIDGE_SUB_ACCOUNTS.with(|v| {
+			v.borrow()
+				.get(account_index as usize)
+				.map(|sub_account| PolkadotStakingLedger {
+					total: sub_account
+						.unlocking
+						.iter()
+						.fold(sub_account.active, |total, chunk| total + chunk.value),
+					active: sub_account.active,
+					unlocking: sub_account.unlocking.clone(),
+				})
+				.unwrap_or_default()
+		})
 	}
 
-	fn balance() -> Balance {
-		StakingPoolModule::total_bonded() + StakingPoolModule::unbonding(StakingPoolModule::current_era()).0
+	fn balance(account_index: SubAccountIndex) -> Balance {
+		let free = BRIDGE_SUB_ACCOUNTS.with(|v| {
+			v.borrow()
+				.get(account_index as usize)
+				.map_or(0, |sub_account| sub_account.free)
+		});
+		Self::ledger(account_index).total + free
 	}
 
 	fn current_era() -> EraIndex {
@@ -203,6 +381,12 @@ parameter_types! {
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub ClaimFeeReturnRatio: Ratio = Ratio::saturating_from_rational(80, 100);	// 80%
 	pub const StakingPoolModuleId: ModuleId = ModuleId(*b"aca/stkp");
+	pub RewardFeeRatio: Ratio = Ratio::saturating_from_rational(10, 100);	// 10%
+	pub const MaxRedeemRequests: u32 = 3;
+	pub const MaxRedeemRequestsPerMint: u32 = 2;
+	pub const MaxRedeemRequestsPerAccount: u32 = 2;
+	pub const MinRedeemRequestAmount: Balance = 100;
+	pub MintRebateRatio: Ratio = Ratio::saturating_from_rational(50, 100);	// 50%
 }
 
 impl Trait for Runtime {
@@ -219,6 +403,12 @@ impl Trait for Runtime {
 	type DefaultExchangeRate = DefaultExchangeRate;
 	type ClaimFeeReturnRatio = ClaimFeeReturnRatio;
 	type ModuleId = StakingPoolModuleId;
+	type RewardFeeRatio = RewardFeeRatio;
+	type MaxRedeemRequests = MaxRedeemRequests;
+	type MaxRedeemRequestsPerMint = MaxRedeemRequestsPerMint;
+	type MaxRedeemRequestsPerAccount = MaxRedeemRequestsPerAccount;
+	type MinRedeemRequestAmount = MinRedeemRequestAmount;
+	type MintRebateRatio = MintRebateRatio;
 }
 pub type StakingPoolModule = Module<Runtime>;
 
@@ -5,9 +5,10 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	BondingDuration, CurrenciesModule, ExtBuilder, Runtime, StakingPoolModule, System, TestEvent, ALICE, BOB, DOT,
-	LDOT, TOTAL_COMMISSION,
+	confirm_results, defer_results, mock_bridge_ledger, mock_reward, mock_slash, BondingDuration, CurrenciesModule,
+	ExtBuilder, MockBridge, Runtime, StakingPoolModule, System, TestEvent, ALICE, BOB, DOT, LDOT, TOTAL_COMMISSION,
 };
+use support::PolkadotUnlockChunk;
 
 #[test]
 fn mint_work() {
@@ -253,6 +254,7 @@ fn rebalance_work() {
 		Unbonding::insert(1, (20000, 10000));
 		UnbondingToFree::put(10000);
 		NextEraUnbond::put((5000, 5000));
+		mock_bridge_ledger(0, 20000, vec![PolkadotUnlockChunk { value: 20000, era: 1 }]);
 
 		assert_eq!(StakingPoolModule::current_era(), 0);
 		assert_eq!(StakingPoolModule::total_bonded(), 20000);
@@ -279,3 +281,556 @@ fn rebalance_work() {
 		assert_eq!(StakingPoolModule::next_era_unbond(), (0, 0));
 	});
 }
+
+#[test]
+fn rebalance_spread_slash_in_proportion() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(20000);
+		NextEraUnbond::put((2000, 2000));
+		<ClaimedUnbond<Runtime>>::insert(BOB, 1 + BondingDuration::get(), 2000);
+		Unbonding::insert(3, (10000, 4000));
+		<ClaimedUnbond<Runtime>>::insert(ALICE, 3, 4000);
+		UnbondingToFree::put(6000);
+		BridgeBalanceRecord::put(30000);
+		mock_bridge_ledger(0, 20000, vec![PolkadotUnlockChunk { value: 10000, era: 3 }]);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 240000));
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(1, 10)
+		);
+
+		mock_slash(3000);
+		CurrentEra::put(1);
+		StakingPoolModule::rebalance(1);
+
+		let slashed_event = TestEvent::staking_pool(RawEvent::Slashed(1, 3000));
+		assert!(System::events().iter().any(|record| record.event == slashed_event));
+		assert_eq!(StakingPoolModule::total_bonded(), 16200);
+		assert_eq!(StakingPoolModule::next_era_unbond(), (0, 0));
+		assert_eq!(StakingPoolModule::unbonding(3), (9000, 3600));
+		assert_eq!(StakingPoolModule::unbonding(1 + BondingDuration::get()), (1800, 1800));
+		assert_eq!(StakingPoolModule::unbonding_to_free(), 5400);
+		assert_eq!(StakingPoolModule::get_claimed_unbond(&ALICE, 3), 3600);
+		assert_eq!(
+			StakingPoolModule::get_claimed_unbond(&BOB, 1 + BondingDuration::get()),
+			1800
+		);
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(9, 100)
+		);
+		assert_eq!(
+			StakingPoolModule::bridge_balance_record(),
+			StakingPoolModule::get_bridge_balance()
+		);
+
+		// claim the slashed unbonding after slash
+		assert_ok!(StakingPoolModule::redeem_by_claim_unbonding(&ALICE, 10000, 3));
+		assert_eq!(StakingPoolModule::claimed_unbond(&ALICE, 3), 4000 + 940);
+		assert_eq!(StakingPoolModule::get_claimed_unbond(&ALICE, 3), 3600 + 846);
+		assert_eq!(StakingPoolModule::unbonding(3), (9000, 3600 + 846));
+
+		// no more slash without shortfall
+		CurrentEra::put(2);
+		StakingPoolModule::rebalance(2);
+		assert_eq!(StakingPoolModule::unbonding(3), (9000, 3600 + 846));
+	});
+}
+
+#[test]
+fn rebalance_take_commission_of_reward() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(9100);
+		BridgeBalanceRecord::put(9100);
+		mock_bridge_ledger(0, 9100, vec![]);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 91000));
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 0);
+
+		mock_reward(1000);
+		CurrentEra::put(1);
+		StakingPoolModule::rebalance(1);
+
+		let reward_event = TestEvent::staking_pool(RawEvent::RewardReceived(1, 1000, 910));
+		assert!(System::events().iter().any(|record| record.event == reward_event));
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 910);
+		assert_eq!(StakingPoolModule::free_unbonded(), 1000);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			1000
+		);
+		assert_eq!(
+			StakingPoolModule::bridge_balance_record(),
+			StakingPoolModule::get_bridge_balance()
+		);
+	});
+}
+
+#[test]
+fn rebalance_credit_reward_and_receipt_when_confirmed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(9100);
+		BridgeBalanceRecord::put(9100);
+		mock_bridge_ledger(0, 9100, vec![]);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 91000));
+		defer_results(true);
+
+		mock_reward(1000);
+		CurrentEra::put(1);
+		StakingPoolModule::rebalance(1);
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 0);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (0, 0));
+		assert_eq!(StakingPoolModule::bridge_balance_record(), 9100);
+
+		// the reward is counted once the payout is confirmed
+		confirm_results(true);
+		let reward_event = TestEvent::staking_pool(RawEvent::RewardReceived(1, 1000, 910));
+		assert!(System::events().iter().any(|record| record.event == reward_event));
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 910);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (0, 1000));
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(
+			StakingPoolModule::bridge_balance_record(),
+			StakingPoolModule::get_bridge_balance()
+		);
+
+		CurrentEra::put(2);
+		StakingPoolModule::rebalance(2);
+		assert_eq!(StakingPoolModule::receiving_from_bridge(), 1000);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()), 0);
+
+		// the free pool is credited once the receipt is confirmed
+		confirm_results(true);
+		assert_eq!(StakingPoolModule::receiving_from_bridge(), 0);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (0, 0));
+		assert_eq!(StakingPoolModule::free_unbonded(), 1000);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			1000
+		);
+	});
+}
+
+#[test]
+fn rebalance_keep_unbonded_at_bridge_if_receive_failed() {
+	ExtBuilder::default().build().execute_with(|| {
+		TotalBonded::put(20000);
+		Unbonding::insert(1, (20000, 10000));
+		UnbondingToFree::put(10000);
+		NextEraUnbond::put((5000, 5000));
+		<ClaimedUnbond<Runtime>>::insert(ALICE, 1, 10000);
+		mock_bridge_ledger(0, 20000, vec![PolkadotUnlockChunk { value: 20000, era: 1 }]);
+		defer_results(true);
+
+		CurrentEra::put(1);
+		StakingPoolModule::rebalance(1);
+		assert_eq!(StakingPoolModule::unbonding(1), (0, 0));
+		assert_eq!(StakingPoolModule::unbonding_to_free(), 0);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (10000, 10000));
+		assert_eq!(StakingPoolModule::receiving_from_bridge(), 20000);
+		assert_eq!(StakingPoolModule::total_claimed_unbonded(), 0);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_noop!(
+			with_transaction_result(|| StakingPoolModule::withdraw_unbonded(&ALICE)),
+			Error::<Runtime>::UnbondedNotReceived
+		);
+
+		confirm_results(false);
+		assert_eq!(StakingPoolModule::receiving_from_bridge(), 0);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (10000, 10000));
+		assert_eq!(StakingPoolModule::total_claimed_unbonded(), 0);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(
+			StakingPoolModule::bridge_balance_record(),
+			StakingPoolModule::get_bridge_balance()
+		);
+
+		// received again at next era
+		defer_results(false);
+		CurrentEra::put(2);
+		StakingPoolModule::rebalance(2);
+		assert_eq!(StakingPoolModule::unbonded_at_bridge(), (0, 0));
+		assert_eq!(StakingPoolModule::total_claimed_unbonded(), 10000);
+		assert_eq!(StakingPoolModule::free_unbonded(), 10000);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			20000
+		);
+		assert_eq!(StakingPoolModule::withdraw_unbonded(&ALICE), Ok(10000));
+	});
+}
+
+#[test]
+fn bond_reverted_if_transfer_to_bridge_failed() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(5000));
+		assert_ok!(StakingPoolModule::bond(500));
+		assert_eq!(StakingPoolModule::total_bonded(), 500);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(StakingPoolModule::bridge_balance_record(), 500);
+
+		// the transfer of others is ignored
+		StakingPoolModule::on_transfer_to_bridge_failed(&ALICE, 1, 250);
+		assert_eq!(StakingPoolModule::total_bonded(), 500);
+
+		// the transfer is refunded by bridge
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 250));
+		StakingPoolModule::on_transfer_to_bridge_failed(&StakingPoolModule::account_id(), 1, 250);
+		assert_eq!(StakingPoolModule::total_bonded(), 250);
+		assert_eq!(StakingPoolModule::free_unbonded(), 250);
+		assert_eq!(StakingPoolModule::bridge_balance_record(), 250);
+	});
+}
+
+#[test]
+fn request_redeem_and_cancel_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 5000));
+		assert_noop!(
+			StakingPoolModule::request_redeem(&ALICE, 6000, Rate::zero()),
+			Error::<Runtime>::LiquidCurrencyNotEnough,
+		);
+
+		assert_eq!(StakingPoolModule::request_redeem(&ALICE, 1000, Rate::zero()), Ok(0));
+		let requested_event = TestEvent::staking_pool(RawEvent::RedeemRequested(ALICE, 0, 1000, Rate::zero()));
+		assert!(System::events().iter().any(|record| record.event == requested_event));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4000);
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			1000
+		);
+		assert_eq!(StakingPoolModule::redeem_queue(), vec![0]);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(0),
+			Some(RedeemRequest {
+				who: ALICE,
+				remaining: 1000,
+				max_fee_rate: Rate::zero(),
+				era: 0,
+			})
+		);
+
+		assert_noop!(
+			StakingPoolModule::request_redeem(&ALICE, 99, Rate::zero()),
+			Error::<Runtime>::RedeemRequestAmountTooLow,
+		);
+		assert_eq!(StakingPoolModule::request_redeem(&ALICE, 1000, Rate::zero()), Ok(1));
+		assert_eq!(StakingPoolModule::redeem_requests_count(ALICE), 2);
+		assert_noop!(
+			StakingPoolModule::request_redeem(&ALICE, 1000, Rate::zero()),
+			Error::<Runtime>::TooManyRedeemRequests,
+		);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 5000));
+		assert_eq!(StakingPoolModule::request_redeem(&BOB, 1000, Rate::zero()), Ok(2));
+		assert_noop!(
+			StakingPoolModule::request_redeem(&BOB, 1000, Rate::zero()),
+			Error::<Runtime>::RedeemQueueFull,
+		);
+
+		assert_noop!(
+			StakingPoolModule::cancel_redeem_request(&BOB, 0),
+			Error::<Runtime>::RedeemRequestNotFound,
+		);
+		assert_ok!(StakingPoolModule::cancel_redeem_request(&ALICE, 0));
+		let cancelled_event = TestEvent::staking_pool(RawEvent::RedeemRequestCancelled(ALICE, 0, 1000));
+		assert!(System::events().iter().any(|record| record.event == cancelled_event));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4000);
+		assert_eq!(StakingPoolModule::redeem_queue(), vec![1, 2]);
+		assert_eq!(StakingPoolModule::redeem_requests(0), None);
+		assert_eq!(StakingPoolModule::redeem_requests_count(ALICE), 1);
+	});
+}
+
+#[test]
+fn process_redeem_queue_in_fifo_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(700);
+		FreeUnbonded::put(300);
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 300));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 5000));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 5000));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&ALICE,
+			5000,
+			Rate::saturating_from_rational(10, 100)
+		));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&BOB,
+			3000,
+			Rate::saturating_from_rational(10, 100)
+		));
+
+		CurrentEra::put(1);
+		assert_eq!(
+			StakingPoolModule::redeem_request_fee_rate(0),
+			Rate::saturating_from_rational(8, 100)
+		);
+		StakingPoolModule::process_redeem_queue();
+
+		let filled_event = TestEvent::staking_pool(RawEvent::RedeemRequestFilled(ALICE, 0, 260, 3000, 300));
+		assert!(System::events().iter().any(|record| record.event == filled_event));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 1300);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1000);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(0).map(|request| request.remaining),
+			Some(1740)
+		);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(1).map(|request| request.remaining),
+			Some(3000)
+		);
+		assert_eq!(StakingPoolModule::redeem_queue(), vec![0, 1]);
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 52);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 10000 - 3260);
+	});
+}
+
+#[test]
+fn process_redeem_queue_skip_request_with_low_max_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(700);
+		FreeUnbonded::put(300);
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 300));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 5000));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 5000));
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 2000, Rate::zero()));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&BOB,
+			3000,
+			Rate::saturating_from_rational(10, 100)
+		));
+
+		CurrentEra::put(1);
+		StakingPoolModule::process_redeem_queue();
+
+		let filled_event = TestEvent::staking_pool(RawEvent::RedeemRequestFilled(BOB, 1, 240, 2760, 276));
+		assert!(System::events().iter().any(|record| record.event == filled_event));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1276);
+		assert_eq!(StakingPoolModule::free_unbonded(), 24);
+		assert_eq!(StakingPoolModule::redeem_requests(1), None);
+		assert_eq!(StakingPoolModule::redeem_requests_count(BOB), 0);
+		assert_eq!(StakingPoolModule::redeem_queue(), vec![0]);
+
+		// the shortfall of remaining requests is unbonded at next era
+		assert_eq!(StakingPoolModule::next_era_unbond(), (182, 0));
+	});
+}
+
+#[test]
+fn process_redeem_queue_pays_nothing_if_burn_failed() {
+	ExtBuilder::default().build().execute_with(|| {
+		TotalBonded::put(700);
+		FreeUnbonded::put(300);
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 300));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 5000));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&BOB,
+			3000,
+			Rate::saturating_from_rational(10, 100)
+		));
+		// the liquid currency kept by staking pool is not enough to burn
+		assert_ok!(CurrenciesModule::withdraw(LDOT, &StakingPoolModule::account_id(), 2000));
+
+		CurrentEra::put(1);
+		StakingPoolModule::process_redeem_queue();
+
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1000);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			300
+		);
+		assert_eq!(StakingPoolModule::free_unbonded(), 300);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(0).map(|request| request.remaining),
+			Some(3000)
+		);
+	});
+}
+
+#[test]
+fn get_redeem_requests_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		TotalBonded::put(1000);
+		NextEraUnbond::put((200, 0));
+		Unbonding::insert(2, (300, 100));
+		UnbondingToFree::put(200);
+		FreeUnbonded::put(100);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 6000));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 5000));
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 500, Rate::zero()));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 2000, Rate::zero()));
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 3000, Rate::zero()));
+
+		assert_eq!(
+			StakingPoolModule::get_redeem_requests(&ALICE),
+			vec![(0, 0, 500, Some(1)), (2, 2, 3000, Some(5))]
+		);
+		assert_eq!(
+			StakingPoolModule::get_redeem_requests(&BOB),
+			vec![(1, 1, 2000, Some(2))]
+		);
+	});
+}
+
+#[test]
+fn mint_fill_redeem_requests_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		TotalBonded::put(1000);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 10000));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&BOB,
+			3000,
+			Rate::saturating_from_rational(10, 100)
+		));
+		assert_eq!(
+			StakingPoolModule::redeem_request_fee_rate(0),
+			Rate::saturating_from_rational(10, 100)
+		);
+
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100), Ok(1044));
+		let filled_event = TestEvent::staking_pool(RawEvent::RedeemRequestFilled(BOB, 0, 111, 1000, 100));
+		assert!(System::events().iter().any(|record| record.event == filled_event));
+		let rebate_event = TestEvent::staking_pool(RawEvent::MintFilledRedeemRequest(ALICE, 0, 100, 44));
+		assert!(System::events().iter().any(|record| record.event == rebate_event));
+		let mint_liquid_event = TestEvent::staking_pool(RawEvent::MintLiquid(ALICE, 100, 1044));
+		assert!(System::events().iter().any(|record| record.event == mint_liquid_event));
+
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 900);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1100);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 1044);
+		assert_eq!(StakingPoolModule::free_unbonded(), 0);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(0).map(|request| request.remaining),
+			Some(1889)
+		);
+		assert_eq!(TOTAL_COMMISSION.with(|v| *v.borrow_mut()), 22);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 10000 - 111 + 44);
+	});
+}
+
+#[test]
+fn mint_fill_redeem_requests_limited_per_mint() {
+	ExtBuilder::default().build().execute_with(|| {
+		TotalBonded::put(1000);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &BOB, 10000));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &2, 10000));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 1000, Rate::zero()));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 1000, Rate::zero()));
+		assert_ok!(StakingPoolModule::request_redeem(
+			&2,
+			3000,
+			Rate::saturating_from_rational(10, 100)
+		));
+
+		// the requests out of the first two are not visited
+		assert_ok!(StakingPoolModule::mint(&ALICE, 100));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &2), 0);
+		assert_eq!(StakingPoolModule::free_unbonded(), 100);
+		assert_eq!(
+			StakingPoolModule::redeem_requests(2).map(|request| request.remaining),
+			Some(3000)
+		);
+	});
+}
+
+#[test]
+fn unbond_and_update_net_bond_and_unbond() {
+	ExtBuilder::default().build().execute_with(|| {
+		TotalBonded::put(1000);
+		FreeUnbonded::put(500);
+		NextEraUnbond::put((300, 100));
+		mock_bridge_ledger(0, 1000, vec![]);
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 500));
+
+		CurrentEra::put(1);
+		StakingPoolModule::unbond_and_update(1, 400);
+		assert_eq!(StakingPoolModule::next_era_unbond(), (0, 0));
+		assert_eq!(StakingPoolModule::total_bonded(), 1100);
+		assert_eq!(StakingPoolModule::free_unbonded(), 100);
+		assert_eq!(StakingPoolModule::unbonding(5), (300, 100));
+		assert_eq!(StakingPoolModule::unbonding_to_free(), 200);
+		assert_eq!(StakingPoolModule::netted_unbonding(5), 300);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			400
+		);
+
+		// the netted unbonding is released at the unbonded era
+		CurrentEra::put(5);
+		StakingPoolModule::rebalance(5);
+		assert_eq!(StakingPoolModule::netted_unbonding(5), 0);
+		assert_eq!(StakingPoolModule::unbonding(5), (0, 0));
+		assert_eq!(StakingPoolModule::total_claimed_unbonded(), 100);
+		assert_eq!(StakingPoolModule::free_unbonded(), 300);
+		assert_eq!(StakingPoolModule::unbonding_to_free(), 0);
+		assert_eq!(StakingPoolModule::total_bonded(), 1100);
+	});
+}
+
+#[test]
+fn bond_spread_across_sub_accounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_bridge_ledger(0, 300, vec![]);
+		assert_eq!(StakingPoolModule::distribute_bond(200), vec![(1, 200)]);
+		assert_eq!(StakingPoolModule::distribute_bond(301), vec![(1, 301)]);
+		assert_eq!(StakingPoolModule::distribute_bond(500), vec![(1, 400), (0, 100)]);
+
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(5000));
+		assert_ok!(StakingPoolModule::bond(500));
+		assert_eq!(StakingPoolModule::total_bonded(), 500);
+		assert_eq!(MockBridge::ledger(0).active, 400);
+		assert_eq!(MockBridge::ledger(1).active, 400);
+	});
+}
+
+#[test]
+fn unbond_from_sub_account_with_largest_active() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_bridge_ledger(0, 1000, vec![PolkadotUnlockChunk { value: 200, era: 3 }]);
+		mock_bridge_ledger(1, 500, vec![]);
+		TotalBonded::put(1500);
+		NextEraUnbond::put((700, 0));
+
+		CurrentEra::put(1);
+		StakingPoolModule::unbond_and_update(1, 0);
+		assert_eq!(StakingPoolModule::next_era_unbond(), (0, 0));
+		assert_eq!(StakingPoolModule::total_bonded(), 800);
+		assert_eq!(
+			MockBridge::ledger(0),
+			PolkadotStakingLedger {
+				total: 1200,
+				active: 300,
+				unlocking: vec![
+					PolkadotUnlockChunk { value: 200, era: 3 },
+					PolkadotUnlockChunk { value: 700, era: 5 }
+				],
+			}
+		);
+		assert_eq!(MockBridge::ledger(1).active, 500);
+		assert_eq!(
+			StakingPoolModule::get_bridge_ledger(),
+			PolkadotStakingLedger {
+				total: 1700,
+				active: 800,
+				unlocking: vec![
+					PolkadotUnlockChunk { value: 200, era: 3 },
+					PolkadotUnlockChunk { value: 700, era: 5 }
+				],
+			}
+		);
+
+		// keep next era unbond if the active at bridge is not enough
+		NextEraUnbond::put((1000, 0));
+		StakingPoolModule::unbond_and_update(1, 0);
+		assert_eq!(StakingPoolModule::next_era_unbond(), (1000, 0));
+		assert_eq!(MockBridge::ledger(0).active, 300);
+	});
+}
@@ -1,19 +1,47 @@
 use super::*;
-use frame_support::{traits::Get, Parameter};
+use frame_support::{traits::Get, weights::Weight, Parameter};
 use sp_runtime::{
 	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member},
-	RuntimeDebug,
+	Perbill, RuntimeDebug,
 };
 
-#[impl_trait_for_tuples::impl_for_tuples(30)]
 pub trait OnNewEra<EraIndex> {
-	fn on_new_era(era: EraIndex);
+	/// Handle the new era, return the consumed weight.
+	fn on_new_era(era: EraIndex) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<EraIndex: Clone> OnNewEra<EraIndex> for Tuple {
+	fn on_new_era(era: EraIndex) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_new_era(era.clone())); )* );
+		weight
+	}
 }
 
 pub trait NomineesProvider<AccountId> {
 	fn nominees() -> Vec<AccountId>;
 }
 
+/// The performance of a validator at relay chain in an era.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct ValidatorPerformance {
+	/// The commission of the validator.
+	pub commission: Perbill,
+	/// The era points earned by the validator.
+	pub era_points: u32,
+	/// Whether the validator is slashed in the era.
+	pub slashed: bool,
+}
+
+pub trait OnValidatorPerformance<PolkadotAccountId, EraIndex> {
+	fn on_validator_performance(era: EraIndex, performances: Vec<(PolkadotAccountId, ValidatorPerformance)>);
+}
+
+impl<PolkadotAccountId, EraIndex> OnValidatorPerformance<PolkadotAccountId, EraIndex> for () {
+	fn on_validator_performance(_era: EraIndex, _performances: Vec<(PolkadotAccountId, ValidatorPerformance)>) {}
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct PolkadotUnlockChunk<Balance, EraIndex> {
 	pub value: Balance,
@@ -27,28 +55,33 @@ pub struct PolkadotStakingLedger<Balance, EraIndex> {
 	pub unlocking: Vec<PolkadotUnlockChunk<Balance, EraIndex>>,
 }
 
+/// The index of the sub-account staking at relay chain on behalf of bridge.
+pub type SubAccountIndex = u32;
+
 pub trait PolkadotBridgeType<BlockNumber, EraIndex> {
 	type BondingDuration: Get<EraIndex>;
 	type EraLength: Get<BlockNumber>;
 	type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
+	/// The number of sub-accounts, indexed from 0.
+	type SubAccountCount: Get<SubAccountIndex>;
 }
 
 pub trait PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex>:
 	PolkadotBridgeType<BlockNumber, EraIndex>
 {
-	fn bond_extra(amount: Balance) -> DispatchResult;
-	fn unbond(amount: Balance) -> DispatchResult;
-	fn rebond(amount: Balance) -> DispatchResult;
-	fn withdraw_unbonded();
-	fn nominate(targets: Vec<Self::PolkadotAccountId>);
-	fn transfer_to_bridge(from: &AccountId, amount: Balance) -> DispatchResult;
-	fn receive_from_bridge(to: &AccountId, amount: Balance) -> DispatchResult;
-	fn payout_nominator();
+	fn bond_extra(account_index: SubAccountIndex, amount: Balance) -> DispatchResult;
+	fn unbond(account_index: SubAccountIndex, amount: Balance) -> DispatchResult;
+	fn rebond(account_index: SubAccountIndex, amount: Balance) -> DispatchResult;
+	fn withdraw_unbonded(account_index: SubAccountIndex);
+	fn nominate(account_index: SubAccountIndex, targets: Vec<Self::PolkadotAccountId>);
+	fn transfer_to_bridge(from: &AccountId, to: SubAccountIndex, amount: Balance) -> DispatchResult;
+	fn receive_from_bridge(to: &AccountId, from: SubAccountIndex, amount: Balance) -> DispatchResult;
+	fn payout_nominator(account_index: SubAccountIndex);
 }
 
 pub trait PolkadotBridgeState<Balance, EraIndex> {
-	fn ledger() -> PolkadotStakingLedger<Balance, EraIndex>;
-	fn balance() -> Balance;
+	fn ledger(account_index: SubAccountIndex) -> PolkadotStakingLedger<Balance, EraIndex>;
+	fn balance(account_index: SubAccountIndex) -> Balance;
 	fn current_era() -> EraIndex;
 }
 
@@ -57,6 +90,24 @@ pub trait PolkadotBridge<AccountId, BlockNumber, Balance, EraIndex>:
 {
 }
 
+/// The handler of the results of bridge calls, which are confirmed by relay
+/// chain asynchronously.
+pub trait OnPolkadotBridgeResult<AccountId, Balance> {
+	/// The staking reward is paid out to the sub-account.
+	fn on_payout(account_index: SubAccountIndex, reward: Balance);
+	/// The transfer from `from` to the sub-account failed and was refunded.
+	fn on_transfer_to_bridge_failed(from: &AccountId, account_index: SubAccountIndex, amount: Balance);
+	/// The transfer from the sub-account to `to` is settled, the staking
+	/// currency is deposited to `to` only if `success`.
+	fn on_receive_from_bridge(to: &AccountId, account_index: SubAccountIndex, amount: Balance, success: bool);
+}
+
+impl<AccountId, Balance> OnPolkadotBridgeResult<AccountId, Balance> for () {
+	fn on_payout(_account_index: SubAccountIndex, _reward: Balance) {}
+	fn on_transfer_to_bridge_failed(_from: &AccountId, _account_index: SubAccountIndex, _amount: Balance) {}
+	fn on_receive_from_bridge(_to: &AccountId, _account_index: SubAccountIndex, _amount: Balance, _success: bool) {}
+}
+
 pub trait OnCommission<Balance, CurrencyId> {
 	fn on_commission(currency_id: CurrencyId, amount: Balance);
 }
@@ -65,6 +116,9 @@ impl<Balance, CurrencyId> OnCommission<Balance, CurrencyId> for () {
 	fn on_commission(_currency_id: CurrencyId, _amount: Balance) {}
 }
 
+/// Redeem request ID
+pub type RedeemRequestId = u32;
+
 pub trait HomaProtocol<AccountId, Balance, EraIndex> {
 	type Balance: Decode + Encode + Debug + Eq + PartialEq + Clone + HasCompact;
 
@@ -73,4 +127,18 @@ pub trait HomaProtocol<AccountId, Balance, EraIndex> {
 	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult;
 	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError>;
+	fn request_redeem(
+		who: &AccountId,
+		amount: Balance,
+		max_fee_rate: Rate,
+	) -> sp_std::result::Result<RedeemRequestId, DispatchError>;
+	fn cancel_redeem_request(who: &AccountId, request_id: RedeemRequestId) -> DispatchResult;
+	/// The weight of `mint`, which fills a bounded number of redeem requests.
+	fn mint_weight() -> Weight;
+	/// The weight of `request_redeem`, which decodes the whole bounded redeem
+	/// queue.
+	fn request_redeem_weight() -> Weight;
+	/// The weight of `cancel_redeem_request`, which scans the whole bounded
+	/// redeem queue.
+	fn cancel_redeem_request_weight() -> Weight;
 }
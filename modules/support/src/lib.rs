@@ -10,8 +10,9 @@ use sp_std::{
 
 pub mod homa;
 pub use homa::{
-	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk,
+	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, OnPolkadotBridgeResult, OnValidatorPerformance,
+	PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
+	PolkadotUnlockChunk, RedeemRequestId, SubAccountIndex, ValidatorPerformance,
 };
 
 pub type Price = FixedU128;
@@ -67,6 +68,7 @@ pub trait AuctionManager<AccountId> {
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult;
 
 	fn get_total_collateral_in_auction(id: Self::CurrencyId) -> Self::Balance;
+	fn get_collateral_auctions_count(id: Self::CurrencyId) -> u32;
 	fn get_total_surplus_in_auction() -> Self::Balance;
 	fn get_total_debit_in_auction() -> Self::Balance;
 	fn get_total_target_in_auction() -> Self::Balance;
@@ -100,6 +102,10 @@ pub trait DEXManager<AccountId, CurrencyId, Balance> {
 	) -> Option<Ratio>;
 
 	fn get_liquidity_pool(currency_id: CurrencyId) -> (Balance, Balance);
+
+	fn get_shares(currency_id: CurrencyId, who: &AccountId) -> Balance;
+
+	fn withdraw_liquidity(who: AccountId, currency_id: CurrencyId, share_amount: Balance) -> DispatchResult;
 }
 
 impl<AccountId, CurrencyId, Balance> DEXManager<AccountId, CurrencyId, Balance> for ()
@@ -143,6 +149,14 @@ where
 	fn get_liquidity_pool(_currency_id: CurrencyId) -> (Balance, Balance) {
 		Default::default()
 	}
+
+	fn get_shares(_currency_id: CurrencyId, _who: &AccountId) -> Balance {
+		Default::default()
+	}
+
+	fn withdraw_liquidity(_who: AccountId, _currency_id: CurrencyId, _share_amount: Balance) -> DispatchResult {
+		Ok(())
+	}
 }
 
 /// An abstraction of cdp treasury for Honzon Protocol.
@@ -208,6 +222,31 @@ pub trait PriceProvider<CurrencyId> {
 	fn unlock_price(currency_id: CurrencyId);
 }
 
+impl<CurrencyId> PriceProvider<CurrencyId> for () {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
+/// The prefix of the offchain index keys of the price history.
+pub const OFFCHAIN_PRICE_HISTORY_PREFIX: &[u8] = b"acala/prices/history/";
+
+/// The offchain index key of the price of `currency_id` at `block_number`.
+pub fn offchain_price_key<CurrencyId: Encode, BlockNumber: Encode>(
+	currency_id: &CurrencyId,
+	block_number: &BlockNumber,
+) -> Vec<u8> {
+	(OFFCHAIN_PRICE_HISTORY_PREFIX, currency_id, block_number).encode()
+}
+
 pub trait ExchangeRateProvider {
 	fn get_exchange_rate() -> ExchangeRate;
 }
@@ -215,3 +254,36 @@ pub trait ExchangeRateProvider {
 pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
+
+impl EmergencyShutdown for () {
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
+/// Per-collateral circuit breaker. A frozen collateral's CDPs cannot mint,
+/// withdraw or be liquidated, while the rest of the system keeps running.
+pub trait CollateralCircuitBreaker<CurrencyId> {
+	fn is_frozen(currency_id: CurrencyId) -> bool;
+}
+
+impl<CurrencyId> CollateralCircuitBreaker<CurrencyId> for () {
+	fn is_frozen(_currency_id: CurrencyId) -> bool {
+		false
+	}
+}
+
+/// Safety checks of the bridged assets. A bridge mints and burns the bridged
+/// assets through it, so that mints and burns are rate limited, can be paused
+/// and large mints are delayed.
+pub trait BridgeSafety<AccountId, CurrencyId, Balance> {
+	/// Check whether the mint is allowed, without changing any state.
+	fn ensure_can_mint(currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Mint `amount` to `who`. A large mint is delayed instead of minted at
+	/// once.
+	fn mint(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Burn `amount` from `who`.
+	fn burn(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+}
@@ -73,6 +73,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
 	pub grandpa: GrandpaDeps<B>,
+	/// The backend instance to use.
+	pub backend: Arc<B>,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -80,13 +82,25 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: sc_client_api::ProofProvider<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>,
+	C::Api: module_accounts_rpc::AccountsRuntimeApi<Block, AccountId>,
 	C::Api: module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>,
+	C::Api: module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>,
+	C::Api: module_prices_rpc::PricesRuntimeApi<
+		Block,
+		CurrencyId,
+		runtime_common::TimeStampedPrice,
+		DataProviderId,
+		BlockNumber,
+		runtime_common::Price,
+	>,
+	C::Api: ecosystem_renvm_bridge_rpc::RenVmBridgeRuntimeApi<Block, BlockNumber, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
@@ -94,7 +108,11 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use ecosystem_renvm_bridge_rpc::{RenVmBridge, RenVmBridgeApi};
+	use module_accounts_rpc::{Accounts, AccountsApi};
+	use module_cdp_treasury_rpc::{CDPTreasury, CDPTreasuryApi};
 	use module_dex_rpc::{Dex, DexApi};
+	use module_prices_rpc::{Prices, PricesApi};
 	use module_staking_pool_rpc::{StakingPool, StakingPoolApi};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_contracts_rpc::{Contracts, ContractsApi};
@@ -111,6 +129,7 @@ where
 		deny_unsafe,
 		babe,
 		grandpa,
+		backend,
 	} = deps;
 	let BabeDeps {
 		keystore,
@@ -154,7 +173,14 @@ where
 	)));
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(DexApi::to_delegate(Dex::new(client.clone())));
-	io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client)));
+	io.extend_with(CDPTreasuryApi::to_delegate(CDPTreasury::new(client.clone())));
+	io.extend_with(PricesApi::to_delegate(Prices::new(
+		client.clone(),
+		backend.offchain_storage(),
+	)));
+	io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
+	io.extend_with(AccountsApi::to_delegate(Accounts::new(client.clone())));
+	io.extend_with(RenVmBridgeApi::to_delegate(RenVmBridge::new(client)));
 
 	io
 }
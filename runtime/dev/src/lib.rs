@@ -12,7 +12,6 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use codec::Encode;
-use hex_literal::hex;
 use sp_api::impl_runtime_apis;
 use sp_core::{
 	crypto::KeyTypeId,
@@ -29,7 +28,7 @@ use sp_runtime::{
 	generic, impl_opaque_keys,
 	traits::AccountIdConversion,
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, DispatchResult, FixedPointNumber, ModuleId,
+	ApplyExtrinsicResult, FixedPointNumber, ModuleId,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -41,7 +40,7 @@ use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use module_support::OnCommission;
 use orml_currencies::{BasicCurrencyAdapter, Currency};
 use orml_tokens::CurrencyAdapter;
-use orml_traits::{create_median_value_data_provider, currency::MultiCurrency, DataFeeder, DataProviderExtended};
+use orml_traits::{currency::MultiCurrency, DataProviderExtended};
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
 use pallet_evm::{EnsureAddressTruncated, FeeCalculator, HashedAddressMapping};
 use pallet_grandpa::fg_primitives;
@@ -85,10 +84,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("acala"),
 	impl_name: create_runtime_str!("acala"),
 	authoring_version: 1,
-	spec_version: 601,
+	spec_version: 606,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 6,
 };
 
 /// The version infromation used to identify this runtime when compiled
@@ -770,20 +769,31 @@ impl orml_oracle::Trait<BandDataProvider> for Runtime {
 	type WeightInfo = ();
 }
 
-create_median_value_data_provider!(
-	AggregatedDataProvider,
-	CurrencyId,
-	Price,
-	TimeStampedPrice,
-	[AcalaOracle, BandOracle]
-);
-// Aggregated data provider cannot feed.
-impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
-	fn feed_value(_: AccountId, _: CurrencyId, _: Price) -> DispatchResult {
-		Err("Not supported".into())
+pub struct OracleFeedSources;
+impl module_prices::PriceFeedSources<Moment> for OracleFeedSources {
+	fn sources() -> Vec<DataProviderId> {
+		vec![DataProviderId::Acala, DataProviderId::Band]
+	}
+
+	fn keys(source: DataProviderId) -> Vec<CurrencyId> {
+		match source {
+			DataProviderId::Acala => AcalaOracle::get_all_values().into_iter().map(|(key, _)| key).collect(),
+			DataProviderId::Band => BandOracle::get_all_values().into_iter().map(|(key, _)| key).collect(),
+			DataProviderId::Aggregated => vec![],
+		}
+	}
+
+	fn raw_feeds(source: DataProviderId, currency_id: CurrencyId) -> Vec<TimeStampedPrice> {
+		match source {
+			DataProviderId::Acala => AcalaOracle::read_raw_values(&currency_id),
+			DataProviderId::Band => BandOracle::read_raw_values(&currency_id),
+			DataProviderId::Aggregated => vec![],
+		}
 	}
 }
 
+type AggregatedDataProvider = module_prices::AggregatedDataProvider<Runtime>;
+
 impl orml_tokens::Trait for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -795,17 +805,27 @@ impl orml_tokens::Trait for Runtime {
 
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
+	pub const DexTwapPeriod: u32 = 10 * MINUTES;
+	pub PriceHistoryCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::DOT, CurrencyId::XBTC, CurrencyId::LDOT, CurrencyId::RENBTC];
+	pub const PriceHistoryLength: u32 = 100;
 }
 
 impl module_prices::Trait for Runtime {
 	type Event = Event;
 	type Source = AggregatedDataProvider;
+	type Time = Timestamp;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureRootOrHalfGeneralCouncil;
 	type LiquidStakingExchangeRateProvider = LiquidStakingExchangeRateProvider;
+	type DEX = Dex;
+	type DexTwapPeriod = DexTwapPeriod;
+	type FeedSources = OracleFeedSources;
+	type DefaultFeedExpiresIn = ExpiresIn;
+	type PriceHistoryCurrencyIds = PriceHistoryCurrencyIds;
+	type PriceHistoryLength = PriceHistoryLength;
 }
 
 pub struct LiquidStakingExchangeRateProvider;
@@ -897,6 +917,8 @@ parameter_types! {
 	pub const AuctionTimeToClose: BlockNumber = 15 * MINUTES;
 	pub const AuctionDurationSoftCap: BlockNumber = 2 * HOURS;
 	pub const AuctionManagerUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxAutoBidsPerCollateral: u32 = 50;
+	pub const MaxAutoBidAuctionsPerBlock: u32 = 20;
 }
 
 impl module_auction_manager::Trait for Runtime {
@@ -913,6 +935,8 @@ impl module_auction_manager::Trait for Runtime {
 	type PriceSource = Prices;
 	type UnsignedPriority = AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type MaxAutoBidsPerCollateral = MaxAutoBidsPerCollateral;
+	type MaxAutoBidAuctionsPerBlock = MaxAutoBidAuctionsPerBlock;
 }
 
 impl module_loans::Trait for Runtime {
@@ -1018,6 +1042,7 @@ impl module_emergency_shutdown::Trait for Runtime {
 	type Event = Event;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = Prices;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
@@ -1054,21 +1079,21 @@ impl module_cdp_treasury::Trait for Runtime {
 	type DEX = Dex;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type EmergencyShutdown = EmergencyShutdown;
+	type PriceSource = Prices;
 }
 
 parameter_types! {
-	pub const FreeTransferCount: u8 = 3;
-	pub const FreeTransferPeriod: BlockNumber = DAYS;
-	pub const FreeTransferDeposit: Balance = DOLLARS;
 	// All currency types except for native currency, Sort by fee charge order
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::AUSD, CurrencyId::LDOT, CurrencyId::DOT, CurrencyId::XBTC, CurrencyId::RENBTC];
 	pub const NewAccountDeposit: Balance = 100 * MILLICENTS;
+	pub const FreeTransferDeposit: Balance = DOLLARS;
+	pub const FreeTransferCount: u32 = 3;
+	pub const FreeTransferPeriod: Moment = DAYS as Moment * MILLISECS_PER_BLOCK;
 }
 
 impl module_accounts::Trait for Runtime {
-	type FreeTransferCount = FreeTransferCount;
-	type FreeTransferPeriod = FreeTransferPeriod;
-	type FreeTransferDeposit = FreeTransferDeposit;
+	type Event = Event;
 	type Time = Timestamp;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
@@ -1079,6 +1104,10 @@ impl module_accounts::Trait for Runtime {
 	type NewAccountDeposit = NewAccountDeposit;
 	type TreasuryModuleId = AcalaTreasuryModuleId;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type FreeTransferDeposit = FreeTransferDeposit;
+	type FreeTransferCount = FreeTransferCount;
+	type FreeTransferPeriod = FreeTransferPeriod;
 }
 
 impl orml_rewards::Trait for Runtime {
@@ -1097,9 +1126,11 @@ impl module_incentives::Trait for Runtime {
 	type LoansIncentivePool = ZeroAccountId;
 	type DexIncentivePool = ZeroAccountId;
 	type HomaIncentivePool = ZeroAccountId;
+	type NomineesElectionPool = ZeroAccountId;
 	type AccumulatePeriod = AccumulatePeriod;
 	type IncentiveCurrencyId = GetNativeCurrencyId;
 	type SavingCurrencyId = GetStableCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
 	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
 	type CDPTreasury = CdpTreasury;
 	type Currency = Currencies;
@@ -1114,6 +1145,8 @@ impl module_airdrop::Trait for Runtime {
 parameter_types! {
 	pub const PolkadotBondingDuration: EraIndex = 7;
 	pub const EraLength: BlockNumber = DAYS;
+	pub const PolkadotSubAccountCount: u32 = 4;
+	pub const PolkadotMaxPendingMessages: u32 = 32;
 }
 
 impl module_polkadot_bridge::Trait for Runtime {
@@ -1123,6 +1156,12 @@ impl module_polkadot_bridge::Trait for Runtime {
 	type BondingDuration = PolkadotBondingDuration;
 	type EraLength = EraLength;
 	type PolkadotAccountId = AccountId;
+	type SubAccountCount = PolkadotSubAccountCount;
+	type Transport = module_polkadot_bridge::LocalRelaychain<Runtime>;
+	type RelaychainOrigin = EnsureRoot<AccountId>;
+	type OnValidatorPerformance = NomineesElection;
+	type OnResult = StakingPool;
+	type MaxPendingMessages = PolkadotMaxPendingMessages;
 }
 
 parameter_types! {
@@ -1133,6 +1172,12 @@ parameter_types! {
 	pub MaxClaimFee: Rate = Rate::saturating_from_rational(5, 100); // 5%
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub ClaimFeeReturnRatio: Ratio = Ratio::saturating_from_rational(98, 100); // 98%
+	pub RewardFeeRatio: Ratio = Ratio::saturating_from_rational(10, 100); // 10%
+	pub const MaxRedeemRequests: u32 = 1000;
+	pub const MaxRedeemRequestsPerMint: u32 = 20;
+	pub const MaxRedeemRequestsPerAccount: u32 = 10;
+	pub const MinRedeemRequestAmount: Balance = 10 * DOLLARS;
+	pub MintRebateRatio: Ratio = Ratio::saturating_from_rational(50, 100); // 50%
 }
 
 impl module_staking_pool::Trait for Runtime {
@@ -1141,7 +1186,7 @@ impl module_staking_pool::Trait for Runtime {
 	type StakingCurrencyId = GetStakingCurrencyId;
 	type LiquidCurrencyId = GetLiquidCurrencyId;
 	type Nominees = NomineesElection;
-	type OnCommission = DealWithCommission<HomaTreasuryModuleId>;
+	type OnCommission = NomineesElection;
 	type Bridge = PolkadotBridge;
 	type MaxBondRatio = MaxBondRatio;
 	type MinBondRatio = MinBondRatio;
@@ -1149,6 +1194,12 @@ impl module_staking_pool::Trait for Runtime {
 	type DefaultExchangeRate = DefaultExchangeRate;
 	type ClaimFeeReturnRatio = ClaimFeeReturnRatio;
 	type ModuleId = StakingPoolModuleId;
+	type RewardFeeRatio = RewardFeeRatio;
+	type MaxRedeemRequests = MaxRedeemRequests;
+	type MaxRedeemRequestsPerMint = MaxRedeemRequestsPerMint;
+	type MaxRedeemRequestsPerAccount = MaxRedeemRequestsPerAccount;
+	type MinRedeemRequestAmount = MinRedeemRequestAmount;
+	type MintRebateRatio = MintRebateRatio;
 }
 
 impl module_homa::Trait for Runtime {
@@ -1160,15 +1211,32 @@ parameter_types! {
 	pub const NominateesCount: usize = 7;
 	pub const MaxUnlockingChunks: usize = 7;
 	pub const NomineesElectionBondingDuration: EraIndex = 7;
+	pub const MaxVotesPerValidator: Balance = 1_000_000 * DOLLARS;
+	pub const SlashExclusionDuration: EraIndex = 28;
+	pub const MaxDelegationDepth: u32 = 3;
+	pub const NomineesElectionRewardPoolId: module_incentives::PoolId = module_incentives::PoolId::NomineesElection;
+	pub NomineesElectionRewardRatio: Ratio = Ratio::saturating_from_rational(20, 100); // 20%
+	pub const MaxRewardShareUpdatesPerBlock: u32 = 50;
 }
 
 impl module_nominees_election::Trait for Runtime {
+	type Event = Event;
 	type Currency = Currency<Runtime, GetLiquidCurrencyId>;
 	type PolkadotAccountId = AccountId;
 	type MinBondThreshold = MinCouncilBondThreshold;
 	type BondingDuration = NomineesElectionBondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxVotesPerValidator = MaxVotesPerValidator;
+	type SlashExclusionDuration = SlashExclusionDuration;
+	type UpdateOrigin = EnsureRootOrHalfHomaCouncil;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type RewardPoolId = NomineesElectionRewardPoolId;
+	type RewardPool = ZeroAccountId;
+	type RewardRatio = NomineesElectionRewardRatio;
+	type MaxRewardShareUpdatesPerBlock = MaxRewardShareUpdatesPerBlock;
+	type OnCommission = DealWithCommission<HomaTreasuryModuleId>;
 }
 
 parameter_types! {
@@ -1220,20 +1288,40 @@ impl pallet_proxy::Trait for Runtime {
 }
 
 parameter_types! {
-	pub const RENBTCCurrencyId: CurrencyId = CurrencyId::RENBTC;
-	pub const RenVmPublickKey: [u8; 20] = hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"];
-	pub const RENBTCIdentifier: [u8; 32] = hex!["0000000000000000000000000a9add98c076448cbcfacf5e457da12ddbef4a8f"];
+	pub const BridgeSafetyWindowLength: BlockNumber = DAYS;
+	pub const BridgeSafetyMintDelay: BlockNumber = 6 * HOURS;
+	pub const BridgeSafetyMaxPendingMintsPerBlock: u32 = 20;
+}
+
+impl module_bridge_safety::Trait for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type WindowLength = BridgeSafetyWindowLength;
+	type MintDelay = BridgeSafetyMintDelay;
+	type MaxPendingMintsPerBlock = BridgeSafetyMaxPendingMintsPerBlock;
+	type GuardianOrigin = EnsureRootOrOneThirdsTechnicalCommittee;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+}
+
+parameter_types! {
 	pub const RenvmBridgeUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 3;
 	pub const BurnEventStoreDuration: BlockNumber = DAYS;
+	pub const RenVmSignersOverlapDuration: BlockNumber = DAYS;
+	pub const RenVmLegacyAsset: (CurrencyId, [u8; 32], [u8; 20]) = (
+		CurrencyId::RENBTC,
+		hex_literal::hex!["0000000000000000000000000a9add98c076448cbcfacf5e457da12ddbef4a8f"],
+		hex_literal::hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"],
+	);
 }
 
 impl ecosystem_renvm_bridge::Trait for Runtime {
 	type Event = Event;
-	type Currency = Currency<Runtime, RENBTCCurrencyId>;
-	type PublicKey = RenVmPublickKey;
-	type CurrencyIdentifier = RENBTCIdentifier;
+	type BridgeSafety = BridgeSafety;
 	type UnsignedPriority = RenvmBridgeUnsignedPriority;
 	type BurnEventStoreDuration = BurnEventStoreDuration;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type SignersOverlapDuration = RenVmSignersOverlapDuration;
+	type LegacyAsset = RenVmLegacyAsset;
 }
 
 parameter_types! {
@@ -1351,18 +1439,19 @@ construct_runtime!(
 		Dex: module_dex::{Module, Storage, Call, Event<T>},
 		CdpTreasury: module_cdp_treasury::{Module, Storage, Call, Config, Event},
 		CdpEngine: module_cdp_engine::{Module, Storage, Call, Event<T>, Config, ValidateUnsigned},
-		EmergencyShutdown: module_emergency_shutdown::{Module, Storage, Call, Event<T>},
-		Accounts: module_accounts::{Module, Call, Storage},
+		EmergencyShutdown: module_emergency_shutdown::{Module, Storage, Call, Config<T>, Event<T>},
+		Accounts: module_accounts::{Module, Call, Storage, Event<T>, Config},
 		Incentives: module_incentives::{Module, Storage, Call},
 		AirDrop: module_airdrop::{Module, Call, Storage, Event<T>, Config<T>},
 		Homa: module_homa::{Module, Call},
-		NomineesElection: module_nominees_election::{Module, Call, Storage},
+		NomineesElection: module_nominees_election::{Module, Call, Storage, Event<T>},
 		StakingPool: module_staking_pool::{Module, Call, Storage, Event<T>},
 		PolkadotBridge: module_polkadot_bridge::{Module, Call, Storage, Event<T>, Config},
 		NFT: module_nft::{Module, Call, Event<T>},
+		BridgeSafety: module_bridge_safety::{Module, Call, Storage, Event<T>, Config},
 
 		// ecosystem modules
-		RenVmBridge: ecosystem_renvm_bridge::{Module, Call, Storage, Event<T>, ValidateUnsigned},
+		RenVmBridge: ecosystem_renvm_bridge::{Module, Call, Config, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -1553,7 +1642,20 @@ impl_runtime_apis! {
 		Balance,
 	> for Runtime {
 		fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			let fee_swap_path = uxt
+				.signature
+				.as_ref()
+				.and_then(|(_, _, extra)| extra.6.fee_swap_path().map(|path| path.to_vec()));
+			let mut info = TransactionPayment::query_info(uxt, len);
+
+			// quote the fee in the fee currency if it's specified, keep the fee in native
+			// currency if it can not be quoted.
+			if let Some(path) = fee_swap_path {
+				if let Some(fee) = Accounts::quote_fee(&path, info.partial_fee) {
+					info.partial_fee = fee;
+				}
+			}
+			info
 		}
 	}
 
@@ -1606,6 +1708,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_cdp_treasury_rpc_runtime_api::CDPTreasuryApi<
+		Block,
+		Balance,
+	> for Runtime {
+		fn get_treasury_health() -> module_cdp_treasury_rpc_runtime_api::TreasuryHealth<Balance> {
+			module_cdp_treasury_rpc_runtime_api::TreasuryHealth {
+				surplus_pool: CdpTreasury::surplus_pool(),
+				debit_pool: CdpTreasury::debit_pool(),
+				total_surplus_in_auction: AuctionManager::total_surplus_in_auction(),
+				total_debit_in_auction: AuctionManager::total_debit_in_auction(),
+				surplus_buffer_size: CdpTreasury::surplus_buffer_size(),
+				excessive_surplus: CdpTreasury::excessive_surplus(),
+				unhandled_debit: CdpTreasury::unhandled_debit(),
+			}
+		}
+	}
+
+	impl module_prices_rpc_runtime_api::PricesApi<
+		Block,
+		CurrencyId,
+		TimeStampedPrice,
+		DataProviderId,
+		BlockNumber,
+		Price,
+	> for Runtime {
+		fn get_aggregated_price(
+			currency_id: CurrencyId,
+		) -> Option<module_prices_rpc_runtime_api::AggregatedPrice<TimeStampedPrice, DataProviderId>> {
+			Prices::aggregated_price(currency_id).map(|(price, sources)| {
+				module_prices_rpc_runtime_api::AggregatedPrice { price, sources }
+			})
+		}
+
+		fn get_price_at(currency_id: CurrencyId, block_number: BlockNumber) -> Option<Price> {
+			Prices::price_at(currency_id, block_number)
+		}
+	}
+
 	impl module_staking_pool_rpc_runtime_api::StakingPoolApi<
 		Block,
 		AccountId,
@@ -1620,6 +1760,80 @@ impl_runtime_apis! {
 		fn get_liquid_staking_exchange_rate() -> ExchangeRate {
 			StakingPool::liquid_exchange_rate()
 		}
+
+		fn get_redeem_requests(
+			account: AccountId,
+		) -> Vec<module_staking_pool_rpc_runtime_api::RedeemRequestStatus<Balance>> {
+			StakingPool::get_redeem_requests(&account)
+				.into_iter()
+				.map(|(request_id, position, remaining, expected_era)| {
+					module_staking_pool_rpc_runtime_api::RedeemRequestStatus {
+						request_id,
+						position,
+						remaining,
+						expected_era,
+					}
+				})
+				.collect()
+		}
+
+		fn get_sub_account_ledgers() -> Vec<module_staking_pool_rpc_runtime_api::SubAccountLedger<Balance>> {
+			StakingPool::get_sub_account_ledgers()
+				.into_iter()
+				.map(|(account_index, ledger)| module_staking_pool_rpc_runtime_api::SubAccountLedger {
+					account_index,
+					total: ledger.total,
+					active: ledger.active,
+					unlocking: ledger
+						.unlocking
+						.into_iter()
+						.map(|chunk| module_staking_pool_rpc_runtime_api::UnlockChunk {
+							value: chunk.value,
+							era: chunk.era,
+						})
+						.collect(),
+				})
+				.collect()
+		}
+	}
+
+	impl module_accounts_rpc_runtime_api::AccountsApi<
+		Block,
+		AccountId,
+	> for Runtime {
+		fn get_remaining_free_calls(account: AccountId) -> Vec<module_accounts_rpc_runtime_api::FreeCallInfo> {
+			Accounts::get_remaining_free_calls(&account)
+				.into_iter()
+				.map(|((pallet_name, function_name), remaining)| module_accounts_rpc_runtime_api::FreeCallInfo {
+					pallet_name,
+					function_name,
+					remaining,
+				})
+				.collect()
+		}
+	}
+
+	impl ecosystem_renvm_bridge_rpc_runtime_api::RenVmBridgeApi<
+		Block,
+		BlockNumber,
+		Balance,
+	> for Runtime {
+		fn get_pending_burns(
+			from_nonce: u64,
+			limit: u32,
+		) -> Vec<ecosystem_renvm_bridge_rpc_runtime_api::BurnInfo<BlockNumber, Balance>> {
+			RenVmBridge::get_pending_burns(from_nonce, limit)
+				.into_iter()
+				.map(|(nonce, details, storage_key)| ecosystem_renvm_bridge_rpc_runtime_api::BurnInfo {
+					nonce,
+					block_number: details.block_number,
+					currency_id: details.currency_id,
+					to: details.to,
+					amount: details.amount,
+					storage_key,
+				})
+				.collect()
+		}
 	}
 
 	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber>
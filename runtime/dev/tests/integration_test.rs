@@ -181,20 +181,38 @@ fn emergency_shutdown_and_cdp_treasury() {
 			assert_eq!(CdpTreasuryModule::total_collaterals(CurrencyId::DOT), 200_000_000);
 			assert_eq!(CdpTreasuryModule::total_collaterals(CurrencyId::LDOT), 40_000_000);
 
+			assert_ok!(set_oracle_price(vec![
+				(CurrencyId::XBTC, Price::saturating_from_integer(10000)),
+				(CurrencyId::DOT, Price::saturating_from_integer(100)),
+			]));
+
 			assert_noop!(
-				EmergencyShutdownModule::refund_collaterals(origin_of(AccountId::from(ALICE)), 1_000_000),
+				EmergencyShutdownModule::pack(origin_of(AccountId::from(ALICE)), 1_000_000),
 				module_emergency_shutdown::Error::<Runtime>::CanNotRefund,
 			);
 			assert_ok!(EmergencyShutdownModule::emergency_shutdown(
 				<Runtime as frame_system::Trait>::Origin::root()
 			));
+			for currency_id in vec![CurrencyId::XBTC, CurrencyId::DOT, CurrencyId::LDOT] {
+				assert_ok!(EmergencyShutdownModule::fix_cage_price(
+					origin_of(AccountId::from(ALICE)),
+					currency_id
+				));
+			}
 			assert_ok!(EmergencyShutdownModule::open_collateral_refund(
 				<Runtime as frame_system::Trait>::Origin::root()
 			));
-			assert_ok!(EmergencyShutdownModule::refund_collaterals(
+			assert_ok!(EmergencyShutdownModule::pack(
 				origin_of(AccountId::from(ALICE)),
 				1_000_000
 			));
+			for currency_id in vec![CurrencyId::XBTC, CurrencyId::DOT, CurrencyId::LDOT] {
+				assert_ok!(EmergencyShutdownModule::cash(
+					origin_of(AccountId::from(ALICE)),
+					currency_id,
+					1_000_000
+				));
+			}
 
 			assert_eq!(CdpTreasuryModule::total_collaterals(CurrencyId::XBTC), 900_000);
 			assert_eq!(CdpTreasuryModule::total_collaterals(CurrencyId::DOT), 180_000_000);
@@ -316,6 +334,7 @@ fn liquidate_cdp() {
 					amount(10),
 					amount(50_000),
 					LiquidationStrategy::Auction,
+					Price::saturating_from_rational(10000, 1),
 				));
 			assert!(SystemModule::events()
 				.iter()
@@ -344,6 +363,7 @@ fn liquidate_cdp() {
 					amount(1),
 					amount(5_000),
 					LiquidationStrategy::Exchange,
+					Price::saturating_from_rational(10000, 1),
 				));
 			assert!(SystemModule::events()
 				.iter()
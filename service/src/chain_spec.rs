@@ -227,12 +227,13 @@ fn testnet_genesis(
 	enable_println: bool,
 ) -> dev_runtime::GenesisConfig {
 	use dev_runtime::{
-		get_all_module_accounts, AcalaOracleConfig, AirDropConfig, BabeConfig, BalancesConfig, BandOracleConfig,
-		CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, GeneralCouncilMembershipConfig, GrandpaConfig,
-		HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit,
-		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, PolkadotBridgeConfig, SessionConfig, StakerStatus,
-		StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, VestingConfig,
-		DOLLARS,
+		get_all_module_accounts, AcalaOracleConfig, AccountsConfig, AirDropConfig, BabeConfig, BalancesConfig,
+		BandOracleConfig, BridgeSafetyConfig, CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId,
+		EmergencyShutdownConfig, GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig,
+		HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit, OperatorMembershipAcalaConfig,
+		OperatorMembershipBandConfig, PolkadotBridgeConfig, RenVmBridgeConfig, SessionConfig, StakerStatus,
+		StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, VestingConfig, DAYS,
+		DOLLARS, HOURS, MILLISECS_PER_BLOCK,
 	};
 
 	let new_account_deposit = NewAccountDeposit::get();
@@ -332,6 +333,15 @@ fn testnet_genesis(
 				(CurrencyId::XBTC, DOLLARS),
 				(CurrencyId::RENBTC, DOLLARS),
 			],
+			surplus_buffer_size: 1_000 * DOLLARS,
+			initial_amount_per_debit_auction: 2_000 * DOLLARS,
+			debit_auction_fixed_size: 1_000 * DOLLARS,
+			surplus_auction_fixed_size: 2_000 * DOLLARS,
+		}),
+		module_emergency_shutdown: Some(EmergencyShutdownConfig {
+			cage_price_delay: 0,
+			settlement_delay: HOURS,
+			cash_delay: HOURS,
 		}),
 		module_cdp_engine: Some(CdpEngineConfig {
 			collaterals_params: vec![
@@ -376,6 +386,25 @@ fn testnet_genesis(
 		module_airdrop: Some(AirDropConfig {
 			airdrop_accounts: vec![],
 		}),
+		module_accounts: Some(AccountsConfig {
+			free_call_quotas: vec![(
+				(b"Currencies".to_vec(), b"transfer".to_vec()),
+				3,                                 // free calls per period
+				DAYS as u64 * MILLISECS_PER_BLOCK, // period in milliseconds
+				DOLLARS,                           // min deposit
+			)],
+		}),
+		module_bridge_safety: Some(BridgeSafetyConfig {
+			limits: vec![(CurrencyId::RENBTC, 100 * DOLLARS, 100 * DOLLARS, 10 * DOLLARS)],
+		}),
+		ecosystem_renvm_bridge: Some(RenVmBridgeConfig {
+			assets: vec![(
+				CurrencyId::RENBTC,
+				hex!["0000000000000000000000000a9add98c076448cbcfacf5e457da12ddbef4a8f"],
+				vec![hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"]],
+				1,
+			)],
+		}),
 		orml_oracle_Instance1: Some(AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
@@ -396,12 +425,13 @@ fn mandala_genesis(
 	enable_println: bool,
 ) -> dev_runtime::GenesisConfig {
 	use dev_runtime::{
-		get_all_module_accounts, AcalaOracleConfig, AirDropConfig, AirDropCurrencyId, BabeConfig, Balance,
-		BalancesConfig, BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId,
-		GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig,
-		IndicesConfig, NewAccountDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig,
-		PolkadotBridgeConfig, SessionConfig, StakerStatus, StakingConfig, SudoConfig, SystemConfig,
-		TechnicalCommitteeMembershipConfig, TokensConfig, VestingConfig, CENTS, DOLLARS,
+		get_all_module_accounts, AcalaOracleConfig, AccountsConfig, AirDropConfig, AirDropCurrencyId, BabeConfig,
+		Balance, BalancesConfig, BandOracleConfig, BridgeSafetyConfig, CdpEngineConfig, CdpTreasuryConfig,
+		ContractsConfig, CurrencyId, EmergencyShutdownConfig, GeneralCouncilMembershipConfig, GrandpaConfig,
+		HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit,
+		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, PolkadotBridgeConfig, RenVmBridgeConfig,
+		SessionConfig, StakerStatus, StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig,
+		TokensConfig, VestingConfig, CENTS, DAYS, DOLLARS, HOURS, MILLISECS_PER_BLOCK,
 	};
 
 	let new_account_deposit = NewAccountDeposit::get();
@@ -496,6 +526,15 @@ fn mandala_genesis(
 				(CurrencyId::XBTC, 5 * CENTS),
 				(CurrencyId::RENBTC, 5 * CENTS),
 			],
+			surplus_buffer_size: 500 * DOLLARS,
+			initial_amount_per_debit_auction: 2_000 * DOLLARS,
+			debit_auction_fixed_size: 500 * DOLLARS,
+			surplus_auction_fixed_size: 1_000 * DOLLARS,
+		}),
+		module_emergency_shutdown: Some(EmergencyShutdownConfig {
+			cage_price_delay: 0,
+			settlement_delay: DAYS,
+			cash_delay: HOURS,
 		}),
 		module_cdp_engine: Some(CdpEngineConfig {
 			collaterals_params: vec![
@@ -545,6 +584,25 @@ fn mandala_genesis(
 				airdrop_accounts
 			},
 		}),
+		module_accounts: Some(AccountsConfig {
+			free_call_quotas: vec![(
+				(b"Currencies".to_vec(), b"transfer".to_vec()),
+				3,                                 // free calls per period
+				DAYS as u64 * MILLISECS_PER_BLOCK, // period in milliseconds
+				DOLLARS,                           // min deposit
+			)],
+		}),
+		module_bridge_safety: Some(BridgeSafetyConfig {
+			limits: vec![(CurrencyId::RENBTC, 100 * DOLLARS, 100 * DOLLARS, 10 * DOLLARS)],
+		}),
+		ecosystem_renvm_bridge: Some(RenVmBridgeConfig {
+			assets: vec![(
+				CurrencyId::RENBTC,
+				hex!["0000000000000000000000000a9add98c076448cbcfacf5e457da12ddbef4a8f"],
+				vec![hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"]],
+				1,
+			)],
+		}),
 		orml_oracle_Instance1: Some(AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
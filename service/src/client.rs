@@ -1,7 +1,7 @@
 //! Acala Client abstractions.
 
 use acala_primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Nonce};
-use runtime_common::TimeStampedPrice;
+use runtime_common::{Price, TimeStampedPrice};
 use sc_client_api::{Backend as BackendT, BlockchainEvents};
 use sp_api::{CallApiAt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -21,6 +21,10 @@ pub trait RuntimeApiCollection:
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
 	+ module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>
+	+ module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>
+	+ module_prices_rpc::PricesRuntimeApi<Block, CurrencyId, TimeStampedPrice, DataProviderId, BlockNumber, Price>
+	+ ecosystem_renvm_bridge_rpc::RenVmBridgeRuntimeApi<Block, BlockNumber, Balance>
+	+ module_accounts_rpc::AccountsRuntimeApi<Block, AccountId>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -42,6 +46,10 @@ where
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
 		+ module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>
+		+ module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>
+		+ module_prices_rpc::PricesRuntimeApi<Block, CurrencyId, TimeStampedPrice, DataProviderId, BlockNumber, Price>
+		+ ecosystem_renvm_bridge_rpc::RenVmBridgeRuntimeApi<Block, BlockNumber, Balance>
+		+ module_accounts_rpc::AccountsRuntimeApi<Block, AccountId>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>,
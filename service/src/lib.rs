@@ -174,6 +174,7 @@ where
 		let keystore = keystore.clone();
 		let transaction_pool = transaction_pool.clone();
 		let select_chain = select_chain.clone();
+		let backend = backend.clone();
 
 		move |deny_unsafe, subscription_executor| -> acala_rpc::RpcExtension {
 			let deps = acala_rpc::FullDeps {
@@ -193,6 +194,7 @@ where
 					subscription_executor,
 					finality_provider: finality_proof_provider.clone(),
 				},
+				backend: backend.clone(),
 			};
 
 			acala_rpc::create_full(deps)